@@ -0,0 +1,536 @@
+#[cfg(test)]
+mod tests;
+
+use crate::bqtype::{BqType, ScalarType};
+use crate::cst::{Node, NodeType};
+use crate::literal::{decode_string_literal, LiteralValue};
+use crate::validate::{Diagnostic, DiagnosticKind};
+
+// functions whose 2nd argument is a regular expression
+const REGEXP_FUNCTIONS: [&str; 6] = [
+    "REGEXP_CONTAINS",
+    "REGEXP_EXTRACT",
+    "REGEXP_EXTRACT_ALL",
+    "REGEXP_INSTR",
+    "REGEXP_REPLACE",
+    "REGEXP_SUBSTR",
+];
+
+// functions whose 2nd argument is a JSONPath
+const JSONPATH_FUNCTIONS: [&str; 5] = [
+    "JSON_QUERY",
+    "JSON_QUERY_ARRAY",
+    "JSON_STRIP_NULLS",
+    "JSON_VALUE",
+    "JSON_VALUE_ARRAY",
+];
+const LEGACY_JSONPATH_FUNCTIONS: [&str; 4] = [
+    "JSON_EXTRACT",
+    "JSON_EXTRACT_ARRAY",
+    "JSON_EXTRACT_SCALAR",
+    "JSON_EXTRACT_STRING_ARRAY",
+];
+
+// functions whose 1st argument is a format string (e.g. `%Y-%m-%d`)
+const FORMAT_FUNCTIONS: [&str; 8] = [
+    "FORMAT_DATE",
+    "FORMAT_DATETIME",
+    "FORMAT_TIME",
+    "FORMAT_TIMESTAMP",
+    "PARSE_DATE",
+    "PARSE_DATETIME",
+    "PARSE_TIME",
+    "PARSE_TIMESTAMP",
+];
+
+// format elements of CAST(... FORMAT '...'), longer ones first
+const DATETIME_FORMAT_ELEMENTS: [&str; 43] = [
+    "Y,YYY", "SYYYY", "YYYY", "IYYY", "RRRR", "MONTH", "SSSSS", "HH24", "HH12", "A.M.", "P.M.",
+    "YYY", "IYY", "MON", "DDD", "DAY", "TZH", "TZM", "SCC", "FF1", "FF2", "FF3", "FF4", "FF5",
+    "FF6", "FF7", "FF8", "FF9", "YY", "IY", "RR", "MM", "DD", "DY", "HH", "MI", "SS", "AM", "PM",
+    "CC", "IW", "WW", "Y",
+];
+const DATETIME_FORMAT_SINGLE_ELEMENTS: [&str; 5] = ["D", "I", "J", "Q", "W"];
+const NUMERIC_FORMAT_ELEMENTS: [&str; 21] = [
+    "EEEE", "TM9", "TME", "MI", "PR", "RN", "FM", "TM", "0", "9", "X", ",", ".", "$", "C", "L",
+    "D", "G", "B", "S", "V",
+];
+const BYTES_FORMATS: [&str; 10] = [
+    "BASE2", "BASE8", "BASE16", "BASE32", "BASE64", "BASE64M", "HEX", "ASCII", "UTF-8", "UTF8",
+];
+
+/// Checks constant string arguments which are written in a mini-language:
+/// regular expressions of `REGEXP_*`, JSONPath of `JSON_*` and format strings
+/// of `FORMAT_*`, `PARSE_*` and `CAST(... FORMAT '...')`.
+/// Non-literal arguments are not checked.
+pub fn check_arguments(stmts: &[Node]) -> Vec<Diagnostic> {
+    let mut checker = Checker {
+        statement: 0,
+        diagnostics: Vec::new(),
+    };
+    for (idx, stmt) in stmts.iter().enumerate() {
+        checker.statement = idx;
+        checker.walk(stmt, 0);
+    }
+    checker.diagnostics
+}
+
+struct Checker {
+    statement: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker {
+    /// `receiver` is 1 if the function is called by the chained syntax (e.g. `(x).UPPER()`).
+    fn walk(&mut self, node: &Node, receiver: usize) {
+        match node.node_type {
+            NodeType::CallingFunction => self.call(node, receiver),
+            NodeType::FunctionChain => {
+                if let Some(left) = node.get_node("left") {
+                    self.walk(left, 0);
+                }
+                if let Some(right) = node.get_node("right") {
+                    self.walk(right, 1);
+                }
+                return;
+            }
+            NodeType::CastArgument => self.cast(node),
+            _ => (),
+        }
+        for (_, child) in node.child_nodes() {
+            self.walk(child, 0);
+        }
+    }
+    fn call(&mut self, node: &Node, receiver: usize) {
+        let Some(parts) = node.get_node("func").and_then(|f| f.identifier_parts()) else {
+            return;
+        };
+        let name = match parts.split_first() {
+            Some((first, rest)) if first.eq_ignore_ascii_case("SAFE") && !rest.is_empty() => {
+                rest.join(".")
+            }
+            _ => parts.join("."),
+        }
+        .to_uppercase();
+        let args: &[Node] = node.get_node_vec("args").map_or(&[], |a| a);
+        // the receiver of the chained syntax is not a string literal node
+        let arg = |idx: usize| idx.checked_sub(receiver).and_then(|i| args.get(i));
+        let name = name.as_str();
+        if REGEXP_FUNCTIONS.contains(&name) {
+            if let Some(pattern) = arg(1) {
+                self.regexp(name, pattern, arg(2));
+            }
+        } else if JSONPATH_FUNCTIONS.contains(&name) || LEGACY_JSONPATH_FUNCTIONS.contains(&name) {
+            if let Some(path) = arg(1) {
+                self.jsonpath(path, JSONPATH_FUNCTIONS.contains(&name));
+            }
+        } else if FORMAT_FUNCTIONS.contains(&name) {
+            if let Some(format) = arg(0) {
+                self.format_string(format);
+            }
+        } else {
+            // JSON_REMOVE(json, path, ...), JSON_SET(json, path, value, ...)
+            let paths: Vec<usize> = match name {
+                "JSON_REMOVE" => (1..receiver + args.len()).collect(),
+                "JSON_SET" | "JSON_ARRAY_APPEND" | "JSON_ARRAY_INSERT" => {
+                    (1..receiver + args.len()).step_by(2).collect()
+                }
+                _ => vec![],
+            };
+            for path in paths.into_iter().filter_map(arg) {
+                self.jsonpath(path, true);
+            }
+        }
+    }
+    fn regexp(&mut self, name: &str, pattern: &Node, replacement: Option<&Node>) {
+        let groups = match string_value(pattern) {
+            Some(LiteralValue::String(s)) => {
+                regex::Regex::new(&re2_to_rust(&s)).map(|r| r.captures_len())
+            }
+            Some(LiteralValue::Bytes(b)) => match String::from_utf8(b) {
+                Ok(s) => regex::bytes::Regex::new(&re2_to_rust(&s)).map(|r| r.captures_len()),
+                Err(_) => return,
+            },
+            None => return,
+        };
+        // the 1st group is the whole match
+        let groups = match groups {
+            Ok(groups) => groups - 1,
+            Err(regex::Error::Syntax(error)) => {
+                // the last line is the reason (e.g. `error: unclosed group`)
+                let reason = error.lines().last().unwrap_or_default();
+                let reason = reason.strip_prefix("error: ").unwrap_or(reason);
+                self.report(
+                    format!("Cannot parse regular expression: {}.", reason),
+                    pattern,
+                );
+                return;
+            }
+            Err(_) => return,
+        };
+        match name {
+            "REGEXP_EXTRACT" | "REGEXP_EXTRACT_ALL" | "REGEXP_INSTR" | "REGEXP_SUBSTR"
+                if 1 < groups =>
+            {
+                self.report(
+                    "Regular expressions passed into extraction functions must not have more than 1 capturing group.".to_string(),
+                    pattern,
+                );
+            }
+            "REGEXP_REPLACE" => {
+                let Some(replacement) = replacement else {
+                    return;
+                };
+                let Some(LiteralValue::String(value)) = string_value(replacement) else {
+                    return;
+                };
+                let mut chars = value.chars();
+                while let Some(c) = chars.next() {
+                    if c != '\\' {
+                        continue;
+                    }
+                    match chars.next() {
+                        Some('\\') => (),
+                        Some(digit @ '0'..='9') => {
+                            let group = digit.to_digit(10).unwrap_or_default() as usize;
+                            if groups < group {
+                                self.report(
+                                    format!("Invalid REGEXP_REPLACE replacement: \\{} refers to a capturing group which does not exist.", group),
+                                    replacement,
+                                );
+                                return;
+                            }
+                        }
+                        _ => {
+                            self.report(
+                                "Invalid REGEXP_REPLACE replacement: \\ must be followed by a digit or \\.".to_string(),
+                                replacement,
+                            );
+                            return;
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    fn jsonpath(&mut self, node: &Node, lax: bool) {
+        let Some(LiteralValue::String(path)) = string_value(node) else {
+            return;
+        };
+        if let Err(message) = parse_jsonpath(&path, lax) {
+            self.report(message, node);
+        }
+    }
+    fn format_string(&mut self, node: &Node) {
+        let Some(LiteralValue::String(format)) = string_value(node) else {
+            return;
+        };
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                continue;
+            }
+            let rest = chars.as_str();
+            let len = format_element_len(rest);
+            if len == 0 {
+                let element: String = rest.chars().take(1).collect();
+                let message = if element.is_empty() {
+                    "Format string cannot end with a single '%'.".to_string()
+                } else {
+                    format!("Invalid format element: %{}.", element)
+                };
+                self.report(message, node);
+                return;
+            }
+            chars = rest[len..].chars();
+        }
+    }
+    fn cast(&mut self, node: &Node) {
+        let Some(format) = node.get_node("format").and_then(|f| f.get_node("expr")) else {
+            return;
+        };
+        let Some(LiteralValue::String(value)) = string_value(format) else {
+            return;
+        };
+        let target = node
+            .get_node("cast_to")
+            .and_then(BqType::from_type)
+            .and_then(|t| t.scalar());
+        let bytes = || {
+            let valid = BYTES_FORMATS.iter().any(|f| f.eq_ignore_ascii_case(&value));
+            if valid {
+                Ok(())
+            } else {
+                Err(0)
+            }
+        };
+        let result = match target {
+            Some(s) if s.is_datetime() => match_format_model(&value, false),
+            Some(ScalarType::Bytes) => bytes(),
+            Some(s) if s.is_numeric() => match_format_model(&value, true),
+            // the format depends on the type of the source
+            Some(ScalarType::String) => bytes()
+                .or_else(|_| match_format_model(&value, true))
+                .or_else(|i| match_format_model(&value, false).map_err(|j| i.max(j))),
+            _ => Ok(()),
+        };
+        if let Err(position) = result {
+            self.report(
+                format!("Cannot find matched format element at {}.", position),
+                format,
+            );
+        }
+    }
+    fn report(&mut self, message: String, node: &Node) {
+        let diagnostic = Diagnostic::new(
+            DiagnosticKind::InvalidArgument,
+            message,
+            self.statement,
+            node,
+        );
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+fn string_value(node: &Node) -> Option<LiteralValue> {
+    if node.node_type != NodeType::StringLiteral {
+        return None;
+    }
+    decode_string_literal(node.token.as_ref()?)
+        .ok()
+        .map(|d| d.value)
+}
+
+/// Rewrites the syntax which is accepted by RE2 but not by the regex crate.
+/// - `\Q...\E` (quoted literal)
+/// - `\C` (any byte)
+/// - `{` which is not a repetition (e.g. `a{`, `{,3}`), which is a literal in RE2
+fn re2_to_rust(pattern: &str) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let rest = |from: usize| -> String { chars[from.min(chars.len())..].iter().collect() };
+    let mut res = String::new();
+    let mut class = false; // inside `[...]`
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let mut len = 1;
+        match c {
+            '\\' if !class && next == Some('Q') => {
+                let rest = rest(i + 2);
+                let quoted = match rest.find("\\E") {
+                    Some(end) => &rest[..end],
+                    None => &rest[..],
+                };
+                res.push_str(&regex::escape(quoted));
+                len = 2 + quoted.chars().count() + 2;
+            }
+            '\\' if !class && next == Some('C') => {
+                res.push_str("(?s:.)");
+                len = 2;
+            }
+            // \p{Greek}, \x{10FFFF}
+            '\\' if matches!(next, Some('p' | 'P' | 'x')) && chars.get(i + 2) == Some(&'{') => {
+                let rest = rest(i);
+                len = match rest.find('}') {
+                    Some(end) => rest[..=end].chars().count(),
+                    None => chars.len() - i,
+                };
+                res.extend(&chars[i..i + len]);
+            }
+            '\\' => {
+                res.push(c);
+                res.extend(next);
+                len = 2;
+            }
+            '[' if !class => {
+                class = true;
+                res.push(c);
+                // `]` just after `[` or `[^` is a literal
+                if next == Some('^') {
+                    res.push('^');
+                    len += 1;
+                }
+                if chars.get(i + len) == Some(&']') {
+                    res.push_str("\\]");
+                    len += 1;
+                }
+            }
+            // [:alpha:]
+            '[' if next == Some(':') && rest(i).contains(":]") => {
+                let rest = rest(i);
+                len = rest[..rest.find(":]").unwrap_or_default() + 2]
+                    .chars()
+                    .count();
+                res.extend(&chars[i..i + len]);
+            }
+            // `[` is not nested in RE2, and the set operations of the regex crate
+            // (e.g. `[a&&b]`) are literals in RE2
+            '[' | '&' | '~' if class => {
+                res.push('\\');
+                res.push(c);
+            }
+            ']' if class => {
+                class = false;
+                res.push(c);
+            }
+            '{' if !class && !is_repetition(&chars[i + 1..]) => res.push_str("\\{"),
+            _ => res.push(c),
+        }
+        i += len;
+    }
+    res
+}
+
+// `n}`, `n,}` or `n,m}` (just after `{`)
+fn is_repetition(chars: &[char]) -> bool {
+    let digits = |from: usize| {
+        chars[from..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count()
+    };
+    let n = digits(0);
+    if n == 0 {
+        return false;
+    }
+    match chars.get(n) {
+        Some('}') => true,
+        Some(',') => chars.get(n + 1 + digits(n + 1)) == Some(&'}'),
+        _ => false,
+    }
+}
+
+/// `$.a."b.c"[0]['d']` (`lax` and `recursive` modes are allowed if `lax` is true)
+fn parse_jsonpath(path: &str, lax: bool) -> Result<(), String> {
+    let mut rest = path;
+    if lax {
+        let mut words = path.split(' ');
+        let mut modes = 0;
+        while let Some(word) = words
+            .next()
+            .filter(|w| w.eq_ignore_ascii_case("lax") || w.eq_ignore_ascii_case("recursive"))
+        {
+            modes += word.len() + 1;
+        }
+        rest = &path[modes.min(path.len())..];
+    }
+    let Some(mut rest) = rest.strip_prefix('$') else {
+        return Err("JSONPath must start with '$'.".to_string());
+    };
+    let invalid = |rest: &str| format!("Invalid token in JSONPath at: {}", rest);
+    while !rest.is_empty() {
+        let remaining = rest;
+        if let Some(member) = rest.strip_prefix('.') {
+            if let Some(quoted) = member.strip_prefix('"') {
+                let Some(end) = closing_quote(quoted, '"') else {
+                    return Err(invalid(remaining));
+                };
+                rest = &quoted[end + 1..];
+            } else {
+                let end = member.find(['.', '[']).unwrap_or(member.len());
+                let name = &member[..end];
+                if name.is_empty()
+                    || name.contains(|c: char| c.is_whitespace() || "\"']".contains(c))
+                {
+                    return Err(invalid(remaining));
+                }
+                rest = &member[end..];
+            }
+        } else if let Some(subscript) = rest.strip_prefix('[') {
+            let end = match subscript.chars().next() {
+                Some(quote @ ('\'' | '"')) => closing_quote(&subscript[1..], quote).map(|i| i + 2),
+                _ => {
+                    let digits = subscript.find(|c: char| !c.is_ascii_digit());
+                    digits.filter(|i| 0 < *i)
+                }
+            };
+            match end.and_then(|i| subscript[i..].strip_prefix(']')) {
+                Some(after) => rest = after,
+                None => return Err(invalid(remaining)),
+            }
+        } else {
+            return Err(invalid(remaining));
+        }
+    }
+    Ok(())
+}
+
+// returns the index of the closing quote (`\` escapes the next character)
+fn closing_quote(value: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == quote => return Some(i),
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Returns the length of the format element after `%` (e.g. 3 for `E3S`).
+/// Returns 0 if it is not a valid format element.
+fn format_element_len(rest: &str) -> usize {
+    let mut chars = rest.chars();
+    match chars.next() {
+        Some(c) if "AaBbCcDdeFGgHhIJjklMmnPpQRSsTtUuVWwXxYyZz%".contains(c) => 1,
+        Some('E') => match chars.next() {
+            Some('z' | 'c' | 'C' | 'x' | 'X' | 'y' | 'Y') => 2,
+            Some('*') if chars.next() == Some('S') => 3,
+            Some('4') if chars.next() == Some('Y') => 3,
+            Some(d) if d.is_ascii_digit() => {
+                let digits = 1 + chars.clone().take_while(|c| c.is_ascii_digit()).count();
+                if rest[1 + digits..].starts_with('S') {
+                    digits + 2
+                } else {
+                    0
+                }
+            }
+            _ => 0,
+        },
+        Some('O') => match chars.next() {
+            Some('d' | 'e' | 'H' | 'I' | 'm' | 'M' | 'S' | 'u' | 'U' | 'V' | 'w' | 'W' | 'y') => 2,
+            _ => 0,
+        },
+        _ => 0,
+    }
+}
+
+/// Matches the format model of `CAST(... FORMAT '...')`.
+/// Returns the index of the first character which cannot be matched.
+fn match_format_model(format: &str, numeric: bool) -> Result<(), usize> {
+    let upper = format.to_uppercase();
+    // `to_uppercase()` may change the length of non-ASCII characters
+    if upper.len() != format.len() {
+        return Ok(());
+    }
+    let mut position = 0;
+    while position < upper.len() {
+        let rest = &upper[position..];
+        let element = if numeric {
+            NUMERIC_FORMAT_ELEMENTS
+                .iter()
+                .find(|e| rest.starts_with(*e))
+                .map(|e| e.len())
+        } else if let Some(quoted) = rest.strip_prefix('"') {
+            // quoted text
+            closing_quote(quoted, '"').map(|i| i + 2)
+        } else if rest.starts_with([' ', '-', '.', '/', ',', '\'', ';', ':']) {
+            Some(1)
+        } else {
+            DATETIME_FORMAT_ELEMENTS
+                .iter()
+                .chain(DATETIME_FORMAT_SINGLE_ELEMENTS.iter())
+                .find(|e| rest.starts_with(*e))
+                .map(|e| e.len())
+        };
+        match element {
+            Some(len) => position += len,
+            None => return Err(format[..position].chars().count()),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use super::*;
+use crate::validate::tests::{diagnostic_tuples, parse};
+
+fn diagnostics(code: &str) -> Vec<(String, usize, usize)> {
+    diagnostic_tuples(check_arguments(&parse(code)))
+        .into_iter()
+        .map(|(kind, message, line, column)| {
+            assert_eq!(kind, DiagnosticKind::InvalidArgument);
+            (message, line, column)
+        })
+        .collect()
+}
+
+#[test]
+fn test_check_arguments_valid() {
+    let res = diagnostics(
+        r#"
+SELECT
+  REGEXP_CONTAINS(s, r'^\d+(\.\d*)?$'), REGEXP_EXTRACT(s, 'a(b)c'), SAFE.REGEXP_SUBSTR(s, '(?i)x'),
+  REGEXP_REPLACE(s, r'(a)(b)', r'\2\1\\'), REGEXP_CONTAINS(b, b'[a-z]'), (s).REGEXP_CONTAINS(r'\w'),
+  REGEXP_CONTAINS(s, pattern), REGEXP_EXTRACT_ALL(s, r'(?:a)(b)');
+SELECT
+  JSON_VALUE(j, '$.a."b.c"[0]'), JSON_QUERY(j, 'lax recursive $.a'), JSON_EXTRACT(j, "$['a'][1]"),
+  JSON_QUERY(j, '$'), JSON_REMOVE(j, '$.a', '$.b[0]'), JSON_SET(j, '$.a', 1, '$.b', 'not a path'),
+  (j).JSON_VALUE('$.a');
+SELECT
+  FORMAT_DATE('%Y-%m-%d %A', d), PARSE_TIMESTAMP('%Y-%m-%d %H:%M:%E*S%Ez', s),
+  FORMAT_TIMESTAMP('%E3S %E4Y %Od %%', ts, 'UTC'), SAFE.PARSE_DATE("%x", s);
+SELECT
+  CAST(s AS DATE FORMAT 'YYYY-MM-DD'), CAST(d AS STRING FORMAT 'DAY, MONTH DD, YYYY "at" HH24:MI'),
+  CAST(n AS STRING FORMAT '$999,999.99'), CAST(b AS STRING FORMAT 'BASE64'),
+  CAST(s AS NUMERIC FORMAT '9.99EEEE'), CAST(ts AS STRING FORMAT 'HH12 A.M. TZH:TZM'), CAST(x AS INT64);
+"#,
+    );
+    assert_eq!(res, vec![]);
+}
+
+#[test]
+fn test_check_arguments_invalid() {
+    let res = diagnostics(
+        r#"SELECT REGEXP_CONTAINS(s, '(a'), REGEXP_EXTRACT(s, '(a)(b)'), REGEXP_REPLACE(s, '(a)', r'\2');
+SELECT REGEXP_REPLACE(s, 'a', r'\x'), JSON_VALUE(j, 'a.b'), JSON_QUERY(j, '$.a..b'), JSON_EXTRACT(j, '$[a]');
+SELECT FORMAT_DATE('%Y-%K', d), PARSE_DATE('%Y-%', s), CAST(s AS DATE FORMAT 'YYYY-MM-DDX');
+SELECT CAST(x AS STRING FORMAT 'YYYY-ABC');
+"#,
+    );
+    assert_eq!(
+        res,
+        vec![
+            (
+                "Cannot parse regular expression: unclosed group.".to_string(),
+                1,
+                27
+            ),
+            (
+                "Regular expressions passed into extraction functions must not have more than 1 capturing group."
+                    .to_string(),
+                1,
+                52
+            ),
+            (
+                "Invalid REGEXP_REPLACE replacement: \\2 refers to a capturing group which does not exist."
+                    .to_string(),
+                1,
+                88
+            ),
+            (
+                "Invalid REGEXP_REPLACE replacement: \\ must be followed by a digit or \\."
+                    .to_string(),
+                2,
+                31
+            ),
+            ("JSONPath must start with '$'.".to_string(), 2, 53),
+            ("Invalid token in JSONPath at: ..b".to_string(), 2, 75),
+            ("Invalid token in JSONPath at: [a]".to_string(), 2, 102),
+            ("Invalid format element: %K.".to_string(), 3, 20),
+            (
+                "Format string cannot end with a single '%'.".to_string(),
+                3,
+                44
+            ),
+            (
+                "Cannot find matched format element at 10.".to_string(),
+                3,
+                78
+            ),
+            (
+                "Cannot find matched format element at 5.".to_string(),
+                4,
+                32
+            ),
+        ]
+    );
+}
+
+// the syntax of RE2, which is different from the regex crate
+#[test]
+fn test_check_arguments_re2() {
+    for pattern in vec![
+        r"\Qa.b\E",
+        r"\Q(\E",
+        r"\Qa.b",
+        r"a\Cb",
+        r"a{",
+        r"{,3}",
+        r"a{1,x}",
+        r"x{2,3}",
+        r"[[]",
+        r"[]a]",
+        r"[^]a]",
+        r"[a&&b]",
+        r"[a~~b]",
+        r"[[:alpha:]]",
+        r"\p{Greek}\x{41}",
+    ] {
+        let code = format!("SELECT REGEXP_CONTAINS(s, r'{}');", pattern);
+        assert_eq!(diagnostics(&code), vec![], "{}", pattern);
+    }
+    assert_eq!(
+        diagnostics(r"SELECT REGEXP_EXTRACT(s, r'\Q(a)\E(b)'), REGEXP_EXTRACT(s, r'\Q(\E(a)(b)');"),
+        vec![(
+            "Regular expressions passed into extraction functions must not have more than 1 capturing group."
+                .to_string(),
+            1,
+            60
+        )]
+    );
+    let res = diagnostics(r"SELECT REGEXP_CONTAINS(s, r'a{2,1}'), REGEXP_CONTAINS(s, r'[z-a]');");
+    assert_eq!(
+        res.iter().map(|(_, l, c)| (*l, *c)).collect::<Vec<_>>(),
+        vec![(1, 27), (1, 58)]
+    );
+}
@@ -0,0 +1,388 @@
+#[cfg(test)]
+mod tests;
+
+use crate::catalog::TableFieldSchema;
+use crate::cst::{Node, NodeType};
+use crate::token::Token;
+use std::fmt;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ScalarType {
+    Int64,
+    Float64,
+    Numeric,
+    BigNumeric,
+    Bool,
+    String,
+    Bytes,
+    Date,
+    Datetime,
+    Time,
+    Timestamp,
+    Interval,
+    Json,
+    Geography,
+}
+
+impl ScalarType {
+    /// Parses the name of the type, including aliases (`INT`, `DECIMAL`, ...)
+    /// and the names used in JSON schema (`INTEGER`, `BOOLEAN`, ...).
+    pub fn from_name(name: &str) -> Option<ScalarType> {
+        let type_ = match name.to_uppercase().as_str() {
+            "INT64" | "INT" | "SMALLINT" | "INTEGER" | "BIGINT" | "TINYINT" | "BYTEINT" => {
+                ScalarType::Int64
+            }
+            "FLOAT64" | "FLOAT" => ScalarType::Float64,
+            "NUMERIC" | "DECIMAL" => ScalarType::Numeric,
+            "BIGNUMERIC" | "BIGDECIMAL" => ScalarType::BigNumeric,
+            "BOOL" | "BOOLEAN" => ScalarType::Bool,
+            "STRING" => ScalarType::String,
+            "BYTES" => ScalarType::Bytes,
+            "DATE" => ScalarType::Date,
+            "DATETIME" => ScalarType::Datetime,
+            "TIME" => ScalarType::Time,
+            "TIMESTAMP" => ScalarType::Timestamp,
+            "INTERVAL" => ScalarType::Interval,
+            "JSON" => ScalarType::Json,
+            "GEOGRAPHY" => ScalarType::Geography,
+            _ => return None,
+        };
+        Some(type_)
+    }
+    pub fn name(self) -> &'static str {
+        match self {
+            ScalarType::Int64 => "INT64",
+            ScalarType::Float64 => "FLOAT64",
+            ScalarType::Numeric => "NUMERIC",
+            ScalarType::BigNumeric => "BIGNUMERIC",
+            ScalarType::Bool => "BOOL",
+            ScalarType::String => "STRING",
+            ScalarType::Bytes => "BYTES",
+            ScalarType::Date => "DATE",
+            ScalarType::Datetime => "DATETIME",
+            ScalarType::Time => "TIME",
+            ScalarType::Timestamp => "TIMESTAMP",
+            ScalarType::Interval => "INTERVAL",
+            ScalarType::Json => "JSON",
+            ScalarType::Geography => "GEOGRAPHY",
+        }
+    }
+    /// The name used in JSON schema (e.g. `INTEGER`).
+    pub fn legacy_name(self) -> &'static str {
+        match self {
+            ScalarType::Int64 => "INTEGER",
+            ScalarType::Float64 => "FLOAT",
+            ScalarType::Bool => "BOOLEAN",
+            other => other.name(),
+        }
+    }
+    pub fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            ScalarType::Int64 | ScalarType::Float64 | ScalarType::Numeric | ScalarType::BigNumeric
+        )
+    }
+    pub fn is_datetime(self) -> bool {
+        matches!(
+            self,
+            ScalarType::Date | ScalarType::Datetime | ScalarType::Time | ScalarType::Timestamp
+        )
+    }
+    /// Implicit coercion (e.g. INT64 -> NUMERIC -> BIGNUMERIC -> FLOAT64, DATE -> DATETIME).
+    pub fn coercible_to(self, target: ScalarType) -> bool {
+        use ScalarType::*;
+        matches!(
+            (self, target),
+            (Int64, Numeric | BigNumeric | Float64)
+                | (Numeric, BigNumeric | Float64)
+                | (BigNumeric, Float64)
+                | (Date, Datetime)
+        ) || self == target
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum BqType {
+    Scalar(ScalarType, Vec<u64>), // parameters, e.g. STRING(10), NUMERIC(10, 2)
+    Array(Box<BqType>),           // ARRAY<T>
+    Struct(Vec<(Option<String>, BqType)>), // STRUCT<a T, U>
+    Range(Box<BqType>),           // RANGE<DATE>
+    Table(Vec<(Option<String>, BqType)>), // TABLE<a T> (table functions)
+    AnyType,                      // ANY TYPE (templated parameters)
+}
+
+impl From<ScalarType> for BqType {
+    fn from(type_: ScalarType) -> BqType {
+        BqType::Scalar(type_, Vec::new())
+    }
+}
+
+/// Canonical representation, e.g. `ARRAY<STRUCT<a STRING(10), b NUMERIC(10, 2)>>`.
+impl fmt::Display for BqType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fields = |fields: &[(Option<String>, BqType)]| {
+            fields
+                .iter()
+                .map(|(name, type_)| match name {
+                    Some(name) => format!("{} {}", name, type_),
+                    None => type_.to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        match self {
+            BqType::Scalar(type_, parameters) if parameters.is_empty() => {
+                write!(f, "{}", type_.name())
+            }
+            BqType::Scalar(type_, parameters) => {
+                let parameters: Vec<String> = parameters.iter().map(|p| p.to_string()).collect();
+                write!(f, "{}({})", type_.name(), parameters.join(", "))
+            }
+            BqType::Array(element) => write!(f, "ARRAY<{}>", element),
+            BqType::Struct(fs) => write!(f, "STRUCT<{}>", fields(fs)),
+            BqType::Range(element) => write!(f, "RANGE<{}>", element),
+            BqType::Table(fs) => write!(f, "TABLE<{}>", fields(fs)),
+            BqType::AnyType => write!(f, "ANY TYPE"),
+        }
+    }
+}
+
+impl BqType {
+    /// Converts `Type` node. Returns `None` if the type is unknown (e.g. `ARRAY`
+    /// without element type, user defined type) or the parameters are not integers.
+    pub fn from_type(node: &Node) -> Option<BqType> {
+        let declaration = node.get_node("type_declaration");
+        let element = || {
+            let element = declaration?.get_node("type")?;
+            Some(Box::new(BqType::from_type(element)?))
+        };
+        let fields = || {
+            let mut res = Vec::new();
+            let declarations = declaration?.get_node_vec("declarations");
+            for decl in declarations.into_iter().flatten() {
+                let name = decl.token.as_ref().map(|t| t.literal.clone());
+                res.push((name, BqType::from_type(decl.get_node("type")?)?));
+            }
+            Some(res)
+        };
+        let type_ = match node.literal().to_uppercase().as_str() {
+            "ARRAY" => BqType::Array(element()?),
+            "RANGE" => BqType::Range(element()?),
+            "STRUCT" => BqType::Struct(fields()?),
+            "TABLE" => BqType::Table(fields()?),
+            "ANY" => BqType::AnyType,
+            name => {
+                let mut parameters = Vec::new();
+                if let Some(parameter) = node.get_node("parameter") {
+                    for expr in parameter.get_node_vec("exprs").into_iter().flatten() {
+                        parameters.push(expr.literal().parse().ok()?);
+                    }
+                }
+                BqType::Scalar(ScalarType::from_name(name)?, parameters)
+            }
+        };
+        Some(type_)
+    }
+    /// Builds `Type` node as if the canonical representation were parsed
+    /// (the first token is at line 1, column 1).
+    pub fn to_type(&self) -> Node {
+        self.build(&mut 1)
+    }
+    fn build(&self, column: &mut usize) -> Node {
+        match self {
+            BqType::Scalar(type_, parameters) => {
+                let mut res = token_node(column, type_.name(), NodeType::Type);
+                if !parameters.is_empty() {
+                    let mut group = token_node(column, "(", NodeType::GroupedExprs);
+                    let mut exprs = Vec::new();
+                    for (idx, parameter) in parameters.iter().enumerate() {
+                        let literal = parameter.to_string();
+                        let mut expr = token_node(column, &literal, NodeType::NumericLiteral);
+                        if idx + 1 < parameters.len() {
+                            expr.push_node("comma", token_node(column, ",", NodeType::Symbol));
+                            *column += 1;
+                        }
+                        exprs.push(expr);
+                    }
+                    group.push_node_vec("exprs", exprs);
+                    group.push_node("rparen", token_node(column, ")", NodeType::Symbol));
+                    res.push_node("parameter", group);
+                }
+                res
+            }
+            BqType::Array(element) | BqType::Range(element) => {
+                let keyword = match self {
+                    BqType::Array(_) => "ARRAY",
+                    _ => "RANGE",
+                };
+                let mut res = token_node(column, keyword, NodeType::Type);
+                let mut group = token_node(column, "<", NodeType::GroupedType);
+                group.push_node("type", element.build(column));
+                group.push_node("rparen", token_node(column, ">", NodeType::Symbol));
+                res.push_node("type_declaration", group);
+                res
+            }
+            BqType::Struct(fields) | BqType::Table(fields) => {
+                let keyword = match self {
+                    BqType::Struct(_) => "STRUCT",
+                    _ => "TABLE",
+                };
+                let mut res = token_node(column, keyword, NodeType::Type);
+                let mut group =
+                    token_node(column, "<", NodeType::GroupedTypeDeclarationOrConstraints);
+                let mut declarations = Vec::new();
+                for (idx, (name, type_)) in fields.iter().enumerate() {
+                    let mut declaration = match name {
+                        Some(name) => {
+                            let declaration = token_node(column, name, NodeType::TypeDeclaration);
+                            *column += 1;
+                            declaration
+                        }
+                        None => Node::empty(NodeType::TypeDeclaration),
+                    };
+                    declaration.push_node("type", type_.build(column));
+                    if idx + 1 < fields.len() {
+                        declaration.push_node("comma", token_node(column, ",", NodeType::Symbol));
+                        *column += 1;
+                    }
+                    declarations.push(declaration);
+                }
+                group.push_node_vec("declarations", declarations);
+                group.push_node("rparen", token_node(column, ">", NodeType::Symbol));
+                res.push_node("type_declaration", group);
+                res
+            }
+            BqType::AnyType => {
+                let mut res = token_node(column, "ANY", NodeType::Type);
+                *column += 1;
+                res.push_node("type", token_node(column, "TYPE", NodeType::Keyword));
+                res
+            }
+        }
+    }
+    /// Converts the column of JSON schema (REPEATED mode means ARRAY).
+    pub fn from_field(field: &TableFieldSchema) -> Option<BqType> {
+        let parse = |value: &Option<String>| value.as_ref().and_then(|v| v.parse::<u64>().ok());
+        let type_ = match field.type_.to_uppercase().as_str() {
+            "RECORD" | "STRUCT" => {
+                let mut fields = Vec::new();
+                for f in &field.fields {
+                    fields.push((Some(f.name.clone()), BqType::from_field(f)?));
+                }
+                BqType::Struct(fields)
+            }
+            "RANGE" => {
+                let element = ScalarType::from_name(&field.range_element_type.as_ref()?.type_)?;
+                BqType::Range(Box::new(element.into()))
+            }
+            name => {
+                let type_ = ScalarType::from_name(name)?;
+                let parameters = match type_ {
+                    ScalarType::String | ScalarType::Bytes => {
+                        parse(&field.max_length).into_iter().collect()
+                    }
+                    ScalarType::Numeric | ScalarType::BigNumeric => parse(&field.precision)
+                        .into_iter()
+                        .chain(parse(&field.scale))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                BqType::Scalar(type_, parameters)
+            }
+        };
+        if field.mode == "REPEATED" {
+            return Some(BqType::Array(Box::new(type_)));
+        }
+        Some(type_)
+    }
+    /// The scalar type ignoring the parameters. `None` if the type is not scalar.
+    pub fn scalar(&self) -> Option<ScalarType> {
+        match self {
+            BqType::Scalar(type_, _) => Some(*type_),
+            _ => None,
+        }
+    }
+    pub fn is_numeric(&self) -> bool {
+        self.scalar().is_some_and(|s| s.is_numeric())
+    }
+    pub fn is_datetime(&self) -> bool {
+        self.scalar().is_some_and(|s| s.is_datetime())
+    }
+    /// The type of the values (parameters are not applied to expressions).
+    pub fn without_parameters(&self) -> BqType {
+        let fields = |fields: &[(Option<String>, BqType)]| {
+            fields
+                .iter()
+                .map(|(name, type_)| (name.clone(), type_.without_parameters()))
+                .collect()
+        };
+        match self {
+            BqType::Scalar(type_, _) => BqType::from(*type_),
+            BqType::Array(element) => BqType::Array(Box::new(element.without_parameters())),
+            BqType::Range(element) => BqType::Range(Box::new(element.without_parameters())),
+            BqType::Struct(fs) => BqType::Struct(fields(fs)),
+            BqType::Table(fs) => BqType::Table(fields(fs)),
+            BqType::AnyType => BqType::AnyType,
+        }
+    }
+    /// Whether the value can be implicitly coerced to the target type.
+    /// The parameters and the field names are ignored.
+    pub fn coercible_to(&self, target: &BqType) -> bool {
+        let fields = |xs: &[(Option<String>, BqType)], ys: &[(Option<String>, BqType)]| {
+            xs.len() == ys.len() && xs.iter().zip(ys).all(|((_, x), (_, y))| x.coercible_to(y))
+        };
+        match (self, target) {
+            (_, BqType::AnyType) => true,
+            (BqType::Scalar(x, _), BqType::Scalar(y, _)) => x.coercible_to(*y),
+            (BqType::Array(x), BqType::Array(y)) => x.coercible_to(y),
+            (BqType::Range(x), BqType::Range(y)) => x.scalar() == y.scalar(),
+            (BqType::Struct(xs), BqType::Struct(ys)) | (BqType::Table(xs), BqType::Table(ys)) => {
+                fields(xs, ys)
+            }
+            _ => false,
+        }
+    }
+}
+
+// the node of the token at `column` of line 1, moving `column` to the end of the token
+fn token_node(column: &mut usize, literal: &str, node_type: NodeType) -> Node {
+    let token = Token::new(1, *column, literal.to_string());
+    *column += literal.chars().count();
+    Node::new(token, node_type)
+}
+
+/// The smallest type both types can be coerced to. The parameters are removed
+/// unless both types are the same.
+pub fn supertype(a: &BqType, b: &BqType) -> Option<BqType> {
+    if a == b {
+        return Some(a.clone());
+    }
+    let fields = |xs: &[(Option<String>, BqType)], ys: &[(Option<String>, BqType)]| {
+        if xs.len() != ys.len() {
+            return None;
+        }
+        let mut res = Vec::new();
+        for ((name, x), (_, y)) in xs.iter().zip(ys) {
+            res.push((name.clone(), supertype(x, y)?));
+        }
+        Some(res)
+    };
+    let type_ = match (a, b) {
+        (BqType::AnyType, other) | (other, BqType::AnyType) => other.clone(),
+        (BqType::Scalar(x, _), BqType::Scalar(y, _)) => {
+            if y.coercible_to(*x) {
+                BqType::from(*x)
+            } else if x.coercible_to(*y) {
+                BqType::from(*y)
+            } else {
+                return None;
+            }
+        }
+        (BqType::Array(x), BqType::Array(y)) => BqType::Array(Box::new(supertype(x, y)?)),
+        (BqType::Range(x), BqType::Range(y)) if x.scalar() == y.scalar() => a.without_parameters(),
+        (BqType::Struct(xs), BqType::Struct(ys)) => BqType::Struct(fields(xs, ys)?),
+        (BqType::Table(xs), BqType::Table(ys)) => BqType::Table(fields(xs, ys)?),
+        _ => return None,
+    };
+    Some(type_)
+}
@@ -0,0 +1,129 @@
+use super::*;
+use crate::validate::tests::parse;
+
+// the type of the parameter of `CREATE TEMP FUNCTION f(x type_)`
+fn parse_type(type_: &str) -> Node {
+    let code = format!("CREATE TEMP FUNCTION f(x {}) AS (1);", type_);
+    let stmts = parse(&code);
+    stmts[0]
+        .get_node("group")
+        .unwrap()
+        .get_node_vec("declarations")
+        .unwrap()[0]
+        .get_node("type")
+        .unwrap()
+        .clone()
+}
+
+fn bq_type(type_: &str) -> BqType {
+    BqType::from_type(&parse_type(type_)).expect("Failed to convert type.")
+}
+
+fn tokens(node: &Node, res: &mut Vec<(usize, usize, String)>) {
+    if let Some(token) = &node.token {
+        res.push((token.line, token.column, token.literal.clone()));
+    }
+    for (_, child) in node.child_nodes() {
+        tokens(child, res);
+    }
+}
+
+#[test]
+fn test_bqtype_from_type() {
+    let tests = [
+        ("int", "INT64"),
+        ("boolean", "BOOL"),
+        ("string(10)", "STRING(10)"),
+        ("numeric(10,2)", "NUMERIC(10, 2)"),
+        (
+            "array<struct<a int, b decimal>>",
+            "ARRAY<STRUCT<a INT64, b NUMERIC>>",
+        ),
+        (
+            "struct<int64, `x` string(1)>",
+            "STRUCT<INT64, `x` STRING(1)>",
+        ),
+        ("range<date>", "RANGE<DATE>"),
+        (
+            "table<a int64, b array<bytes>>",
+            "TABLE<a INT64, b ARRAY<BYTES>>",
+        ),
+        ("any type", "ANY TYPE"),
+    ];
+    for (type_, expected) in tests {
+        assert_eq!(bq_type(type_).to_string(), expected);
+    }
+    assert_eq!(BqType::from_type(&parse_type("array<mytype>")), None);
+    assert_eq!(
+        bq_type("array<string(10)>"),
+        BqType::Array(Box::new(BqType::Scalar(ScalarType::String, vec![10])))
+    );
+}
+
+#[test]
+fn test_bqtype_to_type() {
+    for type_ in [
+        "INT64",
+        "NUMERIC(10, 2)",
+        "ARRAY<STRUCT<a STRING(10), INT64>>",
+        "RANGE<TIMESTAMP>",
+        "TABLE<a INT64, b BOOL>",
+        "ANY TYPE",
+    ] {
+        let node = bq_type(type_).to_type();
+        assert_eq!(BqType::from_type(&node), Some(bq_type(type_)));
+        // the positions of the tokens correspond to the canonical representation
+        let mut res = Vec::new();
+        tokens(&node, &mut res);
+        for (line, column, literal) in res {
+            assert_eq!(line, 1);
+            let text: String = type_
+                .chars()
+                .skip(column - 1)
+                .take(literal.chars().count())
+                .collect();
+            assert_eq!(text, literal);
+        }
+    }
+}
+
+#[test]
+fn test_bqtype_supertype() {
+    let supertype_of =
+        |a: &str, b: &str| supertype(&bq_type(a), &bq_type(b)).map(|t| t.to_string());
+    assert_eq!(
+        supertype_of("int64", "numeric"),
+        Some("NUMERIC".to_string())
+    );
+    assert_eq!(
+        supertype_of("float64", "bignumeric"),
+        Some("FLOAT64".to_string())
+    );
+    assert_eq!(
+        supertype_of("date", "datetime"),
+        Some("DATETIME".to_string())
+    );
+    assert_eq!(
+        supertype_of("string(10)", "string(10)"),
+        Some("STRING(10)".to_string())
+    );
+    assert_eq!(
+        supertype_of("string(10)", "string(20)"),
+        Some("STRING".to_string())
+    );
+    assert_eq!(
+        supertype_of("array<struct<a int64>>", "array<struct<b float64>>"),
+        Some("ARRAY<STRUCT<a FLOAT64>>".to_string())
+    );
+    assert_eq!(supertype_of("any type", "bytes"), Some("BYTES".to_string()));
+    assert_eq!(supertype_of("string", "int64"), None);
+    assert_eq!(supertype_of("range<date>", "range<datetime>"), None);
+    assert_eq!(supertype_of("struct<int64>", "struct<int64, int64>"), None);
+
+    assert!(bq_type("int64").coercible_to(&bq_type("numeric(10, 2)")));
+    assert!(bq_type("array<date>").coercible_to(&bq_type("array<datetime>")));
+    assert!(bq_type("struct<a int64>").coercible_to(&bq_type("struct<b float64>")));
+    assert!(bq_type("json").coercible_to(&bq_type("any type")));
+    assert!(!bq_type("float64").coercible_to(&bq_type("int64")));
+    assert!(!bq_type("array<int64>").coercible_to(&bq_type("int64")));
+}
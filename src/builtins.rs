@@ -0,0 +1,736 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{Node, NodeType};
+use crate::validate::{Diagnostic, DiagnosticKind};
+use serde::Serialize;
+use FunctionKind::{Aggregate, Analytic, Scalar};
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize)]
+pub enum FunctionKind {
+    Scalar,
+    Aggregate, // can also be called with OVER clause
+    Analytic,  // can only be called with OVER clause
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize)]
+pub struct Builtin {
+    pub name: &'static str, // e.g. SUBSTR, NET.HOST
+    pub kind: FunctionKind,
+    pub min_args: usize,
+    pub max_args: usize, // VARIADIC if there is no upper limit
+}
+
+pub const VARIADIC: usize = usize::MAX;
+
+// namespaces of the built-in functions (e.g. NET.HOST)
+pub const NAMESPACES: [&str; 8] = [
+    "AEAD",
+    "AI",
+    "HLL_COUNT",
+    "KEYS",
+    "KLL_QUANTILES",
+    "ML",
+    "NET",
+    "OBJ",
+];
+
+// aggregate functions which accept DISTINCT, IGNORE NULLS or ORDER BY and LIMIT
+const DISTINCT: [&str; 15] = [
+    "ARRAY_AGG",
+    "AVG",
+    "BIT_AND",
+    "BIT_OR",
+    "BIT_XOR",
+    "COUNT",
+    "COUNTIF",
+    "MAX",
+    "MIN",
+    "STDDEV",
+    "STDDEV_POP",
+    "STDDEV_SAMP",
+    "STRING_AGG",
+    "SUM",
+    "VARIANCE",
+];
+const IGNORE_NULLS: [&str; 7] = [
+    "APPROX_QUANTILES",
+    "ARRAY_AGG",
+    "FIRST_VALUE",
+    "LAST_VALUE",
+    "NTH_VALUE",
+    "PERCENTILE_CONT",
+    "PERCENTILE_DISC",
+];
+const ORDER_BY: [&str; 3] = ["ARRAY_AGG", "ARRAY_CONCAT_AGG", "STRING_AGG"];
+
+const BUILTINS: &[(&str, FunctionKind, usize, usize)] = &[
+    // aggregate
+    ("ANY_VALUE", Aggregate, 1, 1),
+    ("APPROX_COUNT_DISTINCT", Aggregate, 1, 1),
+    ("APPROX_QUANTILES", Aggregate, 2, 2),
+    ("APPROX_TOP_COUNT", Aggregate, 2, 2),
+    ("APPROX_TOP_SUM", Aggregate, 3, 3),
+    ("ARRAY_AGG", Aggregate, 1, 1),
+    ("ARRAY_CONCAT_AGG", Aggregate, 1, 1),
+    ("AVG", Aggregate, 1, 1),
+    ("BIT_AND", Aggregate, 1, 1),
+    ("BIT_OR", Aggregate, 1, 1),
+    ("BIT_XOR", Aggregate, 1, 1),
+    ("CORR", Aggregate, 2, 2),
+    ("COUNT", Aggregate, 1, 1),
+    ("COUNTIF", Aggregate, 1, 1),
+    ("COVAR_POP", Aggregate, 2, 2),
+    ("COVAR_SAMP", Aggregate, 2, 2),
+    ("GROUPING", Aggregate, 1, 1),
+    ("LOGICAL_AND", Aggregate, 1, 1),
+    ("LOGICAL_OR", Aggregate, 1, 1),
+    ("MAX", Aggregate, 1, 1),
+    ("MAX_BY", Aggregate, 2, 2),
+    ("MIN", Aggregate, 1, 1),
+    ("MIN_BY", Aggregate, 2, 2),
+    ("ST_CENTROID_AGG", Aggregate, 1, 1),
+    ("ST_EXTENT", Aggregate, 1, 1),
+    ("ST_UNION_AGG", Aggregate, 1, 1),
+    ("STDDEV", Aggregate, 1, 1),
+    ("STDDEV_POP", Aggregate, 1, 1),
+    ("STDDEV_SAMP", Aggregate, 1, 1),
+    ("STRING_AGG", Aggregate, 1, 2),
+    ("SUM", Aggregate, 1, 1),
+    ("VAR_POP", Aggregate, 1, 1),
+    ("VAR_SAMP", Aggregate, 1, 1),
+    ("VARIANCE", Aggregate, 1, 1),
+    ("HLL_COUNT.INIT", Aggregate, 1, 2),
+    ("HLL_COUNT.MERGE", Aggregate, 1, 1),
+    ("HLL_COUNT.MERGE_PARTIAL", Aggregate, 1, 1),
+    ("KLL_QUANTILES.INIT_DOUBLE", Aggregate, 2, 3),
+    ("KLL_QUANTILES.INIT_INT64", Aggregate, 2, 3),
+    ("KLL_QUANTILES.INIT_UINT64", Aggregate, 2, 3),
+    ("KLL_QUANTILES.MERGE_DOUBLE", Aggregate, 2, 2),
+    ("KLL_QUANTILES.MERGE_INT64", Aggregate, 2, 2),
+    ("KLL_QUANTILES.MERGE_PARTIAL", Aggregate, 1, 1),
+    ("KLL_QUANTILES.MERGE_POINT_DOUBLE", Aggregate, 2, 2),
+    ("KLL_QUANTILES.MERGE_POINT_INT64", Aggregate, 2, 2),
+    ("KLL_QUANTILES.MERGE_POINT_UINT64", Aggregate, 2, 2),
+    ("KLL_QUANTILES.MERGE_UINT64", Aggregate, 2, 2),
+    // analytic (numbering, navigation)
+    ("CUME_DIST", Analytic, 0, 0),
+    ("DENSE_RANK", Analytic, 0, 0),
+    ("FIRST_VALUE", Analytic, 1, 1),
+    ("LAG", Analytic, 1, 3),
+    ("LAST_VALUE", Analytic, 1, 1),
+    ("LEAD", Analytic, 1, 3),
+    ("NTH_VALUE", Analytic, 2, 2),
+    ("NTILE", Analytic, 1, 1),
+    ("PERCENT_RANK", Analytic, 0, 0),
+    ("PERCENTILE_CONT", Analytic, 2, 2),
+    ("PERCENTILE_DISC", Analytic, 2, 2),
+    ("RANK", Analytic, 0, 0),
+    ("ROW_NUMBER", Analytic, 0, 0),
+    ("ST_CLUSTERDBSCAN", Analytic, 3, 3),
+    // conversion, conditional, debugging
+    ("CAST", Scalar, 1, 1),
+    ("COALESCE", Scalar, 1, VARIADIC),
+    ("ERROR", Scalar, 1, 1),
+    ("IF", Scalar, 3, 3),
+    ("IFNULL", Scalar, 2, 2),
+    ("NULLIF", Scalar, 2, 2),
+    ("NULLIFZERO", Scalar, 1, 1),
+    ("PARSE_BIGNUMERIC", Scalar, 1, 1),
+    ("PARSE_NUMERIC", Scalar, 1, 1),
+    ("SAFE_CAST", Scalar, 1, 1),
+    ("ZEROIFNULL", Scalar, 1, 1),
+    // array
+    ("ARRAY", Scalar, 1, 1),
+    ("ARRAY_CONCAT", Scalar, 1, VARIADIC),
+    ("ARRAY_FIRST", Scalar, 1, 1),
+    ("ARRAY_LAST", Scalar, 1, 1),
+    ("ARRAY_LENGTH", Scalar, 1, 1),
+    ("ARRAY_REVERSE", Scalar, 1, 1),
+    ("ARRAY_SLICE", Scalar, 3, 3),
+    ("ARRAY_TO_STRING", Scalar, 2, 3),
+    ("GENERATE_ARRAY", Scalar, 2, 3),
+    ("GENERATE_DATE_ARRAY", Scalar, 2, 3),
+    ("GENERATE_TIMESTAMP_ARRAY", Scalar, 3, 3),
+    // date and time
+    ("CURRENT_DATE", Scalar, 0, 1),
+    ("CURRENT_DATETIME", Scalar, 0, 1),
+    ("CURRENT_TIME", Scalar, 0, 1),
+    ("CURRENT_TIMESTAMP", Scalar, 0, 0),
+    ("DATE", Scalar, 1, 3),
+    ("DATE_ADD", Scalar, 2, 2),
+    ("DATE_BUCKET", Scalar, 2, 3),
+    ("DATE_DIFF", Scalar, 3, 3),
+    ("DATE_FROM_UNIX_DATE", Scalar, 1, 1),
+    ("DATE_SUB", Scalar, 2, 2),
+    ("DATE_TRUNC", Scalar, 2, 2),
+    ("DATETIME", Scalar, 1, 6),
+    ("DATETIME_ADD", Scalar, 2, 2),
+    ("DATETIME_BUCKET", Scalar, 2, 3),
+    ("DATETIME_DIFF", Scalar, 3, 3),
+    ("DATETIME_SUB", Scalar, 2, 2),
+    ("DATETIME_TRUNC", Scalar, 2, 2),
+    ("EXTRACT", Scalar, 1, 1),
+    ("FORMAT_DATE", Scalar, 2, 2),
+    ("FORMAT_DATETIME", Scalar, 2, 2),
+    ("FORMAT_TIME", Scalar, 2, 2),
+    ("FORMAT_TIMESTAMP", Scalar, 2, 3),
+    ("LAST_DAY", Scalar, 1, 2),
+    ("PARSE_DATE", Scalar, 2, 2),
+    ("PARSE_DATETIME", Scalar, 2, 2),
+    ("PARSE_TIME", Scalar, 2, 2),
+    ("PARSE_TIMESTAMP", Scalar, 2, 3),
+    ("TIME", Scalar, 1, 3),
+    ("TIME_ADD", Scalar, 2, 2),
+    ("TIME_DIFF", Scalar, 3, 3),
+    ("TIME_SUB", Scalar, 2, 2),
+    ("TIME_TRUNC", Scalar, 2, 2),
+    ("TIMESTAMP", Scalar, 1, 2),
+    ("TIMESTAMP_ADD", Scalar, 2, 2),
+    ("TIMESTAMP_BUCKET", Scalar, 2, 3),
+    ("TIMESTAMP_DIFF", Scalar, 3, 3),
+    ("TIMESTAMP_MICROS", Scalar, 1, 1),
+    ("TIMESTAMP_MILLIS", Scalar, 1, 1),
+    ("TIMESTAMP_SECONDS", Scalar, 1, 1),
+    ("TIMESTAMP_SUB", Scalar, 2, 2),
+    ("TIMESTAMP_TRUNC", Scalar, 2, 3),
+    ("UNIX_DATE", Scalar, 1, 1),
+    ("UNIX_MICROS", Scalar, 1, 1),
+    ("UNIX_MILLIS", Scalar, 1, 1),
+    ("UNIX_SECONDS", Scalar, 1, 1),
+    // interval and range
+    ("GENERATE_RANGE_ARRAY", Scalar, 2, 3),
+    ("JUSTIFY_DAYS", Scalar, 1, 1),
+    ("JUSTIFY_HOURS", Scalar, 1, 1),
+    ("JUSTIFY_INTERVAL", Scalar, 1, 1),
+    ("MAKE_INTERVAL", Scalar, 0, 6),
+    ("RANGE", Scalar, 2, 2),
+    ("RANGE_CONTAINS", Scalar, 2, 2),
+    ("RANGE_END", Scalar, 1, 1),
+    ("RANGE_INTERSECT", Scalar, 2, 2),
+    ("RANGE_OVERLAPS", Scalar, 2, 2),
+    ("RANGE_START", Scalar, 1, 1),
+    // geography
+    ("S2_CELLIDFROMPOINT", Scalar, 1, 2),
+    ("S2_COVERINGCELLIDS", Scalar, 1, 5),
+    ("ST_ANGLE", Scalar, 3, 3),
+    ("ST_AREA", Scalar, 1, 2),
+    ("ST_ASBINARY", Scalar, 1, 1),
+    ("ST_ASGEOJSON", Scalar, 1, 1),
+    ("ST_ASTEXT", Scalar, 1, 1),
+    ("ST_AZIMUTH", Scalar, 2, 2),
+    ("ST_BOUNDARY", Scalar, 1, 1),
+    ("ST_BOUNDINGBOX", Scalar, 1, 1),
+    ("ST_BUFFER", Scalar, 2, 4),
+    ("ST_BUFFERWITHTOLERANCE", Scalar, 3, 5),
+    ("ST_CENTROID", Scalar, 1, 1),
+    ("ST_CLOSESTPOINT", Scalar, 2, 3),
+    ("ST_CONTAINS", Scalar, 2, 2),
+    ("ST_CONVEXHULL", Scalar, 1, 1),
+    ("ST_COVEREDBY", Scalar, 2, 2),
+    ("ST_COVERS", Scalar, 2, 2),
+    ("ST_DIFFERENCE", Scalar, 2, 2),
+    ("ST_DIMENSION", Scalar, 1, 1),
+    ("ST_DISJOINT", Scalar, 2, 2),
+    ("ST_DISTANCE", Scalar, 2, 3),
+    ("ST_DUMP", Scalar, 1, 2),
+    ("ST_DWITHIN", Scalar, 3, 4),
+    ("ST_ENDPOINT", Scalar, 1, 1),
+    ("ST_EQUALS", Scalar, 2, 2),
+    ("ST_EXTERIORRING", Scalar, 1, 1),
+    ("ST_GEOGFROM", Scalar, 1, 1),
+    ("ST_GEOGFROMGEOJSON", Scalar, 1, 2),
+    ("ST_GEOGFROMTEXT", Scalar, 1, 4),
+    ("ST_GEOGFROMWKB", Scalar, 1, 2),
+    ("ST_GEOGPOINT", Scalar, 2, 2),
+    ("ST_GEOGPOINTFROMGEOHASH", Scalar, 1, 1),
+    ("ST_GEOHASH", Scalar, 1, 2),
+    ("ST_GEOMETRYTYPE", Scalar, 1, 1),
+    ("ST_HAUSDORFFDISTANCE", Scalar, 2, 3),
+    ("ST_INTERIORRINGS", Scalar, 1, 1),
+    ("ST_INTERSECTION", Scalar, 2, 2),
+    ("ST_INTERSECTS", Scalar, 2, 2),
+    ("ST_INTERSECTSBOX", Scalar, 5, 5),
+    ("ST_ISCLOSED", Scalar, 1, 1),
+    ("ST_ISCOLLECTION", Scalar, 1, 1),
+    ("ST_ISEMPTY", Scalar, 1, 1),
+    ("ST_ISRING", Scalar, 1, 1),
+    ("ST_LENGTH", Scalar, 1, 2),
+    ("ST_LINEINTERPOLATEPOINT", Scalar, 2, 2),
+    ("ST_LINELOCATEPOINT", Scalar, 2, 2),
+    ("ST_LINESUBSTRING", Scalar, 3, 3),
+    ("ST_MAKELINE", Scalar, 1, 2),
+    ("ST_MAKEPOLYGON", Scalar, 1, 2),
+    ("ST_MAKEPOLYGONORIENTED", Scalar, 1, 1),
+    ("ST_MAXDISTANCE", Scalar, 2, 3),
+    ("ST_NPOINTS", Scalar, 1, 1),
+    ("ST_NUMGEOMETRIES", Scalar, 1, 1),
+    ("ST_NUMPOINTS", Scalar, 1, 1),
+    ("ST_PERIMETER", Scalar, 1, 2),
+    ("ST_POINTN", Scalar, 2, 2),
+    ("ST_REGIONSTATS", Scalar, 2, 4),
+    ("ST_SIMPLIFY", Scalar, 2, 2),
+    ("ST_SNAPTOGRID", Scalar, 2, 2),
+    ("ST_STARTPOINT", Scalar, 1, 1),
+    ("ST_TOUCHES", Scalar, 2, 2),
+    ("ST_UNION", Scalar, 1, 2),
+    ("ST_WITHIN", Scalar, 2, 2),
+    ("ST_X", Scalar, 1, 1),
+    ("ST_Y", Scalar, 1, 1),
+    // hash, encryption
+    ("AEAD.DECRYPT_BYTES", Scalar, 3, 3),
+    ("AEAD.DECRYPT_STRING", Scalar, 3, 3),
+    ("AEAD.ENCRYPT", Scalar, 3, 3),
+    ("DETERMINISTIC_DECRYPT_BYTES", Scalar, 3, 3),
+    ("DETERMINISTIC_DECRYPT_STRING", Scalar, 3, 3),
+    ("DETERMINISTIC_ENCRYPT", Scalar, 3, 3),
+    ("FARM_FINGERPRINT", Scalar, 1, 1),
+    ("KEYS.ADD_KEY_FROM_RAW_BYTES", Scalar, 3, 3),
+    ("KEYS.KEYSET_CHAIN", Scalar, 2, 3),
+    ("KEYS.KEYSET_FROM_JSON", Scalar, 1, 1),
+    ("KEYS.KEYSET_LENGTH", Scalar, 1, 1),
+    ("KEYS.KEYSET_TO_JSON", Scalar, 1, 1),
+    ("KEYS.NEW_KEYSET", Scalar, 1, 1),
+    ("KEYS.NEW_WRAPPED_KEYSET", Scalar, 2, 2),
+    ("KEYS.REWRAP_KEYSET", Scalar, 3, 3),
+    ("KEYS.ROTATE_KEYSET", Scalar, 2, 2),
+    ("KEYS.ROTATE_WRAPPED_KEYSET", Scalar, 3, 3),
+    ("MD5", Scalar, 1, 1),
+    ("SHA1", Scalar, 1, 1),
+    ("SHA256", Scalar, 1, 1),
+    ("SHA512", Scalar, 1, 1),
+    // sketches
+    ("HLL_COUNT.EXTRACT", Scalar, 1, 1),
+    ("KLL_QUANTILES.EXTRACT_DOUBLE", Scalar, 2, 2),
+    ("KLL_QUANTILES.EXTRACT_INT64", Scalar, 2, 2),
+    ("KLL_QUANTILES.EXTRACT_POINT_DOUBLE", Scalar, 2, 2),
+    ("KLL_QUANTILES.EXTRACT_POINT_INT64", Scalar, 2, 2),
+    ("KLL_QUANTILES.EXTRACT_POINT_UINT64", Scalar, 2, 2),
+    ("KLL_QUANTILES.EXTRACT_UINT64", Scalar, 2, 2),
+    // JSON
+    ("BOOL", Scalar, 1, 1),
+    ("BOOL_ARRAY", Scalar, 1, 1),
+    ("FLOAT64", Scalar, 1, 2),
+    ("FLOAT64_ARRAY", Scalar, 1, 2),
+    ("INT64", Scalar, 1, 1),
+    ("INT64_ARRAY", Scalar, 1, 1),
+    ("JSON_ARRAY", Scalar, 0, VARIADIC),
+    ("JSON_ARRAY_APPEND", Scalar, 3, VARIADIC),
+    ("JSON_ARRAY_INSERT", Scalar, 3, VARIADIC),
+    ("JSON_EXTRACT", Scalar, 1, 2),
+    ("JSON_EXTRACT_ARRAY", Scalar, 1, 2),
+    ("JSON_EXTRACT_SCALAR", Scalar, 1, 2),
+    ("JSON_EXTRACT_STRING_ARRAY", Scalar, 1, 2),
+    ("JSON_KEYS", Scalar, 1, 3),
+    ("JSON_OBJECT", Scalar, 0, VARIADIC),
+    ("JSON_QUERY", Scalar, 2, 2),
+    ("JSON_QUERY_ARRAY", Scalar, 1, 2),
+    ("JSON_REMOVE", Scalar, 2, VARIADIC),
+    ("JSON_SET", Scalar, 3, VARIADIC),
+    ("JSON_STRIP_NULLS", Scalar, 1, 3),
+    ("JSON_TYPE", Scalar, 1, 1),
+    ("JSON_VALUE", Scalar, 1, 2),
+    ("JSON_VALUE_ARRAY", Scalar, 1, 2),
+    ("LAX_BOOL", Scalar, 1, 1),
+    ("LAX_FLOAT64", Scalar, 1, 1),
+    ("LAX_INT64", Scalar, 1, 1),
+    ("LAX_STRING", Scalar, 1, 1),
+    ("PARSE_JSON", Scalar, 1, 2),
+    ("STRING_ARRAY", Scalar, 1, 1),
+    ("TO_JSON", Scalar, 1, 2),
+    ("TO_JSON_STRING", Scalar, 1, 2),
+    // mathematical
+    ("ABS", Scalar, 1, 1),
+    ("ACOS", Scalar, 1, 1),
+    ("ACOSH", Scalar, 1, 1),
+    ("ASIN", Scalar, 1, 1),
+    ("ASINH", Scalar, 1, 1),
+    ("ATAN", Scalar, 1, 1),
+    ("ATAN2", Scalar, 2, 2),
+    ("ATANH", Scalar, 1, 1),
+    ("BIT_COUNT", Scalar, 1, 1),
+    ("CBRT", Scalar, 1, 1),
+    ("CEIL", Scalar, 1, 1),
+    ("CEILING", Scalar, 1, 1),
+    ("COS", Scalar, 1, 1),
+    ("COSH", Scalar, 1, 1),
+    ("COSINE_DISTANCE", Scalar, 2, 2),
+    ("COT", Scalar, 1, 1),
+    ("COTH", Scalar, 1, 1),
+    ("CSC", Scalar, 1, 1),
+    ("CSCH", Scalar, 1, 1),
+    ("DIV", Scalar, 2, 2),
+    ("EUCLIDEAN_DISTANCE", Scalar, 2, 2),
+    ("EXP", Scalar, 1, 1),
+    ("FLOOR", Scalar, 1, 1),
+    ("GREATEST", Scalar, 1, VARIADIC),
+    ("IEEE_DIVIDE", Scalar, 2, 2),
+    ("IS_INF", Scalar, 1, 1),
+    ("IS_NAN", Scalar, 1, 1),
+    ("LEAST", Scalar, 1, VARIADIC),
+    ("LN", Scalar, 1, 1),
+    ("LOG", Scalar, 1, 2),
+    ("LOG10", Scalar, 1, 1),
+    ("MOD", Scalar, 2, 2),
+    ("POW", Scalar, 2, 2),
+    ("POWER", Scalar, 2, 2),
+    ("RAND", Scalar, 0, 0),
+    ("RANGE_BUCKET", Scalar, 2, 2),
+    ("ROUND", Scalar, 1, 3),
+    ("SAFE_ADD", Scalar, 2, 2),
+    ("SAFE_DIVIDE", Scalar, 2, 2),
+    ("SAFE_MULTIPLY", Scalar, 2, 2),
+    ("SAFE_NEGATE", Scalar, 1, 1),
+    ("SAFE_SUBTRACT", Scalar, 2, 2),
+    ("SEC", Scalar, 1, 1),
+    ("SECH", Scalar, 1, 1),
+    ("SIGN", Scalar, 1, 1),
+    ("SIN", Scalar, 1, 1),
+    ("SINH", Scalar, 1, 1),
+    ("SQRT", Scalar, 1, 1),
+    ("TAN", Scalar, 1, 1),
+    ("TANH", Scalar, 1, 1),
+    ("TRUNC", Scalar, 1, 2),
+    // string
+    ("ASCII", Scalar, 1, 1),
+    ("BYTE_LENGTH", Scalar, 1, 1),
+    ("CHAR_LENGTH", Scalar, 1, 1),
+    ("CHARACTER_LENGTH", Scalar, 1, 1),
+    ("CHR", Scalar, 1, 1),
+    ("CODE_POINTS_TO_BYTES", Scalar, 1, 1),
+    ("CODE_POINTS_TO_STRING", Scalar, 1, 1),
+    ("COLLATE", Scalar, 2, 2),
+    ("CONCAT", Scalar, 1, VARIADIC),
+    ("CONTAINS_SUBSTR", Scalar, 2, 3),
+    ("EDIT_DISTANCE", Scalar, 2, 3),
+    ("ENDS_WITH", Scalar, 2, 2),
+    ("FORMAT", Scalar, 1, VARIADIC),
+    ("FROM_BASE32", Scalar, 1, 1),
+    ("FROM_BASE64", Scalar, 1, 1),
+    ("FROM_HEX", Scalar, 1, 1),
+    ("INITCAP", Scalar, 1, 2),
+    ("INSTR", Scalar, 2, 4),
+    ("LEFT", Scalar, 2, 2),
+    ("LENGTH", Scalar, 1, 1),
+    ("LOWER", Scalar, 1, 1),
+    ("LPAD", Scalar, 2, 3),
+    ("LTRIM", Scalar, 1, 2),
+    ("NORMALIZE", Scalar, 1, 2),
+    ("NORMALIZE_AND_CASEFOLD", Scalar, 1, 2),
+    ("OCTET_LENGTH", Scalar, 1, 1),
+    ("REGEXP_CONTAINS", Scalar, 2, 2),
+    ("REGEXP_EXTRACT", Scalar, 2, 4),
+    ("REGEXP_EXTRACT_ALL", Scalar, 2, 2),
+    ("REGEXP_INSTR", Scalar, 2, 5),
+    ("REGEXP_REPLACE", Scalar, 3, 3),
+    ("REGEXP_SUBSTR", Scalar, 2, 4),
+    ("REPEAT", Scalar, 2, 2),
+    ("REPLACE", Scalar, 3, 3),
+    ("REVERSE", Scalar, 1, 1),
+    ("RIGHT", Scalar, 2, 2),
+    ("RPAD", Scalar, 2, 3),
+    ("RTRIM", Scalar, 1, 2),
+    ("SAFE_CONVERT_BYTES_TO_STRING", Scalar, 1, 1),
+    ("SOUNDEX", Scalar, 1, 1),
+    ("SPLIT", Scalar, 1, 2),
+    ("STARTS_WITH", Scalar, 2, 2),
+    ("STRING", Scalar, 1, 2),
+    ("STRPOS", Scalar, 2, 2),
+    ("SUBSTR", Scalar, 2, 3),
+    ("SUBSTRING", Scalar, 2, 3),
+    ("TO_BASE32", Scalar, 1, 1),
+    ("TO_BASE64", Scalar, 1, 1),
+    ("TO_CODE_POINTS", Scalar, 1, 1),
+    ("TO_HEX", Scalar, 1, 1),
+    ("TRANSLATE", Scalar, 3, 3),
+    ("TRIM", Scalar, 1, 2),
+    ("UNICODE", Scalar, 1, 1),
+    ("UPPER", Scalar, 1, 1),
+    // search, text analysis
+    ("BAG_OF_WORDS", Scalar, 1, 1),
+    ("SEARCH", Scalar, 2, VARIADIC),
+    ("TEXT_ANALYZE", Scalar, 1, VARIADIC),
+    // network
+    ("NET.HOST", Scalar, 1, 1),
+    ("NET.IP_FROM_STRING", Scalar, 1, 1),
+    ("NET.IP_NET_MASK", Scalar, 2, 2),
+    ("NET.IP_TO_STRING", Scalar, 1, 1),
+    ("NET.IP_TRUNC", Scalar, 2, 2),
+    ("NET.IPV4_FROM_INT64", Scalar, 1, 1),
+    ("NET.IPV4_TO_INT64", Scalar, 1, 1),
+    ("NET.PUBLIC_SUFFIX", Scalar, 1, 1),
+    ("NET.REG_DOMAIN", Scalar, 1, 1),
+    ("NET.SAFE_IP_FROM_STRING", Scalar, 1, 1),
+    // ML (preprocessing functions used in TRANSFORM clause or queries)
+    ("ML.BAG_OF_WORDS", Analytic, 1, 3),
+    ("ML.BUCKETIZE", Scalar, 2, 3),
+    ("ML.CONVERT_COLOR_SPACE", Scalar, 2, 2),
+    ("ML.CONVERT_IMAGE_TYPE", Scalar, 2, 2),
+    ("ML.DECODE_IMAGE", Scalar, 1, 1),
+    ("ML.DISTANCE", Scalar, 2, 3),
+    ("ML.FEATURE_CROSS", Scalar, 1, 2),
+    ("ML.HASH_BUCKETIZE", Scalar, 2, 2),
+    ("ML.IMPUTER", Analytic, 2, 2),
+    ("ML.LABEL_ENCODER", Analytic, 1, 3),
+    ("ML.LP_NORM", Scalar, 2, 2),
+    ("ML.MAX_ABS_SCALER", Analytic, 1, 1),
+    ("ML.MIN_MAX_SCALER", Analytic, 1, 1),
+    ("ML.MULTI_HOT_ENCODER", Analytic, 1, 3),
+    ("ML.NGRAMS", Scalar, 2, 3),
+    ("ML.NORMALIZER", Scalar, 1, 2),
+    ("ML.ONE_HOT_ENCODER", Analytic, 1, 4),
+    ("ML.POLYNOMIAL_EXPAND", Scalar, 1, 2),
+    ("ML.QUANTILE_BUCKETIZE", Analytic, 2, 2),
+    ("ML.RESIZE_IMAGE", Scalar, 3, 4),
+    ("ML.ROBUST_SCALER", Analytic, 1, 3),
+    ("ML.STANDARD_SCALER", Analytic, 1, 1),
+    ("ML.TF_IDF", Analytic, 1, 3),
+    // AI
+    ("AI.CLASSIFY", Scalar, 2, VARIADIC),
+    ("AI.EMBED", Scalar, 1, VARIADIC),
+    ("AI.GENERATE", Scalar, 1, VARIADIC),
+    ("AI.GENERATE_BOOL", Scalar, 1, VARIADIC),
+    ("AI.GENERATE_DOUBLE", Scalar, 1, VARIADIC),
+    ("AI.GENERATE_INT", Scalar, 1, VARIADIC),
+    ("AI.IF", Scalar, 1, VARIADIC),
+    ("AI.SCORE", Scalar, 1, VARIADIC),
+    ("AI.SIMILARITY", Scalar, 2, VARIADIC),
+    // object tables
+    ("OBJ.FETCH_METADATA", Scalar, 1, 1),
+    ("OBJ.GET_ACCESS_URL", Scalar, 2, 3),
+    ("OBJ.MAKE_REF", Scalar, 1, 2),
+    // others (including the syntax which looks like function)
+    ("CLASSIFIER", Scalar, 0, 0),        // MATCH_RECOGNIZE
+    ("CUBE", Scalar, 1, VARIADIC),       // GROUPING SETS(CUBE(...))
+    ("DESCRIPTOR", Scalar, 1, VARIADIC), // arguments of TVF
+    ("EXISTS", Scalar, 1, 1),
+    ("FIRST", Scalar, 1, 1), // MATCH_RECOGNIZE
+    ("GENERATE_UUID", Scalar, 0, 0),
+    ("LAST", Scalar, 1, 1),             // MATCH_RECOGNIZE
+    ("MATCH_NUMBER", Scalar, 0, 0),     // MATCH_RECOGNIZE
+    ("MATCH_ROW_NUMBER", Scalar, 0, 0), // MATCH_RECOGNIZE
+    ("NEXT", Scalar, 1, 2),             // MATCH_RECOGNIZE
+    ("OFFSET", Scalar, 1, 1),           // arr[OFFSET(0)]
+    ("ORDINAL", Scalar, 1, 1),
+    ("PREV", Scalar, 1, 2),          // MATCH_RECOGNIZE
+    ("ROLLUP", Scalar, 1, VARIADIC), // GROUPING SETS(ROLLUP(...))
+    ("SAFE_OFFSET", Scalar, 1, 1),
+    ("SAFE_ORDINAL", Scalar, 1, 1),
+    ("SESSION_USER", Scalar, 0, 0),
+    ("WEEK", Scalar, 1, 1),        // date part (e.g. WEEK(MONDAY))
+    ("WITH", Scalar, 2, VARIADIC), // WITH(a AS 1, a + 1)
+];
+
+/// Finds the built-in function (e.g. `substr`, `NET.HOST`). `SAFE.` prefix is not accepted.
+pub fn find_builtin(name: &str) -> Option<Builtin> {
+    BUILTINS
+        .iter()
+        .find(|(n, _, _, _)| n.eq_ignore_ascii_case(name))
+        .map(|&(name, kind, min_args, max_args)| Builtin {
+            name,
+            kind,
+            min_args,
+            max_args,
+        })
+}
+
+/// Checks the calls of the built-in functions (name, number of arguments and
+/// the clauses such as OVER and DISTINCT). Calls of user-defined functions are
+/// not checked.
+pub fn check_functions(stmts: &[Node]) -> Vec<Diagnostic> {
+    let mut checker = Checker {
+        udfs: Vec::new(),
+        statement: 0,
+        diagnostics: Vec::new(),
+    };
+    for stmt in stmts {
+        checker.collect_udfs(stmt);
+    }
+    for (idx, stmt) in stmts.iter().enumerate() {
+        checker.statement = idx;
+        checker.walk(stmt, 0);
+    }
+    checker.diagnostics
+}
+
+struct Checker {
+    udfs: Vec<String>, // functions created in the script
+    statement: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker {
+    fn collect_udfs(&mut self, node: &Node) {
+        if node.node_type == NodeType::CreateFunctionStatement {
+            if let Some(parts) = node.get_node("ident").and_then(|i| i.identifier_parts()) {
+                self.udfs.push(parts.join("."));
+            }
+        }
+        for (_, child) in node.child_nodes() {
+            self.collect_udfs(child);
+        }
+    }
+    /// `receiver` is 1 if the function is called by the chained syntax (e.g. `(x).UPPER()`).
+    fn walk(&mut self, node: &Node, receiver: usize) {
+        match node.node_type {
+            NodeType::CallingFunction => self.call(node, receiver),
+            NodeType::FunctionChain => {
+                if let Some(left) = node.get_node("left") {
+                    self.walk(left, 0);
+                }
+                if let Some(right) = node.get_node("right") {
+                    self.walk(right, 1);
+                }
+                return;
+            }
+            NodeType::CallStatement => {
+                // the procedure is not a function
+                if let Some(procedure) = node.get_node("procedure") {
+                    self.walk_args(procedure);
+                }
+                return;
+            }
+            NodeType::BasePipeOperator if node.literal().eq_ignore_ascii_case("CALL") => {
+                // |> CALL tvf()
+                for tvf in node.get_node_vec("exprs").into_iter().flatten() {
+                    self.walk_args(tvf);
+                }
+                return;
+            }
+            _ => (),
+        }
+        for (key, child) in node.child_nodes() {
+            match key {
+                "func" if node.node_type == NodeType::CallingFunction => continue,
+                // USING(col), REFERENCES t(col)
+                "using" | "references" => continue,
+                _ => self.walk(child, 0),
+            }
+        }
+    }
+    fn walk_args(&mut self, node: &Node) {
+        for arg in node.get_node_vec("args").into_iter().flatten() {
+            self.walk(arg, 0);
+        }
+    }
+    fn call(&mut self, node: &Node, receiver: usize) {
+        let Some(func) = node.get_node("func") else {
+            return;
+        };
+        let Some(parts) = func.identifier_parts() else {
+            return;
+        };
+        let name = parts.join(".");
+        let (safe, unprefixed) = match parts.split_first() {
+            Some((first, rest)) if first.eq_ignore_ascii_case("SAFE") && !rest.is_empty() => {
+                (true, rest.join("."))
+            }
+            _ => (false, name.clone()),
+        };
+        let builtin = match find_builtin(&unprefixed) {
+            Some(builtin) => builtin,
+            None => {
+                let namespace = unprefixed.split('.').next().unwrap_or_default();
+                let qualified = unprefixed.contains('.');
+                let defined = self.udfs.iter().any(|u| u.eq_ignore_ascii_case(&name));
+                // other qualified names are user-defined functions (e.g. `ds.f`)
+                let builtin_namespace =
+                    NAMESPACES.iter().any(|n| n.eq_ignore_ascii_case(namespace));
+                if !defined && (!qualified || builtin_namespace) {
+                    self.report(
+                        DiagnosticKind::UnknownFunction,
+                        format!("Function not found: {}.", name),
+                        func,
+                    );
+                }
+                return;
+            }
+        };
+        let label = builtin.name;
+        let args = node.get_node_vec("args").map_or(0, |a| a.len()) + receiver;
+        if args < builtin.min_args || builtin.max_args < args {
+            let expected = match (builtin.min_args, builtin.max_args) {
+                (min, VARIADIC) => format!("at least {}", min),
+                (min, max) if min == max => min.to_string(),
+                (min, max) => format!("{} to {}", min, max),
+            };
+            self.report(
+                DiagnosticKind::WrongArgumentCount,
+                format!(
+                    "Number of arguments does not match for function {}. Expected {} but got {}.",
+                    label, expected, args
+                ),
+                func,
+            );
+        }
+        if safe && builtin.kind != Scalar {
+            self.report(
+                DiagnosticKind::InvalidFunctionCall,
+                format!(
+                    "SAFE. prefix is not supported for {} function {}.",
+                    match builtin.kind {
+                        Aggregate => "aggregate",
+                        _ => "analytic",
+                    },
+                    label
+                ),
+                func,
+            );
+        }
+        match (node.get_node("over"), builtin.kind) {
+            (Some(over), Scalar) => self.report(
+                DiagnosticKind::InvalidFunctionCall,
+                format!("OVER clause is not supported for function {}.", label),
+                over,
+            ),
+            (None, Analytic) => self.report(
+                DiagnosticKind::InvalidFunctionCall,
+                format!(
+                    "Analytic function {} cannot be called without an OVER clause.",
+                    label
+                ),
+                func,
+            ),
+            _ => (),
+        }
+        if let Some(distinct) = node.get_node("distinct") {
+            if !DISTINCT.contains(&label) {
+                self.report(
+                    DiagnosticKind::InvalidFunctionCall,
+                    format!("DISTINCT is not supported for function {}.", label),
+                    distinct,
+                );
+            }
+        }
+        if let Some(nulls) = node.get_node_vec("ignore_nulls") {
+            if !IGNORE_NULLS.contains(&label) {
+                let keywords: Vec<String> =
+                    nulls.iter().map(|n| n.literal().to_uppercase()).collect();
+                self.report(
+                    DiagnosticKind::InvalidFunctionCall,
+                    format!(
+                        "{} is not supported for function {}.",
+                        keywords.join(" "),
+                        label
+                    ),
+                    &nulls[0],
+                );
+            }
+        }
+        for (key, clause) in [("orderby", "ORDER BY"), ("limit", "LIMIT")] {
+            if let Some(child) = node.get_node(key) {
+                if !ORDER_BY.contains(&label) {
+                    self.report(
+                        DiagnosticKind::InvalidFunctionCall,
+                        format!("{} is not supported for function {}.", clause, label),
+                        child,
+                    );
+                }
+            }
+        }
+    }
+    fn report(&mut self, kind: DiagnosticKind, message: String, node: &Node) {
+        let diagnostic = Diagnostic::new(kind, message, self.statement, node);
+        self.diagnostics.push(diagnostic);
+    }
+}
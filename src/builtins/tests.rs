@@ -0,0 +1,171 @@
+use super::*;
+use crate::validate::tests::{diagnostic_tuples, parse};
+
+fn diagnostics(code: &str) -> Vec<(DiagnosticKind, String, usize, usize)> {
+    diagnostic_tuples(check_functions(&parse(code)))
+}
+
+#[test]
+fn test_find_builtin() {
+    assert_eq!(
+        find_builtin("substr"),
+        Some(Builtin {
+            name: "SUBSTR",
+            kind: FunctionKind::Scalar,
+            min_args: 2,
+            max_args: 3,
+        })
+    );
+    assert_eq!(find_builtin("net.host").map(|b| b.name), Some("NET.HOST"));
+    assert_eq!(
+        find_builtin("ROW_NUMBER").map(|b| b.kind),
+        Some(FunctionKind::Analytic)
+    );
+    assert_eq!(
+        find_builtin("array_agg").map(|b| b.kind),
+        Some(FunctionKind::Aggregate)
+    );
+    assert_eq!(find_builtin("SAFE.SUBSTR"), None);
+    assert_eq!(find_builtin("not_exist"), None);
+}
+
+#[test]
+fn test_check_functions_valid() {
+    let res = diagnostics(
+        "\
+CREATE TEMP FUNCTION my_func(x INT64) AS (x + 1);
+SELECT
+  SUBSTR('abc', 1), SAFE.SUBSTR('abc', 1, 2), NET.HOST(url), SAFE.NET.HOST(url),
+  KEYS.NEW_KEYSET('AEAD_AES_GCM_256'), ML.STANDARD_SCALER(x) OVER (), COUNT(*),
+  COUNT(DISTINCT x) OVER (PARTITION BY y), ROW_NUMBER() OVER w, my_func(1), ds.udf(1, 2, 3),
+  ARRAY_AGG(x IGNORE NULLS ORDER BY y LIMIT 10), STRING_AGG(s, ',' ORDER BY s),
+  LAST_VALUE(x IGNORE NULLS) OVER w, ('abc').UPPER(), CAST(x AS STRING), COALESCE(a, b, c),
+  ARRAY(SELECT 1), WITH(a AS 1, a + 1), CURRENT_DATE()
+FROM t WINDOW w AS (ORDER BY x);
+SELECT * FROM ds.tvf(1, 2) AS t, ML.PREDICT(MODEL m, TABLE t);
+CALL ds.procedure(UPPER('a'));
+SELECT arr[OFFSET(0)], EXISTS(SELECT 1), DATE_TRUNC(d, WEEK(MONDAY)) FROM a JOIN b USING (id);
+CREATE TABLE ds.t (x STRING REFERENCES ds.u(y) NOT ENFORCED);
+",
+    );
+    assert_eq!(res, vec![]);
+}
+
+#[test]
+fn test_check_functions_invalid() {
+    let res = diagnostics(
+        "\
+SELECT not_exist(1), NET.not_exist('x'), SAFE.not_exist(1), SUBSTR('a'), CONCAT(), UPPER(s, 1);
+SELECT UPPER(s) OVER (), ROW_NUMBER(), SAFE.SUM(x), ('a').SUBSTR();
+SELECT UPPER(DISTINCT s), ROW_NUMBER(DISTINCT x) OVER (), SUM(x IGNORE NULLS),
+  MAX(x ORDER BY y), LOWER(x LIMIT 1), ARRAY_AGG(x RESPECT NULLS);
+",
+    );
+    assert_eq!(
+        res,
+        vec![
+            (
+                DiagnosticKind::UnknownFunction,
+                "Function not found: not_exist.".to_string(),
+                1,
+                8
+            ),
+            (
+                DiagnosticKind::UnknownFunction,
+                "Function not found: NET.not_exist.".to_string(),
+                1,
+                22
+            ),
+            (
+                DiagnosticKind::UnknownFunction,
+                "Function not found: SAFE.not_exist.".to_string(),
+                1,
+                42
+            ),
+            (
+                DiagnosticKind::WrongArgumentCount,
+                "Number of arguments does not match for function SUBSTR. Expected 2 to 3 but got 1."
+                    .to_string(),
+                1,
+                61
+            ),
+            (
+                DiagnosticKind::WrongArgumentCount,
+                "Number of arguments does not match for function CONCAT. Expected at least 1 but got 0."
+                    .to_string(),
+                1,
+                74
+            ),
+            (
+                DiagnosticKind::WrongArgumentCount,
+                "Number of arguments does not match for function UPPER. Expected 1 but got 2."
+                    .to_string(),
+                1,
+                84
+            ),
+            (
+                DiagnosticKind::InvalidFunctionCall,
+                "OVER clause is not supported for function UPPER.".to_string(),
+                2,
+                17
+            ),
+            (
+                DiagnosticKind::InvalidFunctionCall,
+                "Analytic function ROW_NUMBER cannot be called without an OVER clause."
+                    .to_string(),
+                2,
+                26
+            ),
+            (
+                DiagnosticKind::InvalidFunctionCall,
+                "SAFE. prefix is not supported for aggregate function SUM.".to_string(),
+                2,
+                40
+            ),
+            (
+                DiagnosticKind::WrongArgumentCount,
+                "Number of arguments does not match for function SUBSTR. Expected 2 to 3 but got 1."
+                    .to_string(),
+                2,
+                59
+            ),
+            (
+                DiagnosticKind::InvalidFunctionCall,
+                "DISTINCT is not supported for function UPPER.".to_string(),
+                3,
+                14
+            ),
+            (
+                DiagnosticKind::WrongArgumentCount,
+                "Number of arguments does not match for function ROW_NUMBER. Expected 0 but got 1."
+                    .to_string(),
+                3,
+                27
+            ),
+            (
+                DiagnosticKind::InvalidFunctionCall,
+                "DISTINCT is not supported for function ROW_NUMBER.".to_string(),
+                3,
+                38
+            ),
+            (
+                DiagnosticKind::InvalidFunctionCall,
+                "IGNORE NULLS is not supported for function SUM.".to_string(),
+                3,
+                65
+            ),
+            (
+                DiagnosticKind::InvalidFunctionCall,
+                "ORDER BY is not supported for function MAX.".to_string(),
+                4,
+                9
+            ),
+            (
+                DiagnosticKind::InvalidFunctionCall,
+                "LIMIT is not supported for function LOWER.".to_string(),
+                4,
+                30
+            ),
+        ]
+    );
+}
@@ -0,0 +1,701 @@
+#[cfg(test)]
+mod tests;
+
+use crate::bqtype::ScalarType;
+use crate::cst::{Node, NodeType};
+use crate::literal::{self, LiteralValue};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// ----- BigQuery JSON schema -----
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct RangeElementType {
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableFieldSchema {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String, // INTEGER, STRING, RECORD, ...
+    #[serde(default = "nullable")]
+    pub mode: String, // NULLABLE, REQUIRED or REPEATED
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<TableFieldSchema>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_value_expression: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_element_type: Option<RangeElementType>,
+}
+
+// ----- catalog -----
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub enum TableKind {
+    Table,
+    ExternalTable,
+    View,
+    MaterializedView,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct ForeignKey {
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+    pub referenced_table: String,
+    pub referenced_columns: Vec<String>,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct Table {
+    pub name: String,
+    pub kind: TableKind,
+    pub temp: bool,
+    pub schema: Vec<TableFieldSchema>, // empty if unknown (e.g. CREATE TABLE AS SELECT)
+    pub options: BTreeMap<String, String>,
+    pub primary_key: Vec<String>,
+    pub foreign_keys: Vec<ForeignKey>,
+    pub partition_by: Option<String>,
+    pub cluster_by: Vec<String>,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct Dataset {
+    pub name: String, // `project.dataset`, `dataset` or empty (for unqualified tables)
+    pub options: BTreeMap<String, String>,
+    pub tables: Vec<Table>,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct CatalogIssue {
+    pub message: String,
+    pub statement: usize, // index of the statement
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct Catalog {
+    pub datasets: Vec<Dataset>,
+    pub issues: Vec<CatalogIssue>, // statements which could not be applied
+    #[serde(skip)]
+    dropped: Vec<Dataset>, // for UNDROP SCHEMA
+}
+
+/// Replays the DDL statements in order and returns the resulting catalog.
+pub fn build_catalog(stmts: &[Node]) -> Catalog {
+    let mut catalog = Catalog {
+        datasets: Vec::new(),
+        issues: Vec::new(),
+        dropped: Vec::new(),
+    };
+    for (idx, stmt) in stmts.iter().enumerate() {
+        if let Err(message) = catalog.apply(stmt) {
+            let (line, column) = stmt.position();
+            catalog.issues.push(CatalogIssue {
+                message,
+                statement: idx,
+                line,
+                column,
+            });
+        }
+    }
+    catalog
+}
+
+impl Catalog {
+    /// Adds (or replaces) a table whose schema is given by a JSON schema file.
+    pub fn add_table(&mut self, name: &str, schema: Vec<TableFieldSchema>) {
+        let table = Table {
+            name: String::new(),
+            kind: TableKind::Table,
+            temp: false,
+            schema,
+            options: BTreeMap::new(),
+            primary_key: Vec::new(),
+            foreign_keys: Vec::new(),
+            partition_by: None,
+            cluster_by: Vec::new(),
+        };
+        self.put_table(&unquote(name), table);
+    }
+    pub fn find_table(&self, name: &str) -> Option<&Table> {
+        let (dataset, table) = split_name(name);
+        self.dataset(&dataset)?
+            .tables
+            .iter()
+            .find(|t| t.name == table)
+    }
+    fn dataset(&self, name: &str) -> Option<&Dataset> {
+        self.datasets.iter().find(|d| d.name == name)
+    }
+    fn dataset_mut(&mut self, name: &str) -> &mut Dataset {
+        let idx = match self.datasets.iter().position(|d| d.name == name) {
+            Some(idx) => idx,
+            None => {
+                self.datasets.push(Dataset {
+                    name: name.to_string(),
+                    options: BTreeMap::new(),
+                    tables: Vec::new(),
+                });
+                self.datasets.len() - 1
+            }
+        };
+        &mut self.datasets[idx]
+    }
+    fn table_mut(&mut self, name: &str) -> Result<&mut Table, String> {
+        let (dataset, table) = split_name(name);
+        self.datasets
+            .iter_mut()
+            .find(|d| d.name == dataset)
+            .and_then(|d| d.tables.iter_mut().find(|t| t.name == table))
+            .ok_or(format!("Table {} is not found.", name))
+    }
+    fn remove_table(&mut self, name: &str) -> Option<Table> {
+        let (dataset, table) = split_name(name);
+        let dataset = self.datasets.iter_mut().find(|d| d.name == dataset)?;
+        let idx = dataset.tables.iter().position(|t| t.name == table)?;
+        Some(dataset.tables.remove(idx))
+    }
+    fn put_table(&mut self, name: &str, mut table: Table) {
+        let (dataset, table_name) = split_name(name);
+        table.name = table_name;
+        let dataset = self.dataset_mut(&dataset);
+        match dataset.tables.iter().position(|t| t.name == table.name) {
+            Some(idx) => dataset.tables[idx] = table,
+            None => dataset.tables.push(table),
+        }
+    }
+
+    fn apply(&mut self, stmt: &Node) -> Result<(), String> {
+        match stmt.node_type {
+            NodeType::CreateSchemaStatement => self.create_schema(stmt),
+            NodeType::CreateTableStatement | NodeType::CreateViewStatement => {
+                self.create_table(stmt)
+            }
+            NodeType::AlterTableStatement | NodeType::AlterViewStatement => self.alter_table(stmt),
+            NodeType::DropStatement => self.drop(stmt),
+            NodeType::UndropStatement => self.undrop(stmt),
+            _ => Ok(()),
+        }
+    }
+    fn create_schema(&mut self, stmt: &Node) -> Result<(), String> {
+        let name = ident_name(stmt)?;
+        if self.dataset(&name).is_some() {
+            if stmt.get_node_vec("if_not_exists").is_some() {
+                return Ok(());
+            }
+            if stmt.get_node_vec("or_replace").is_none() {
+                return Err(format!("Schema {} already exists.", name));
+            }
+            self.datasets.retain(|d| d.name != name);
+        }
+        let options = options(stmt);
+        self.dataset_mut(&name).options = options;
+        Ok(())
+    }
+    fn create_table(&mut self, stmt: &Node) -> Result<(), String> {
+        let name = ident_name(stmt)?;
+        if self.find_table(&name).is_some() {
+            if stmt.get_node_vec("if_not_exists").is_some() {
+                return Ok(());
+            }
+            if stmt.get_node_vec("or_replace").is_none() {
+                return Err(format!("Table {} already exists.", name));
+            }
+        }
+        let kind = if stmt.node_type == NodeType::CreateViewStatement {
+            if stmt.get_node("materialized").is_some() {
+                TableKind::MaterializedView
+            } else {
+                TableKind::View
+            }
+        } else if stmt.get_node("external").is_some() {
+            TableKind::ExternalTable
+        } else {
+            TableKind::Table
+        };
+        let mut table = match stmt.get_node("source_table") {
+            // LIKE | COPY | CLONE
+            Some(source) => {
+                let source_name = identifier_name(source)?;
+                match self.find_table(&source_name) {
+                    Some(source) => source.clone(),
+                    None => return Err(format!("Table {} is not found.", source_name)),
+                }
+            }
+            None => Table {
+                name: "".to_string(),
+                kind: kind.clone(),
+                temp: false,
+                schema: Vec::new(),
+                options: BTreeMap::new(),
+                primary_key: Vec::new(),
+                foreign_keys: Vec::new(),
+                partition_by: None,
+                cluster_by: Vec::new(),
+            },
+        };
+        table.kind = kind;
+        table.temp = stmt.get_node("temp").is_some();
+        if let Some(group) = stmt.get_node("column_schema_group") {
+            table.schema = Vec::new();
+            for decl in group.get_node_vec("declarations").into_iter().flatten() {
+                match decl.node_type {
+                    NodeType::TypeDeclaration => table.schema.push(field(decl)),
+                    NodeType::Constraint => add_constraint(&mut table, decl),
+                    _ => (),
+                }
+            }
+        }
+        if let Some(partition_by) = stmt.get_node("partitionby") {
+            table.partition_by = partition_by
+                .get_node_vec("exprs")
+                .map(|exprs| exprs.iter().map(sql_text).collect::<Vec<_>>().join(", "));
+        }
+        if let Some(cluster_by) = stmt.get_node("clusterby") {
+            table.cluster_by = cluster_by
+                .get_node_vec("exprs")
+                .into_iter()
+                .flatten()
+                .map(sql_text)
+                .collect();
+        }
+        table.options.extend(options(stmt));
+        // OPTIONS of column_name_list of CREATE VIEW
+        if let Some(list) = stmt.get_node("column_name_list") {
+            for ident in list.get_node_vec("idents").into_iter().flatten() {
+                let description = options(ident).remove("description");
+                if let (Some(description), Some(f)) =
+                    (description, find_field(&mut table.schema, ident.literal()))
+                {
+                    f.description = Some(description);
+                }
+            }
+        }
+        self.put_table(&name, table);
+        Ok(())
+    }
+    fn alter_table(&mut self, stmt: &Node) -> Result<(), String> {
+        let name = ident_name(stmt)?;
+        if self.find_table(&name).is_none() {
+            if stmt.get_node_vec("if_exists").is_some() {
+                return Ok(());
+            }
+            return Err(format!("Table {} is not found.", name));
+        }
+        // RENAME TO
+        if let Some(to) = stmt.get_node("to") {
+            let new_name = to.get_node("expr").map(identifier_name).transpose()?;
+            let table = self.remove_table(&name).unwrap();
+            let (dataset, _) = split_name(&name);
+            let new_name = match (dataset.as_str(), new_name) {
+                ("", Some(n)) => n,
+                (d, Some(n)) => format!("{}.{}", d, n),
+                (_, None) => return Err("Invalid RENAME TO.".to_string()),
+            };
+            self.put_table(&new_name, table);
+            return Ok(());
+        }
+        let table = self.table_mut(&name)?;
+        // SET OPTIONS
+        if stmt.get_node("set").is_some() {
+            for (key, value) in options(stmt) {
+                if value == "NULL" {
+                    table.options.remove(&key);
+                } else {
+                    table.options.insert(key, value);
+                }
+            }
+        }
+        // SET DEFAULT COLLATE
+        if let Some(collate) = stmt
+            .get_node("default_collate")
+            .and_then(|d| d.get_node("next_keyword"))
+            .and_then(|c| c.get_node("expr"))
+        {
+            table
+                .options
+                .insert("default_collate".to_string(), sql_value(collate));
+        }
+        for add in stmt.get_node_vec("add_columns").into_iter().flatten() {
+            let decl = match add.get_node("type_declaration") {
+                Some(decl) => decl,
+                None => continue,
+            };
+            if find_field(&mut table.schema, decl.literal()).is_some() {
+                if add.get_node_vec("if_not_exists").is_some() {
+                    continue;
+                }
+                return Err(format!("Column {} already exists.", decl.literal()));
+            }
+            table.schema.push(field(decl));
+        }
+        for add in stmt.get_node_vec("add_constraints").into_iter().flatten() {
+            if let Some(constraint) = add.get_node("what") {
+                add_constraint(table, constraint);
+            }
+        }
+        for drop in stmt.get_node_vec("drop_columns").into_iter().flatten() {
+            let what = drop.get_node("what").map(|w| w.literal().to_uppercase());
+            let ident = drop.get_node("ident").map(|i| unquote(i.literal()));
+            match (what.as_deref(), ident) {
+                (Some("PRIMARY"), _) => table.primary_key.clear(),
+                (Some("CONSTRAINT"), Some(ident)) => table
+                    .foreign_keys
+                    .retain(|fk| fk.name.as_deref() != Some(ident.as_str())),
+                (_, Some(ident)) => {
+                    let len = table.schema.len();
+                    table
+                        .schema
+                        .retain(|f| !f.name.eq_ignore_ascii_case(&ident));
+                    if len == table.schema.len() && drop.get_node_vec("if_exists").is_none() {
+                        return Err(format!("Column {} is not found.", ident));
+                    }
+                }
+                _ => (),
+            }
+        }
+        for rename in stmt.get_node_vec("rename_columns").into_iter().flatten() {
+            let from = rename.get_node("ident").map(|i| unquote(i.literal()));
+            let to = rename
+                .get_node("to")
+                .and_then(|t| t.get_node("expr"))
+                .map(|i| unquote(i.literal()));
+            let (Some(from), Some(to)) = (from, to) else {
+                continue;
+            };
+            match find_field(&mut table.schema, &from) {
+                Some(f) => f.name = to,
+                None if rename.get_node("if_exists").is_some() => (),
+                None => return Err(format!("Column {} is not found.", from)),
+            }
+        }
+        if let Some(alter) = stmt.get_node("alter_column_stmt") {
+            let column = alter.get_node("ident").map(|i| i.literal()).unwrap_or("");
+            let f = match find_field(&mut table.schema, column) {
+                Some(f) => f,
+                None if alter.get_node_vec("if_exists").is_some() => return Ok(()),
+                None => return Err(format!("Column {} is not found.", unquote(column))),
+            };
+            if let Some(type_) = alter.get_node("type") {
+                let mode = f.mode.clone();
+                let mut new = field_from_type(&f.name, type_);
+                new.description = f.description.clone();
+                new.default_value_expression = f.default_value_expression.clone();
+                if mode == "REQUIRED" {
+                    new.mode = mode;
+                }
+                *f = new;
+            }
+            if alter.get_node_vec("drop_not_null").is_some() {
+                f.mode = "NULLABLE".to_string();
+            }
+            if alter.get_node_vec("drop_default").is_some() {
+                f.default_value_expression = None;
+            }
+            if let Some(default) = alter.get_node("default").and_then(|d| d.get_node("expr")) {
+                f.default_value_expression = Some(sql_text(default));
+            }
+            if let Some(description) = options(alter).remove("description") {
+                f.description = if description == "NULL" {
+                    None
+                } else {
+                    Some(description)
+                };
+            }
+        }
+        Ok(())
+    }
+    fn drop(&mut self, stmt: &Node) -> Result<(), String> {
+        let name = ident_name(stmt)?;
+        let what = stmt
+            .get_node("what")
+            .map(|w| w.literal().to_uppercase())
+            .unwrap_or_default();
+        match what.as_str() {
+            "SCHEMA" => match self.datasets.iter().position(|d| d.name == name) {
+                Some(idx) => {
+                    let dataset = self.datasets.remove(idx);
+                    self.dropped.retain(|d| d.name != name);
+                    self.dropped.push(dataset);
+                }
+                None if stmt.get_node_vec("if_exists").is_some() => (),
+                None => return Err(format!("Schema {} is not found.", name)),
+            },
+            "TABLE" | "VIEW" => {
+                let removed = self.remove_table(&name);
+                if removed.is_none() && stmt.get_node_vec("if_exists").is_none() {
+                    return Err(format!("Table {} is not found.", name));
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+    fn undrop(&mut self, stmt: &Node) -> Result<(), String> {
+        let name = ident_name(stmt)?;
+        if self.dataset(&name).is_some() {
+            if stmt.get_node_vec("if_not_exists").is_some() {
+                return Ok(());
+            }
+            return Err(format!("Schema {} already exists.", name));
+        }
+        match self.dropped.iter().position(|d| d.name == name) {
+            Some(idx) => {
+                let dataset = self.dropped.remove(idx);
+                self.datasets.push(dataset);
+                Ok(())
+            }
+            None => Err(format!("Dropped schema {} is not found.", name)),
+        }
+    }
+}
+
+// ----- helpers -----
+fn nullable() -> String {
+    "NULLABLE".to_string()
+}
+
+fn unquote(s: &str) -> String {
+    s.trim_matches('`').to_string()
+}
+
+fn identifier_name(node: &Node) -> Result<String, String> {
+    match node.identifier_parts() {
+        Some(parts) => Ok(parts.join(".")),
+        None => Err("Invalid identifier.".to_string()),
+    }
+}
+
+fn ident_name(stmt: &Node) -> Result<String, String> {
+    match stmt.get_node("ident") {
+        Some(ident) => identifier_name(ident),
+        None => Err("Identifier is not found.".to_string()),
+    }
+}
+
+// `project.dataset.table` -> (`project.dataset`, `table`)
+fn split_name(name: &str) -> (String, String) {
+    match name.rsplit_once('.') {
+        Some((dataset, table)) => (dataset.to_string(), table.to_string()),
+        None => ("".to_string(), name.to_string()),
+    }
+}
+
+fn find_field<'a>(
+    fields: &'a mut [TableFieldSchema],
+    name: &str,
+) -> Option<&'a mut TableFieldSchema> {
+    let name = unquote(name);
+    fields
+        .iter_mut()
+        .find(|f| f.name.eq_ignore_ascii_case(&name))
+}
+
+/// Reconstructs SQL from the tokens of the node (without its trailing comma).
+fn sql_text(node: &Node) -> String {
+    fn collect<'a>(node: &'a Node, tokens: &mut Vec<&'a crate::token::Token>) {
+        if let Some(token) = &node.token {
+            tokens.push(token);
+        }
+        for (_, child) in node.child_nodes() {
+            collect(child, tokens);
+        }
+    }
+    let mut tokens = Vec::new();
+    if let Some(token) = &node.token {
+        tokens.push(token);
+    }
+    for (key, child) in node.child_nodes() {
+        if key != "comma" {
+            collect(child, &mut tokens);
+        }
+    }
+    tokens.sort_by_key(|t| (t.line, t.column));
+    let mut res = String::new();
+    let mut prev: Option<&str> = None;
+    for token in tokens {
+        let literal = token.literal.as_str();
+        let space = match prev {
+            None => false,
+            Some("(") | Some("[") | Some(".") => false,
+            Some(_) => !matches!(literal, "(" | ")" | "[" | "]" | "," | "."),
+        };
+        if space {
+            res.push(' ');
+        }
+        res.push_str(literal);
+        prev = Some(literal);
+    }
+    res
+}
+
+/// The value of an option. String literals are decoded.
+fn sql_value(node: &Node) -> String {
+    if node.node_type == NodeType::StringLiteral {
+        if let Ok(decoded) = literal::decode_string_literal(node.token.as_ref().unwrap()) {
+            if let LiteralValue::String(s) = decoded.value {
+                return s;
+            }
+        }
+    }
+    if node.node_type == NodeType::NullLiteral {
+        return "NULL".to_string();
+    }
+    sql_text(node)
+}
+
+fn options(node: &Node) -> BTreeMap<String, String> {
+    let mut res = BTreeMap::new();
+    let exprs = node
+        .get_node("options")
+        .and_then(|o| o.get_node("group"))
+        .and_then(|g| g.get_node_vec("exprs"));
+    for expr in exprs.into_iter().flatten() {
+        if let (Some(left), Some(right)) = (expr.get_node("left"), expr.get_node("right")) {
+            res.insert(left.literal().to_lowercase(), sql_value(right));
+        }
+    }
+    res
+}
+
+fn column_names(node: Option<&Node>) -> Vec<String> {
+    node.and_then(|n| n.get_node_vec("exprs"))
+        .into_iter()
+        .flatten()
+        .map(|e| unquote(e.literal()))
+        .collect()
+}
+
+fn add_constraint(table: &mut Table, constraint: &Node) {
+    match constraint.literal().to_uppercase().as_str() {
+        "PRIMARY" => table.primary_key = column_names(constraint.get_node("columns")),
+        _ => {
+            // FOREIGN KEY (a) REFERENCES t(b) | CONSTRAINT name FOREIGN KEY ...
+            let referenced = constraint
+                .get_node("references")
+                .and_then(|r| r.get_node("expr"));
+            let (referenced_table, referenced_columns) = match referenced {
+                Some(call) if call.node_type == NodeType::CallingFunction => (
+                    call.get_node("func")
+                        .and_then(|f| identifier_name(f).ok())
+                        .unwrap_or_default(),
+                    call.get_node_vec("args")
+                        .into_iter()
+                        .flatten()
+                        .map(|a| unquote(a.literal()))
+                        .collect(),
+                ),
+                Some(ident) => (identifier_name(ident).unwrap_or_default(), vec![]),
+                None => return,
+            };
+            table.foreign_keys.push(ForeignKey {
+                name: constraint.get_node("ident").map(|i| unquote(i.literal())),
+                columns: column_names(constraint.get_node("columns")),
+                referenced_table,
+                referenced_columns,
+            });
+        }
+    }
+}
+
+fn field(decl: &Node) -> TableFieldSchema {
+    let name = unquote(decl.literal());
+    match decl.get_node("type") {
+        Some(type_) => field_from_type(&name, type_),
+        None => field_from_type(&name, decl),
+    }
+}
+
+/// Converts a Type node into a field of BigQuery JSON schema.
+fn field_from_type(name: &str, type_: &Node) -> TableFieldSchema {
+    let mut res = TableFieldSchema {
+        name: name.to_string(),
+        type_: "".to_string(),
+        mode: "NULLABLE".to_string(),
+        fields: Vec::new(),
+        description: None,
+        max_length: None,
+        precision: None,
+        scale: None,
+        collation: None,
+        default_value_expression: None,
+        range_element_type: None,
+    };
+    let inner = type_.get_node("type_declaration");
+    match type_.literal().to_uppercase().as_str() {
+        "ARRAY" => {
+            if let Some(element) = inner.and_then(|i| i.get_node("type")) {
+                let mut element = field_from_type(name, element);
+                element.mode = "REPEATED".to_string();
+                res = element;
+            }
+        }
+        "STRUCT" => {
+            res.type_ = "RECORD".to_string();
+            res.fields = inner
+                .and_then(|i| i.get_node_vec("declarations"))
+                .into_iter()
+                .flatten()
+                .map(field)
+                .collect();
+        }
+        "RANGE" => {
+            res.type_ = "RANGE".to_string();
+            res.range_element_type =
+                inner
+                    .and_then(|i| i.get_node("type"))
+                    .map(|t| RangeElementType {
+                        type_: legacy_type_name(t.literal()),
+                    });
+        }
+        other => res.type_ = legacy_type_name(other),
+    }
+    if let Some(parameters) = type_
+        .get_node("parameter")
+        .and_then(|p| p.get_node_vec("exprs"))
+    {
+        let values: Vec<String> = parameters.iter().map(sql_text).collect();
+        match res.type_.as_str() {
+            "STRING" | "BYTES" => res.max_length = values.first().cloned(),
+            _ => {
+                res.precision = values.first().cloned();
+                res.scale = values.get(1).cloned();
+            }
+        }
+    }
+    if type_.get_node_vec("not_null").is_some() {
+        res.mode = "REQUIRED".to_string();
+    }
+    if let Some(collate) = type_.get_node("collate").and_then(|c| c.get_node("expr")) {
+        res.collation = Some(sql_value(collate));
+    }
+    if let Some(default) = type_.get_node("default").and_then(|d| d.get_node("expr")) {
+        res.default_value_expression = Some(sql_text(default));
+    }
+    res.description = options(type_).remove("description");
+    res
+}
+
+// type names used in BigQuery JSON schema
+fn legacy_type_name(name: &str) -> String {
+    match name.to_uppercase().as_str() {
+        "STRUCT" | "RECORD" => "RECORD".to_string(),
+        other => match ScalarType::from_name(other) {
+            Some(type_) => type_.legacy_name().to_string(),
+            None => other.to_string(),
+        },
+    }
+}
@@ -0,0 +1,176 @@
+use super::*;
+use crate::validate::tests::parse;
+
+fn catalog(code: &str) -> Catalog {
+    build_catalog(&parse(code))
+}
+
+fn column(name: &str, type_: &str, mode: &str) -> TableFieldSchema {
+    TableFieldSchema {
+        name: name.to_string(),
+        type_: type_.to_string(),
+        mode: mode.to_string(),
+        fields: Vec::new(),
+        description: None,
+        max_length: None,
+        precision: None,
+        scale: None,
+        collation: None,
+        default_value_expression: None,
+        range_element_type: None,
+    }
+}
+
+#[test]
+fn test_build_catalog_create() {
+    let c = catalog(
+        "\
+CREATE SCHEMA ds OPTIONS(location = 'US');
+CREATE TABLE ds.t (
+  id INT64 NOT NULL OPTIONS(description = 'identifier'),
+  name STRING(10) COLLATE 'und:ci' DEFAULT 'x',
+  tags ARRAY<STRING>,
+  s STRUCT<a INT64, b ARRAY<STRUCT<c DATE NOT NULL>>>,
+  n NUMERIC(10, 2),
+  r RANGE<DATE>,
+  PRIMARY KEY (id) NOT ENFORCED,
+  CONSTRAINT fk FOREIGN KEY (name) REFERENCES ds.u(name) NOT ENFORCED
+)
+PARTITION BY DATE(ts)
+CLUSTER BY id, name
+OPTIONS (description = 'table', labels = [('a', 'b')]);
+CREATE TEMP TABLE tmp LIKE ds.t;
+",
+    );
+    assert!(c.issues.is_empty());
+    assert_eq!(c.datasets.len(), 2);
+    assert_eq!(c.datasets[0].name, "ds");
+    assert_eq!(c.datasets[0].options["location"], "US");
+    let t = c.find_table("ds.t").unwrap();
+    assert_eq!(t.kind, TableKind::Table);
+    let mut id = column("id", "INTEGER", "REQUIRED");
+    id.description = Some("identifier".to_string());
+    let mut name = column("name", "STRING", "NULLABLE");
+    name.max_length = Some("10".to_string());
+    name.collation = Some("und:ci".to_string());
+    name.default_value_expression = Some("'x'".to_string());
+    let mut s = column("s", "RECORD", "NULLABLE");
+    let mut b = column("b", "RECORD", "REPEATED");
+    b.fields = vec![column("c", "DATE", "REQUIRED")];
+    s.fields = vec![column("a", "INTEGER", "NULLABLE"), b];
+    let mut n = column("n", "NUMERIC", "NULLABLE");
+    n.precision = Some("10".to_string());
+    n.scale = Some("2".to_string());
+    let mut r = column("r", "RANGE", "NULLABLE");
+    r.range_element_type = Some(RangeElementType {
+        type_: "DATE".to_string(),
+    });
+    assert_eq!(
+        t.schema,
+        vec![id, name, column("tags", "STRING", "REPEATED"), s, n, r]
+    );
+    assert_eq!(t.primary_key, vec!["id"]);
+    assert_eq!(
+        t.foreign_keys,
+        vec![ForeignKey {
+            name: Some("fk".to_string()),
+            columns: vec!["name".to_string()],
+            referenced_table: "ds.u".to_string(),
+            referenced_columns: vec!["name".to_string()],
+        }]
+    );
+    assert_eq!(t.partition_by.as_deref(), Some("DATE(ts)"));
+    assert_eq!(t.cluster_by, vec!["id", "name"]);
+    assert_eq!(t.options["description"], "table");
+    assert_eq!(t.options["labels"], "[('a', 'b')]");
+    let tmp = c.find_table("tmp").unwrap();
+    assert!(tmp.temp);
+    assert_eq!(tmp.schema, t.schema);
+}
+
+#[test]
+fn test_build_catalog_alter() {
+    let c = catalog(
+        "\
+CREATE TABLE ds.t (a INT64 NOT NULL, b STRING, c DATE);
+ALTER TABLE ds.t ADD COLUMN d BOOL, ADD COLUMN IF NOT EXISTS a STRING;
+ALTER TABLE ds.t DROP COLUMN b, DROP COLUMN IF EXISTS x;
+ALTER TABLE ds.t RENAME COLUMN c TO cc;
+ALTER TABLE ds.t ALTER COLUMN a SET DATA TYPE NUMERIC;
+ALTER TABLE ds.t ALTER COLUMN a DROP NOT NULL;
+ALTER TABLE ds.t ALTER COLUMN a SET OPTIONS (description = 'desc');
+ALTER TABLE ds.t ALTER COLUMN d SET DEFAULT TRUE;
+ALTER TABLE ds.t SET OPTIONS (description = 'x', labels = [('k', 'v')]);
+ALTER TABLE ds.t SET OPTIONS (description = NULL);
+ALTER TABLE ds.t ADD PRIMARY KEY (a) NOT ENFORCED;
+ALTER TABLE ds.t RENAME TO t2;
+ALTER TABLE ds.t ADD COLUMN e INT64;
+ALTER TABLE ds.t2 DROP COLUMN zz;
+",
+    );
+    assert!(c.find_table("ds.t").is_none());
+    let t = c.find_table("ds.t2").unwrap();
+    let mut a = column("a", "NUMERIC", "NULLABLE");
+    a.description = Some("desc".to_string());
+    let mut d = column("d", "BOOLEAN", "NULLABLE");
+    d.default_value_expression = Some("TRUE".to_string());
+    assert_eq!(t.schema, vec![a, column("cc", "DATE", "NULLABLE"), d]);
+    assert_eq!(t.primary_key, vec!["a"]);
+    assert_eq!(
+        t.options,
+        BTreeMap::from([("labels".to_string(), "[('k', 'v')]".to_string())])
+    );
+    let issues: Vec<(&str, usize, usize, usize)> = c
+        .issues
+        .iter()
+        .map(|i| (i.message.as_str(), i.statement, i.line, i.column))
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            ("Table ds.t is not found.", 12, 13, 1),
+            ("Column zz is not found.", 13, 14, 1),
+        ]
+    );
+}
+
+#[test]
+fn test_build_catalog_drop() {
+    let c = catalog(
+        "\
+CREATE TABLE ds.t (a INT64);
+CREATE VIEW ds.v AS SELECT 1 AS x;
+CREATE MATERIALIZED VIEW ds.mv AS SELECT 1 AS x;
+CREATE EXTERNAL TABLE ds.e (x INT64) OPTIONS (format = 'CSV');
+DROP VIEW ds.v;
+DROP TABLE IF EXISTS ds.none;
+DROP SCHEMA ds CASCADE;
+UNDROP SCHEMA ds;
+CREATE TABLE ds.t (a INT64);
+CREATE TABLE IF NOT EXISTS ds.t (b INT64);
+CREATE OR REPLACE TABLE ds.t COPY ds.e;
+",
+    );
+    let kinds: Vec<(&str, TableKind)> = c.datasets[0]
+        .tables
+        .iter()
+        .map(|t| (t.name.as_str(), t.kind.clone()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("t", TableKind::Table),
+            ("mv", TableKind::MaterializedView),
+            ("e", TableKind::ExternalTable),
+        ]
+    );
+    let t = c.find_table("ds.t").unwrap();
+    assert_eq!(t.schema, vec![column("x", "INTEGER", "NULLABLE")]);
+    assert_eq!(t.options["format"], "CSV");
+    let issues: Vec<(&str, usize)> = c
+        .issues
+        .iter()
+        .map(|i| (i.message.as_str(), i.statement))
+        .collect();
+    assert_eq!(issues, vec![("Table ds.t already exists.", 8)]);
+}
@@ -0,0 +1,380 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{Node, NodeType};
+use crate::validate::{Diagnostic, DiagnosticKind};
+use serde::Serialize;
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub enum FlowNodeKind {
+    Entry,     // start of the script or the procedure
+    Exit,      // end of the script or the procedure (RETURN, uncaught RAISE)
+    Statement, // IF, LOOP, BEGIN and so on are also statements
+    Clause,    // EXCEPTION WHEN ERROR THEN, UNTIL
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct FlowNode {
+    pub kind: FlowNodeKind,
+    pub statement: usize, // index of the top-level statement
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct ControlFlowGraph {
+    pub nodes: Vec<FlowNode>,
+    pub edges: Vec<(usize, usize)>, // indexes of `nodes`
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Builds the control-flow graph of the script (the body of each procedure
+/// has its own entry and exit) and reports invalid BREAK / CONTINUE,
+/// labels, RAISE statements and unreachable statements.
+pub fn analyze_control_flow(stmts: &[Node]) -> ControlFlowGraph {
+    let mut builder = Builder {
+        graph: ControlFlowGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            diagnostics: Vec::new(),
+        },
+        statement: 0,
+        exit: 0,
+        targets: Vec::new(),
+        handlers: Vec::new(),
+        in_handler: 0,
+        sequences: Vec::new(),
+    };
+    let stmts: Vec<&Node> = stmts
+        .iter()
+        .filter(|s| s.node_type != NodeType::EOF)
+        .collect();
+    // the entry and the exit have the position of the first statement
+    let position = stmts.first().map_or((1, 1), |s| s.position());
+    let entry = builder.add(FlowNodeKind::Entry, position);
+    builder.exit = builder.add(FlowNodeKind::Exit, position);
+    let mut preds = vec![entry];
+    let mut sequence = Vec::new();
+    for (idx, stmt) in stmts.iter().enumerate() {
+        builder.statement = idx;
+        let (id, exits) = builder.stmt(stmt, preds);
+        sequence.push((id, *stmt));
+        preds = exits;
+    }
+    builder.sequences.push(sequence);
+    builder.connect(&preds, builder.exit);
+    builder.report_unreachable();
+    let mut graph = builder.graph;
+    graph.diagnostics.sort_by_key(|d| (d.line, d.column));
+    graph
+}
+
+// a loop or a labeled block which can be the target of BREAK / CONTINUE
+struct Target {
+    label: Option<String>,
+    continue_to: Option<usize>, // None if it is not a loop
+    breaks: Vec<usize>,
+}
+
+struct Builder<'a> {
+    graph: ControlFlowGraph,
+    statement: usize,
+    exit: usize,                            // exit of the current script or procedure
+    targets: Vec<Target>,                   // enclosing loops and labeled blocks
+    handlers: Vec<usize>,                   // enclosing exception handlers
+    in_handler: usize,                      // depth of EXCEPTION WHEN ERROR THEN
+    sequences: Vec<Vec<(usize, &'a Node)>>, // statements in the same block
+}
+
+impl<'a> Builder<'a> {
+    fn add(&mut self, kind: FlowNodeKind, (line, column): (usize, usize)) -> usize {
+        self.graph.nodes.push(FlowNode {
+            kind,
+            statement: self.statement,
+            line,
+            column,
+        });
+        self.graph.nodes.len() - 1
+    }
+    fn connect(&mut self, preds: &[usize], to: usize) {
+        for pred in preds {
+            self.graph.edges.push((*pred, to));
+        }
+    }
+    fn report(&mut self, kind: DiagnosticKind, message: String, node: &Node) {
+        let diagnostic = Diagnostic::new(kind, message, self.statement, node);
+        self.graph.diagnostics.push(diagnostic);
+    }
+    /// Returns the nodes from which the control reaches the next statement.
+    fn block(&mut self, node: Option<&'a Node>, preds: Vec<usize>) -> Vec<usize> {
+        let stmts = node.and_then(|n| n.get_node_vec("stmts"));
+        let mut preds = preds;
+        let mut sequence = Vec::new();
+        for stmt in stmts.into_iter().flatten() {
+            let (id, exits) = self.stmt(stmt, preds);
+            sequence.push((id, stmt));
+            preds = exits;
+        }
+        self.sequences.push(sequence);
+        preds
+    }
+    /// Returns the node of the statement and the nodes from which the control
+    /// reaches the next statement.
+    fn stmt(&mut self, node: &'a Node, preds: Vec<usize>) -> (usize, Vec<usize>) {
+        let id = self.add(FlowNodeKind::Statement, node.position());
+        self.connect(&preds, id);
+        let label = node
+            .get_node("leading_label")
+            .map(|l| l.literal().to_string());
+        if let (Some(label), Some(trailing)) = (&label, node.get_node("trailing_label")) {
+            if !label.eq_ignore_ascii_case(trailing.literal()) {
+                self.report(
+                    DiagnosticKind::InvalidLabel,
+                    format!(
+                        "Label {} does not match the leading label {}.",
+                        trailing.literal(),
+                        label
+                    ),
+                    trailing,
+                );
+            }
+        }
+        if let (Some(label), Some(leading)) = (&label, node.get_node("leading_label")) {
+            let defined = self.targets.iter().any(|t| {
+                t.label
+                    .as_ref()
+                    .is_some_and(|l| l.eq_ignore_ascii_case(label))
+            });
+            if defined {
+                self.report(
+                    DiagnosticKind::InvalidLabel,
+                    format!("Label {} is already defined.", label),
+                    leading,
+                );
+            }
+        }
+        let exits = match node.node_type {
+            NodeType::IfStatement => {
+                let mut exits = self.block(node.get_node("then"), vec![id]);
+                for elseif in node.get_node_vec("elseifs").into_iter().flatten() {
+                    exits.append(&mut self.block(elseif.get_node("then"), vec![id]));
+                }
+                match node.get_node("else") {
+                    Some(else_) => exits.append(&mut self.block(Some(else_), vec![id])),
+                    None => exits.push(id),
+                }
+                exits
+            }
+            NodeType::CaseStatement => {
+                let mut exits = Vec::new();
+                let arms = node.get_node_vec("arms").map_or(&[][..], |a| a);
+                for arm in arms {
+                    exits.append(&mut self.block(Some(arm), vec![id]));
+                }
+                // CASE without ELSE
+                if arms.iter().all(|a| a.get_node("expr").is_some()) {
+                    exits.push(id);
+                }
+                exits
+            }
+            NodeType::LoopStatement => self.loop_(label, id, id, Some(node)),
+            NodeType::WhileStatement | NodeType::ForStatement => {
+                let mut exits = self.loop_(label, id, id, node.get_node("do"));
+                exits.push(id);
+                exits
+            }
+            NodeType::RepeatStatement => {
+                let until = node
+                    .get_node("until")
+                    .map_or(node.position(), |u| u.position());
+                let until = self.add(FlowNodeKind::Clause, until);
+                let mut exits = self.loop_(label, id, until, Some(node));
+                exits.push(until);
+                exits
+            }
+            NodeType::BeginStatement => self.begin(node, label, id),
+            // invalid BREAK / CONTINUE does not change the flow
+            NodeType::BreakContinueStatement if self.break_continue(node, id) => vec![],
+            NodeType::SingleTokenStatement if node.literal().eq_ignore_ascii_case("RETURN") => {
+                self.connect(&[id], self.exit);
+                vec![]
+            }
+            NodeType::RaiseStatement => {
+                if node.get_node("using").is_none() && self.in_handler == 0 {
+                    self.report(
+                        DiagnosticKind::InvalidControlFlow,
+                        "RAISE without a message is only allowed inside an exception handler."
+                            .to_string(),
+                        node,
+                    );
+                }
+                let to = *self.handlers.last().unwrap_or(&self.exit);
+                self.connect(&[id], to);
+                vec![]
+            }
+            NodeType::CreateProcedureStatement => {
+                if let Some(body) = node.get_node("stmt") {
+                    self.procedure(node, body);
+                }
+                vec![id]
+            }
+            _ => vec![id],
+        };
+        (id, exits)
+    }
+    /// `body` is the node which has the statements of the loop.
+    /// `continue_to` is the node to which CONTINUE jumps.
+    fn loop_(
+        &mut self,
+        label: Option<String>,
+        id: usize,
+        continue_to: usize,
+        body: Option<&'a Node>,
+    ) -> Vec<usize> {
+        self.targets.push(Target {
+            label,
+            continue_to: Some(continue_to),
+            breaks: Vec::new(),
+        });
+        let exits = self.block(body, vec![id]);
+        self.connect(&exits, continue_to);
+        if continue_to != id {
+            // UNTIL -> REPEAT
+            self.connect(&[continue_to], id);
+        }
+        self.targets.pop().map_or(vec![], |t| t.breaks)
+    }
+    fn begin(&mut self, node: &'a Node, label: Option<String>, id: usize) -> Vec<usize> {
+        let labeled = label.is_some();
+        if labeled {
+            self.targets.push(Target {
+                label,
+                continue_to: None,
+                breaks: Vec::new(),
+            });
+        }
+        // any statement in the block may raise an error
+        let handler = node
+            .get_node_vec("exception_when_error")
+            .and_then(|e| e.first())
+            .map(|e| {
+                let handler = self.add(FlowNodeKind::Clause, e.position());
+                self.connect(&[id], handler);
+                handler
+            });
+        if let Some(handler) = handler {
+            self.handlers.push(handler);
+        }
+        let mut exits = self.block(Some(node), vec![id]);
+        if let Some(handler) = handler {
+            self.handlers.pop();
+            self.in_handler += 1;
+            exits.append(&mut self.block(node.get_node("then"), vec![handler]));
+            self.in_handler -= 1;
+        }
+        if labeled {
+            exits.append(&mut self.targets.pop().map_or(vec![], |t| t.breaks));
+        }
+        exits
+    }
+    /// Returns false if the target of BREAK / CONTINUE is not found.
+    fn break_continue(&mut self, node: &Node, id: usize) -> bool {
+        let keyword = node.literal().to_uppercase();
+        let is_break = keyword == "BREAK" || keyword == "LEAVE";
+        let target = match node.get_node("label") {
+            Some(label) => {
+                let target = self.targets.iter().rposition(|t| {
+                    t.label
+                        .as_ref()
+                        .is_some_and(|l| l.eq_ignore_ascii_case(label.literal()))
+                });
+                match target {
+                    Some(i) if !is_break && self.targets[i].continue_to.is_none() => {
+                        self.report(
+                            DiagnosticKind::InvalidLabel,
+                            format!(
+                                "{} cannot refer to the label of a block: {}.",
+                                keyword,
+                                label.literal()
+                            ),
+                            label,
+                        );
+                        return false;
+                    }
+                    Some(i) => i,
+                    None => {
+                        self.report(
+                            DiagnosticKind::InvalidLabel,
+                            format!("Label {} is not defined.", label.literal()),
+                            label,
+                        );
+                        return false;
+                    }
+                }
+            }
+            None => match self.targets.iter().rposition(|t| t.continue_to.is_some()) {
+                Some(i) => i,
+                None => {
+                    self.report(
+                        DiagnosticKind::InvalidControlFlow,
+                        format!("{} is only allowed inside a loop.", keyword),
+                        node,
+                    );
+                    return false;
+                }
+            },
+        };
+        match self.targets[target].continue_to {
+            Some(continue_to) if !is_break => self.connect(&[id], continue_to),
+            _ => self.targets[target].breaks.push(id),
+        }
+        true
+    }
+    // the body of the procedure is not executed when it is created
+    fn procedure(&mut self, node: &Node, body: &'a Node) {
+        let exit = self.exit;
+        let targets = std::mem::take(&mut self.targets);
+        let handlers = std::mem::take(&mut self.handlers);
+        let in_handler = self.in_handler;
+        self.in_handler = 0;
+        let entry = self.add(FlowNodeKind::Entry, node.position());
+        self.exit = self.add(FlowNodeKind::Exit, node.position());
+        let (_, exits) = self.stmt(body, vec![entry]);
+        self.connect(&exits, self.exit);
+        self.exit = exit;
+        self.targets = targets;
+        self.handlers = handlers;
+        self.in_handler = in_handler;
+    }
+    // reports the first unreachable statement after reachable ones
+    fn report_unreachable(&mut self) {
+        let mut successors = vec![Vec::new(); self.graph.nodes.len()];
+        for (from, to) in &self.graph.edges {
+            successors[*from].push(*to);
+        }
+        let mut reachable = vec![false; self.graph.nodes.len()];
+        let mut stack: Vec<usize> = (0..self.graph.nodes.len())
+            .filter(|i| self.graph.nodes[*i].kind == FlowNodeKind::Entry)
+            .collect();
+        while let Some(node) = stack.pop() {
+            if reachable[node] {
+                continue;
+            }
+            reachable[node] = true;
+            stack.extend(successors[node].iter().filter(|n| !reachable[**n]));
+        }
+        for sequence in &self.sequences {
+            for pair in sequence.windows(2) {
+                let ((prev, _), (id, node)) = (pair[0], pair[1]);
+                if reachable[prev] && !reachable[id] {
+                    self.graph.diagnostics.push(Diagnostic::new(
+                        DiagnosticKind::UnreachableStatement,
+                        "Unreachable statement.".to_string(),
+                        self.graph.nodes[id].statement,
+                        node,
+                    ));
+                }
+            }
+        }
+    }
+}
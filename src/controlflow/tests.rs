@@ -0,0 +1,162 @@
+use super::*;
+use crate::validate::tests::{diagnostic_tuples, parse};
+
+fn analyze(code: &str) -> ControlFlowGraph {
+    analyze_control_flow(&parse(code))
+}
+
+fn diagnostics(code: &str) -> Vec<(DiagnosticKind, String, usize, usize)> {
+    diagnostic_tuples(analyze(code).diagnostics)
+}
+
+#[test]
+fn test_control_flow_graph() {
+    let graph = analyze(
+        "\
+IF x THEN
+  RETURN;
+END IF;
+WHILE y DO
+  CONTINUE;
+END WHILE;
+",
+    );
+    let nodes: Vec<(FlowNodeKind, usize, usize)> = graph
+        .nodes
+        .into_iter()
+        .map(|n| (n.kind, n.line, n.column))
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![
+            (FlowNodeKind::Entry, 1, 1),
+            (FlowNodeKind::Exit, 1, 1),
+            (FlowNodeKind::Statement, 1, 1), // IF
+            (FlowNodeKind::Statement, 2, 3), // RETURN
+            (FlowNodeKind::Statement, 4, 1), // WHILE
+            (FlowNodeKind::Statement, 5, 3), // CONTINUE
+        ]
+    );
+    assert_eq!(
+        graph.edges,
+        vec![(0, 2), (2, 3), (3, 1), (2, 4), (4, 5), (5, 4), (4, 1)]
+    );
+    assert_eq!(graph.diagnostics, vec![]);
+}
+
+#[test]
+fn test_valid_control_flow() {
+    let res = diagnostics(
+        "\
+outer: LOOP
+  inner: WHILE TRUE DO
+    IF x THEN BREAK outer; ELSEIF y THEN CONTINUE inner; ELSE LEAVE; END IF;
+  END WHILE inner;
+  REPEAT ITERATE; UNTIL z END REPEAT;
+  FOR r IN (SELECT 1) DO ITERATE outer; END FOR;
+END LOOP outer;
+blk: BEGIN
+  LEAVE blk;
+EXCEPTION WHEN ERROR THEN
+  RAISE;
+END blk;
+BEGIN
+  RAISE USING MESSAGE = 'error';
+  SELECT 'unreachable when raised, but the handler is reachable';
+EXCEPTION WHEN ERROR THEN
+  SELECT @@error.message;
+END;
+CREATE PROCEDURE ds.p() BEGIN RETURN; END;
+SELECT 1;
+",
+    );
+    assert_eq!(
+        res,
+        vec![(
+            DiagnosticKind::UnreachableStatement,
+            "Unreachable statement.".to_string(),
+            15,
+            3
+        )]
+    );
+}
+
+#[test]
+fn test_invalid_control_flow() {
+    let res = diagnostics(
+        "\
+BREAK;
+LOOP
+  CONTINUE not_exist;
+  BREAK;
+END LOOP;
+blk: BEGIN
+  LOOP ITERATE blk; BREAK; END LOOP;
+END blk;
+a: LOOP
+  a: LOOP BREAK; END LOOP;
+  BREAK;
+END LOOP b;
+IF x THEN
+  RETURN;
+  SELECT 1;
+  SELECT 2;
+END IF;
+RAISE;
+SELECT 3;
+",
+    );
+    assert_eq!(
+        res,
+        vec![
+            (
+                DiagnosticKind::InvalidControlFlow,
+                "BREAK is only allowed inside a loop.".to_string(),
+                1,
+                1
+            ),
+            (
+                DiagnosticKind::InvalidLabel,
+                "Label not_exist is not defined.".to_string(),
+                3,
+                12
+            ),
+            (
+                DiagnosticKind::InvalidLabel,
+                "ITERATE cannot refer to the label of a block: blk.".to_string(),
+                7,
+                16
+            ),
+            (
+                DiagnosticKind::InvalidLabel,
+                "Label a is already defined.".to_string(),
+                10,
+                3
+            ),
+            (
+                DiagnosticKind::InvalidLabel,
+                "Label b does not match the leading label a.".to_string(),
+                12,
+                10
+            ),
+            (
+                DiagnosticKind::UnreachableStatement,
+                "Unreachable statement.".to_string(),
+                15,
+                3
+            ),
+            (
+                DiagnosticKind::InvalidControlFlow,
+                "RAISE without a message is only allowed inside an exception handler.".to_string(),
+                18,
+                1
+            ),
+            (
+                DiagnosticKind::UnreachableStatement,
+                "Unreachable statement.".to_string(),
+                19,
+                1
+            ),
+        ]
+    );
+}
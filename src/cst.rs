@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests;
 
+use crate::error::BQ2CSTError;
 use crate::token::Token;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -10,14 +11,16 @@ use std::fmt;
 pub enum ContentType {
     Node(Node),
     NodeVec(Vec<Node>),
+    Error(BQ2CSTError), // the error of the SQL of EXECUTE IMMEDIATE
 }
 
 #[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub enum NodeType {
-    AccessOperator,        // arr[OFFSET(1)] | json['path']
-    AddColumnClause,       // ADD COLUMN x INT64 OPTIONS()
-    AddConstraintClause,   // ADD PRIMARY KEY (a) | ADD REFERENCES `table`(col) NOT ENFORCED
-    AggregatePipeOperator, // AGGREGATE COUNT(*) GROUP BY col
+    AccessOperator,             // arr[OFFSET(1)] | json['path']
+    AddColumnClause,            // ADD COLUMN x INT64 OPTIONS()
+    AddConstraintClause,        // ADD PRIMARY KEY (a) | ADD REFERENCES `table`(col) NOT ENFORCED
+    AggregatePipeOperator,      // AGGREGATE COUNT(*) GROUP BY col
+    AggregationThresholdClause, // WITH AGGREGATION_THRESHOLD OPTIONS ()
     AlterColumnStatement,
     AlterBICapacityStatement,
     AlterModelStatement,
@@ -29,7 +32,8 @@ pub enum NodeType {
     AlterTableStatement,
     AlterVectorIndexStatement,
     AlterViewStatement,
-    ArrayLiteral, // [1, 2]
+    ArrayLiteral,       // [1, 2]
+    AssertPipeOperator, // ASSERT x > 0, 'payload'
     AssertStatement,
     Asterisk,
     BasePipeOperator, // SELECT a, b | LIMIT 10
@@ -52,14 +56,17 @@ pub enum NodeType {
     CreateIndexStatement,
     CreateModelStatement,
     CreateProcedureStatement,
-    CreateReservationStatement, // CREATE CAPACITY `ident` AS JSON '{}' | ...
+    CreatePropertyGraphStatement, // CREATE PROPERTY GRAPH g NODE TABLES (t) EDGE TABLES (...)
+    CreateReservationStatement,   // CREATE CAPACITY `ident` AS JSON '{}' | ...
     CreateRowAccessPolicyStatement,
     CreateSchemaStatement,
     CreateTableStatement,
     CreateViewStatement,
     Comment,
     DeclareStatement,
+    DefineMacroStatement, // DEFINE MACRO name body;
     DeleteStatement,
+    DescribePipeOperator,      // DESCRIBE | STATIC_DESCRIBE
     DifferentialPrivacyClause, // WITH DIFFERENTIAL_PRIVACY OPTIONS ()
     DotOperator,
     DropStatement,
@@ -73,9 +80,18 @@ pub enum NodeType {
     ExtractArgument,         // DAY FROM expr
     ForSystemTimeAsOfClause, // FOR SYSTEM_TIME AS OF ts
     ForStatement,
-    FromStatement, // FROM table_name;
+    ForkPipeOperator, // FORK (|> WHERE x), (|> WHERE y) | TEE (...)
+    FromStatement,    // FROM table_name;
     FunctionChain,
     GrantStatement,
+    GraphEdgePattern,   // -[e:Label]-> | <- | ...
+    GraphLabel,         // LABEL x PROPERTIES (a, b)
+    GraphMatchOperator, // MATCH (a)-[e]->(b) WHERE cond
+    GraphNodePattern,   // (a:Label {id: 1})
+    GraphPropertySpec,  // {id: 1}
+    GraphStatement,     // GRAPH g MATCH (a) RETURN a
+    GraphTableConfig,   // (g MATCH (a) COLUMNS (a.id))
+    GraphTableExpr,     // GRAPH_TABLE (g MATCH (a) COLUMNS (a.id))
     GroupByExprs,
     GroupedIdentWithOptions,             // (col OPTIONS())
     GroupedExpr,                         // (1)
@@ -95,6 +111,7 @@ pub enum NodeType {
     Identifier,
     IdentWithOptions,
     IfStatement,
+    IfPipeOperator, // IF cond THEN (|> WHERE x) ELSE (|> WHERE y)
     InsertStatement,
     IntervalLiteral,
     InOperator,
@@ -104,7 +121,9 @@ pub enum NodeType {
     LimitClause,
     LimitPipeOperator,
     LoadStatement,
+    LogPipeOperator, // LOG (|> SELECT x)
     LoopStatement,
+    MacroInvocation,      // $name(arg1, arg2) | $1
     MatchRecognizeClause, // MATCH_RECOGNIZE ()
     MatchRecognizeConfig, // (PARTITION BY x ORDER BY y MEASURES ...)
     MatchRecognizePipeOperator,
@@ -124,7 +143,8 @@ pub enum NodeType {
     PivotOperator,
     PivotPipeOperator,
     RaiseStatement,
-    RangeLiteral, // RANGE<DATE> '[2023-01-01, 2024-01-01)'
+    RangeLiteral,               // RANGE<DATE> '[2023-01-01, 2024-01-01)'
+    RecursiveUnionPipeOperator, // RECURSIVE UNION ALL (|> WHERE x) AS t
     RenameColumnClause,
     RepeatStatement,
     RevokeStatement,
@@ -133,8 +153,10 @@ pub enum NodeType {
     SetOperator,          // UNION | INTERSECT | EXCEPT
     SetStatement,         // SET x = 5
     SingleTokenStatement, // BREAK; | LEAVE; | ...
+    SqlxBlock,            // config { ... } (Dataform)
     StringLiteral,
     StructLiteral,
+    Subpipeline,       // (|> WHERE x |> SELECT y)
     Symbol,            // ) | ] | * | ...
     TableSampleClause, // TABLESAMPLE SYSTEM (10 PERCENT)
     TableSamplePipeOperator,
@@ -158,6 +180,7 @@ pub enum NodeType {
     WindowClause,        // WINDOW x AS (PARTITION BY c1)
     WindowExpr,          // x AS (PARTITION BY c1 ORDER BY c2)
     WindowFrameClause,   // ROWS BETWEEN 2 PRECEDING AND 2 FOLLOWING
+    WindowPipeOperator,  // WINDOW SUM(x) OVER ()
     WindowSpecification, // PARTITION BY c1 ORDER BY c2 ROWS UNBOUNDED PRECEDING
     WithClause,          // WITH x AS (SELECT 1)
     WithOffsetClause,
@@ -223,6 +246,16 @@ impl Node {
                         res.push(n.format(indent + 1, true));
                     }
                 }
+                Some(ContentType::Error(e)) => {
+                    res.push(format!("{}{}:", " ".repeat(indent * 2), k));
+                    res.push(format!(
+                        "{}{} ({}:{})",
+                        " ".repeat((indent + 1) * 2),
+                        e.message(),
+                        e.line,
+                        e.column
+                    ));
+                }
                 None => panic!(),
             }
         }
@@ -236,6 +269,129 @@ impl Node {
         self.children
             .insert(key.to_string(), ContentType::NodeVec(nodes));
     }
+    pub fn push_error(&mut self, key: &str, error: BQ2CSTError) {
+        self.children
+            .insert(key.to_string(), ContentType::Error(error));
+    }
+    pub fn get_node(&self, key: &str) -> Option<&Node> {
+        match self.children.get(key) {
+            Some(ContentType::Node(n)) => Some(n),
+            _ => None,
+        }
+    }
+    pub fn get_node_vec(&self, key: &str) -> Option<&Vec<Node>> {
+        match self.children.get(key) {
+            Some(ContentType::NodeVec(ns)) => Some(ns),
+            _ => None,
+        }
+    }
+    pub fn literal(&self) -> &str {
+        match &self.token {
+            Some(t) => t.literal.as_str(),
+            None => "",
+        }
+    }
+    /// Returns the direct children (comments are excluded) in source order.
+    pub fn child_nodes(&self) -> Vec<(&str, &Node)> {
+        let mut res = Vec::new();
+        for (k, v) in &self.children {
+            if k == "leading_comments" || k == "trailing_comments" {
+                continue;
+            }
+            match v {
+                ContentType::Node(n) => res.push((k.as_str(), n)),
+                ContentType::NodeVec(ns) => {
+                    for n in ns {
+                        res.push((k.as_str(), n));
+                    }
+                }
+                ContentType::Error(_) => (),
+            }
+        }
+        res.sort_by_key(|(_, n)| n.position());
+        res
+    }
+    /// Returns the comments attached to this node and its descendants in source order.
+    pub fn comments(&self) -> Vec<&Node> {
+        let mut res = Vec::new();
+        for key in ["leading_comments", "trailing_comments"] {
+            if let Some(ns) = self.get_node_vec(key) {
+                res.extend(ns.iter());
+            }
+        }
+        for (_, n) in self.child_nodes() {
+            res.extend(n.comments());
+        }
+        res.sort_by_key(|n| n.position());
+        res
+    }
+    /// Returns the position of the first token in this subtree.
+    pub fn position(&self) -> (usize, usize) {
+        let mut res = match &self.token {
+            Some(t) => (t.line, t.column),
+            None => (usize::MAX, usize::MAX),
+        };
+        for v in self.children.values() {
+            let pos = match v {
+                ContentType::Node(n) => n.position(),
+                ContentType::NodeVec(ns) => match ns.first() {
+                    Some(n) => n.position(),
+                    None => continue,
+                },
+                ContentType::Error(_) => continue,
+            };
+            if pos < res {
+                res = pos;
+            }
+        }
+        res
+    }
+    /// Returns the position just after the last token in this subtree.
+    pub fn end_position(&self) -> (usize, usize) {
+        let mut res = match &self.token {
+            // the literal may contain newlines (e.g. comments, multiline strings)
+            Some(t) => match t.literal.rfind('\n') {
+                Some(i) => (
+                    t.line + t.literal.matches('\n').count(),
+                    t.literal[i + 1..].chars().count() + 1,
+                ),
+                None => (t.line, t.column + t.literal.chars().count()),
+            },
+            None => (0, 0),
+        };
+        for (_, n) in self.child_nodes() {
+            res = res.max(n.end_position());
+        }
+        res
+    }
+    /// Returns the parts of a (possibly qualified) identifier, e.g.
+    /// `` `project.dataset`.table `` -> `["project", "dataset", "table"]`.
+    pub fn identifier_parts(&self) -> Option<Vec<String>> {
+        match self.node_type {
+            NodeType::DotOperator => {
+                let mut left = self.get_node("left")?.identifier_parts()?;
+                left.append(&mut self.get_node("right")?.identifier_parts()?);
+                Some(left)
+            }
+            NodeType::Identifier | NodeType::MultiTokenIdentifier | NodeType::IdentWithOptions => {
+                let mut literal = self.literal().to_string();
+                if let Some(idents) = self.get_node_vec("trailing_idents") {
+                    for i in idents {
+                        literal.push_str(i.literal());
+                    }
+                }
+                Some(
+                    literal
+                        .trim_matches('`')
+                        .split('.')
+                        .filter(|s| !s.is_empty())
+                        .map(|s| s.to_string())
+                        .collect(),
+                )
+            }
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Node {
@@ -0,0 +1,446 @@
+#[cfg(test)]
+mod tests;
+
+use crate::builtins::{find_builtin, NAMESPACES};
+use crate::cst::{Node, NodeType};
+use crate::error::BQ2CSTError;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+// table functions which are not user-defined (e.g. FROM EXTERNAL_QUERY(...))
+const TABLE_FUNCTIONS: [&str; 8] = [
+    "APPENDS",
+    "CHANGES",
+    "EXTERNAL_OBJECT_TRANSFORM",
+    "EXTERNAL_QUERY",
+    "GAP_FILL",
+    "RANGE_SESSIONIZE",
+    "UNNEST",
+    "VECTOR_SEARCH",
+];
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize)]
+pub enum ObjectKind {
+    Schema,
+    Table, // also used for the references to views
+    View,
+    Function,
+    Procedure,
+    Model,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct DatabaseObject {
+    pub kind: ObjectKind,
+    pub name: String, // e.g. `project.dataset.table`, `dataset.table`
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct StatementDependencies {
+    pub file: usize,      // index of the file
+    pub statement: usize, // index of the statement in the file
+    pub line: usize,
+    pub column: usize,
+    pub creates: Vec<DatabaseObject>, // temporary objects are not included
+    pub references: Vec<DatabaseObject>,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct DependencyGraph {
+    pub files: Vec<String>,
+    pub statements: Vec<StatementDependencies>, // statements which create or refer to objects
+    pub edges: Vec<(usize, usize)>, // (file, file which refers to the objects created by it)
+    pub order: Vec<usize>,          // deployment order (files in or after cycles are excluded)
+    pub cycles: Vec<Vec<usize>>,    // files which depend on each other
+}
+
+/// The error of a file passed to `build_dependency_graph`.
+#[derive(Debug, Serialize)]
+pub struct FileError {
+    pub file: String,
+    #[serde(flatten)]
+    pub error: BQ2CSTError,
+}
+
+/// Records the objects created and referred to by each statement and returns
+/// the dependencies between the files. A file depends on another file if it
+/// refers to an object which is created only by the other file.
+pub fn build_dependency_graph(files: &[(String, Vec<Node>)]) -> DependencyGraph {
+    let mut statements = Vec::new();
+    for (file, (_, stmts)) in files.iter().enumerate() {
+        let mut collector = Collector::default();
+        for (idx, stmt) in stmts.iter().enumerate() {
+            collector.creates.clear();
+            collector.references.clear();
+            collector.ctes.clear();
+            collector.ranges.clear();
+            collector.walk(stmt);
+            if collector.creates.is_empty() && collector.references.is_empty() {
+                continue;
+            }
+            let (line, column) = stmt.position();
+            statements.push(StatementDependencies {
+                file,
+                statement: idx,
+                line,
+                column,
+                creates: collector.creates.clone(),
+                references: collector.references.clone(),
+            });
+        }
+    }
+    let mut edges = BTreeSet::new();
+    for dependent in &statements {
+        for reference in &dependent.references {
+            let creators: Vec<usize> = statements
+                .iter()
+                .filter(|s| s.creates.iter().any(|c| same_object(reference, c)))
+                .map(|s| s.file)
+                .collect();
+            // the object created in the same file is used
+            if creators.contains(&dependent.file) {
+                continue;
+            }
+            for file in creators {
+                edges.insert((file, dependent.file));
+            }
+        }
+    }
+    let edges: Vec<(usize, usize)> = edges.into_iter().collect();
+    DependencyGraph {
+        files: files.iter().map(|(name, _)| name.clone()).collect(),
+        order: topological_order(files.len(), &edges),
+        cycles: cycles(files.len(), &edges),
+        statements,
+        edges,
+    }
+}
+
+#[derive(Default)]
+struct Collector {
+    temps: Vec<String>, // temporary tables and functions created in the file
+    ctes: Vec<String>,
+    ranges: Vec<String>, // aliases and names of the tables in FROM clause
+    creates: Vec<DatabaseObject>,
+    references: Vec<DatabaseObject>,
+}
+
+impl Collector {
+    fn create(&mut self, node: &Node, kind: ObjectKind) {
+        let Some(parts) = node.get_node("ident").and_then(|i| i.identifier_parts()) else {
+            return;
+        };
+        if node.get_node("temp").is_some() {
+            self.temps.push(parts.join("."));
+            return;
+        }
+        // `dataset.table` requires the dataset
+        if parts.len() >= 2 && kind != ObjectKind::Schema {
+            self.refer(ObjectKind::Schema, parts[..parts.len() - 1].join("."));
+        }
+        self.creates.push(DatabaseObject {
+            kind,
+            name: parts.join("."),
+        });
+    }
+    fn refer(&mut self, kind: ObjectKind, name: String) {
+        if self.temps.iter().any(|t| t.eq_ignore_ascii_case(&name)) {
+            return;
+        }
+        let object = DatabaseObject { kind, name };
+        if !self.references.contains(&object) {
+            self.references.push(object);
+        }
+    }
+    fn refer_node(&mut self, node: Option<&Node>, kind: ObjectKind) {
+        if let Some(parts) = node.and_then(|n| n.identifier_parts()) {
+            self.refer(kind, parts.join("."));
+        }
+    }
+    fn walk(&mut self, node: &Node) {
+        // the names of CTEs are visible in the query (and in themselves if recursive)
+        let queries = node
+            .get_node("with")
+            .and_then(|w| w.get_node_vec("queries"));
+        for query in queries.into_iter().flatten() {
+            self.ctes
+                .push(query.literal().trim_matches('`').to_string());
+        }
+        match node.node_type {
+            NodeType::CreateSchemaStatement => self.create(node, ObjectKind::Schema),
+            NodeType::CreateTableStatement => {
+                self.create(node, ObjectKind::Table);
+                // CREATE TABLE ... LIKE / COPY / CLONE
+                self.table(node.get_node("source_table"));
+                self.table(node.get_node("clone").and_then(|c| c.get_node("expr")));
+            }
+            NodeType::CreateViewStatement => self.create(node, ObjectKind::View),
+            NodeType::CreateFunctionStatement => self.create(node, ObjectKind::Function),
+            NodeType::CreateProcedureStatement => self.create(node, ObjectKind::Procedure),
+            NodeType::CreateModelStatement => self.create(node, ObjectKind::Model),
+            NodeType::CreateIndexStatement => self.table(node.get_node("tablename")),
+            NodeType::CreateRowAccessPolicyStatement => {
+                self.table(node.get_node("on").and_then(|o| o.get_node("expr")))
+            }
+            NodeType::AlterTableStatement | NodeType::AlterViewStatement => {
+                self.table(node.get_node("ident"))
+            }
+            NodeType::AlterModelStatement => {
+                self.refer_node(node.get_node("ident"), ObjectKind::Model)
+            }
+            NodeType::AlterSchemaStatement => {
+                self.refer_node(node.get_node("ident"), ObjectKind::Schema)
+            }
+            NodeType::InsertStatement => self.table(node.get_node("target_name")),
+            NodeType::UpdateStatement
+            | NodeType::DeleteStatement
+            | NodeType::MergeStatement
+            | NodeType::TruncateStatement => self.table(node.get_node("table_name")),
+            NodeType::LoadStatement => self.table(node.get_node("ident")),
+            // the dropped objects are not required
+            NodeType::DropStatement | NodeType::DropRowAccessPolicyStatement => return,
+            NodeType::CallStatement => {
+                if let Some(procedure) = node.get_node("procedure") {
+                    self.refer_node(procedure.get_node("func"), ObjectKind::Procedure);
+                    self.walk_args(procedure);
+                }
+                return;
+            }
+            NodeType::CallingFunction => {
+                self.function(node);
+                self.walk_args(node);
+                return;
+            }
+            // ML.PREDICT(MODEL ds.m, TABLE ds.t)
+            NodeType::UnaryOperator if node.literal().eq_ignore_ascii_case("MODEL") => {
+                return self.refer_node(node.get_node("right"), ObjectKind::Model);
+            }
+            NodeType::UnaryOperator if node.literal().eq_ignore_ascii_case("TABLE") => {
+                return self.table(node.get_node("right"));
+            }
+            NodeType::FromStatement => return self.table_expr(node.get_node("expr")),
+            NodeType::JoinPipeOperator => {
+                for item in node.get_node_vec("exprs").into_iter().flatten() {
+                    self.table_expr(Some(item));
+                }
+                self.walk_child(node.get_node("on"));
+                return;
+            }
+            _ => (),
+        }
+        for (key, child) in node.child_nodes() {
+            match key {
+                // USING(col), REFERENCES t(col)
+                "using" if node.node_type == NodeType::JoinOperator => continue,
+                // MERGE t USING s
+                "using" if node.node_type == NodeType::MergeStatement => {
+                    self.table_expr(child.get_node("expr"))
+                }
+                "references" => continue,
+                "from" => match child.get_node("expr") {
+                    Some(expr) => self.table_expr(Some(expr)),
+                    None => self.walk(child),
+                },
+                _ => self.walk(child),
+            }
+        }
+    }
+    fn walk_child(&mut self, node: Option<&Node>) {
+        if let Some(node) = node {
+            self.walk(node);
+        }
+    }
+    fn walk_args(&mut self, node: &Node) {
+        for arg in node.get_node_vec("args").into_iter().flatten() {
+            self.walk(arg);
+        }
+    }
+    fn table_expr(&mut self, node: Option<&Node>) {
+        let Some(node) = node else {
+            return;
+        };
+        if let Some(alias) = node.get_node("alias") {
+            self.ranges
+                .push(alias.literal().trim_matches('`').to_string());
+        }
+        match node.node_type {
+            NodeType::JoinOperator => {
+                self.table_expr(node.get_node("left"));
+                self.table_expr(node.get_node("right"));
+                self.walk_child(node.get_node("on"));
+            }
+            NodeType::GroupedExpr => self.table_expr(node.get_node("expr")),
+            NodeType::Identifier | NodeType::DotOperator | NodeType::MultiTokenIdentifier => {
+                self.table(Some(node));
+                if let Some(parts) = node.identifier_parts() {
+                    self.ranges.extend(parts.last().cloned());
+                }
+            }
+            NodeType::CallingTableFunction => {
+                self.function(node);
+                self.walk_args(node);
+            }
+            _ => self.walk(node),
+        }
+        for key in ["pivot", "unpivot"] {
+            self.walk_child(node.get_node(key));
+        }
+    }
+    fn table(&mut self, node: Option<&Node>) {
+        let Some(parts) = node.and_then(|n| n.identifier_parts()) else {
+            return;
+        };
+        let is_cte =
+            parts.len() == 1 && self.ctes.iter().any(|c| c.eq_ignore_ascii_case(&parts[0]));
+        // correlated array path, e.g. FROM t, t.arr
+        let is_path = parts.len() >= 2
+            && self
+                .ranges
+                .iter()
+                .any(|r| r.eq_ignore_ascii_case(&parts[0]));
+        let is_system = parts.iter().any(|p| {
+            p.to_uppercase().starts_with("INFORMATION_SCHEMA")
+                || p.to_lowercase().starts_with("region-")
+        });
+        if !is_cte && !is_path && !is_system {
+            self.refer(ObjectKind::Table, parts.join("."));
+        }
+    }
+    fn function(&mut self, node: &Node) {
+        let Some(parts) = node.get_node("func").and_then(|f| f.identifier_parts()) else {
+            return;
+        };
+        let parts = match parts.split_first() {
+            Some((first, rest)) if first.eq_ignore_ascii_case("SAFE") && !rest.is_empty() => rest,
+            _ => &parts[..],
+        };
+        let name = parts.join(".");
+        let builtin = find_builtin(&name).is_some()
+            || TABLE_FUNCTIONS
+                .iter()
+                .any(|f| f.eq_ignore_ascii_case(&name))
+            || (parts.len() >= 2 && NAMESPACES.iter().any(|n| n.eq_ignore_ascii_case(&parts[0])));
+        if !builtin {
+            self.refer(ObjectKind::Function, name);
+        }
+    }
+}
+
+// `project.dataset.table` and `dataset.table` (in the default project) are the same
+fn same_object(reference: &DatabaseObject, created: &DatabaseObject) -> bool {
+    let kind = match (reference.kind, created.kind) {
+        (ObjectKind::Table, ObjectKind::View) => true,
+        (r, c) => r == c,
+    };
+    if !kind {
+        return false;
+    }
+    let a: Vec<&str> = reference.name.split('.').collect();
+    let b: Vec<&str> = created.name.split('.').collect();
+    // the shortest name is `dataset` for schemas and `dataset.table` for others
+    let min = if created.kind == ObjectKind::Schema {
+        1
+    } else {
+        2
+    };
+    a == b
+        || (a.len() == b.len() + 1 && b.len() >= min && a[1..] == b[..])
+        || (b.len() == a.len() + 1 && a.len() >= min && b[1..] == a[..])
+}
+
+// Kahn's algorithm (the file which comes first in the input is deployed first)
+fn topological_order(size: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut indegrees = vec![0; size];
+    for &(_, to) in edges {
+        indegrees[to] += 1;
+    }
+    let mut ready: BTreeSet<usize> = (0..size).filter(|&i| indegrees[i] == 0).collect();
+    let mut order = Vec::new();
+    while let Some(file) = ready.pop_first() {
+        order.push(file);
+        for &(from, to) in edges {
+            if from == file {
+                indegrees[to] -= 1;
+                if indegrees[to] == 0 {
+                    ready.insert(to);
+                }
+            }
+        }
+    }
+    order
+}
+
+// strongly connected components with more than one file (Tarjan's algorithm)
+fn cycles(size: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut tarjan = Tarjan {
+        edges,
+        index: 0,
+        indices: vec![None; size],
+        lowlinks: vec![0; size],
+        stack: Vec::new(),
+        on_stack: vec![false; size],
+        components: Vec::new(),
+    };
+    for file in 0..size {
+        if tarjan.indices[file].is_none() {
+            tarjan.visit(file);
+        }
+    }
+    let mut components: Vec<Vec<usize>> = tarjan
+        .components
+        .into_iter()
+        .filter(|c| c.len() > 1)
+        .map(|mut c| {
+            c.sort();
+            c
+        })
+        .collect();
+    components.sort();
+    components
+}
+
+struct Tarjan<'a> {
+    edges: &'a [(usize, usize)],
+    index: usize,
+    indices: Vec<Option<usize>>,
+    lowlinks: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, file: usize) {
+        self.indices[file] = Some(self.index);
+        self.lowlinks[file] = self.index;
+        self.index += 1;
+        self.stack.push(file);
+        self.on_stack[file] = true;
+        for &(from, to) in self.edges {
+            if from != file {
+                continue;
+            }
+            match self.indices[to] {
+                None => {
+                    self.visit(to);
+                    self.lowlinks[file] = self.lowlinks[file].min(self.lowlinks[to]);
+                }
+                Some(index) if self.on_stack[to] => {
+                    self.lowlinks[file] = self.lowlinks[file].min(index);
+                }
+                _ => (),
+            }
+        }
+        if Some(self.lowlinks[file]) == self.indices[file] {
+            let mut component = Vec::new();
+            while let Some(top) = self.stack.pop() {
+                self.on_stack[top] = false;
+                component.push(top);
+                if top == file {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use super::*;
+use crate::validate::tests::parse;
+
+fn build(files: &[(&str, &str)]) -> DependencyGraph {
+    let files: Vec<(String, Vec<Node>)> = files
+        .iter()
+        .map(|(name, code)| (name.to_string(), parse(code)))
+        .collect();
+    build_dependency_graph(&files)
+}
+
+fn objects(objects: &[DatabaseObject]) -> Vec<(ObjectKind, &str)> {
+    objects.iter().map(|o| (o.kind, o.name.as_str())).collect()
+}
+
+#[test]
+fn test_statement_dependencies() {
+    let graph = build(&[(
+        "a.sql",
+        "\
+CREATE SCHEMA ds;
+CREATE TEMP FUNCTION tmp(x INT64) AS (x);
+CREATE OR REPLACE VIEW ds.v AS
+WITH c AS (SELECT 1 AS id)
+SELECT ds.f(t.x), tmp(1), UPPER('a'), SAFE.ds.g(1), NET.HOST('a')
+FROM c JOIN `proj.ds.t` AS t USING (id), t.arr, UNNEST([1]), ds.tvf(1);
+SELECT * FROM ML.PREDICT(MODEL ds.m, TABLE ds.input), region-us.INFORMATION_SCHEMA.JOBS;
+CALL ds.p((SELECT MAX(x) FROM ds.u));
+INSERT INTO ds.t1 SELECT * FROM ds.t2;
+MERGE ds.t1 USING ds.t3 ON TRUE WHEN MATCHED THEN DELETE;
+CREATE TABLE ds.copy CLONE ds.t1;
+DROP TABLE ds.old;
+FROM ds.p1 |> JOIN ds.p2 USING (id) |> CALL ds.ptvf();
+",
+    )]);
+    let statements: Vec<(usize, Vec<(ObjectKind, &str)>, Vec<(ObjectKind, &str)>)> = graph
+        .statements
+        .iter()
+        .map(|s| (s.statement, objects(&s.creates), objects(&s.references)))
+        .collect();
+    assert_eq!(
+        statements,
+        vec![
+            (0, vec![(ObjectKind::Schema, "ds")], vec![]),
+            (
+                2,
+                vec![(ObjectKind::View, "ds.v")],
+                vec![
+                    (ObjectKind::Schema, "ds"),
+                    (ObjectKind::Function, "ds.f"),
+                    (ObjectKind::Function, "ds.g"),
+                    (ObjectKind::Table, "proj.ds.t"),
+                    (ObjectKind::Function, "ds.tvf"),
+                ]
+            ),
+            (
+                3,
+                vec![],
+                vec![(ObjectKind::Model, "ds.m"), (ObjectKind::Table, "ds.input"),]
+            ),
+            (
+                4,
+                vec![],
+                vec![(ObjectKind::Procedure, "ds.p"), (ObjectKind::Table, "ds.u"),]
+            ),
+            (
+                5,
+                vec![],
+                vec![(ObjectKind::Table, "ds.t1"), (ObjectKind::Table, "ds.t2")]
+            ),
+            (
+                6,
+                vec![],
+                vec![(ObjectKind::Table, "ds.t1"), (ObjectKind::Table, "ds.t3")]
+            ),
+            (
+                7,
+                vec![(ObjectKind::Table, "ds.copy")],
+                vec![(ObjectKind::Schema, "ds"), (ObjectKind::Table, "ds.t1")]
+            ),
+            (
+                9,
+                vec![],
+                vec![
+                    (ObjectKind::Table, "ds.p1"),
+                    (ObjectKind::Table, "ds.p2"),
+                    (ObjectKind::Function, "ds.ptvf"),
+                ]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_deployment_order() {
+    let graph = build(&[
+        ("report.sql", "SELECT * FROM ds.summary;"),
+        (
+            "summary.sql",
+            "CREATE VIEW ds.summary AS SELECT udf.clean(x) FROM `project.ds.raw`;",
+        ),
+        ("raw.sql", "CREATE TABLE ds.raw (x STRING);"),
+        (
+            "udf.sql",
+            "CREATE FUNCTION udf.clean(x STRING) AS (TRIM(x));",
+        ),
+        ("schema.sql", "CREATE SCHEMA ds; CREATE SCHEMA udf;"),
+        // the object created in the same file is used
+        (
+            "local.sql",
+            "CREATE TABLE ds.raw (x STRING); SELECT * FROM ds.raw;",
+        ),
+    ]);
+    assert_eq!(
+        graph.edges,
+        vec![
+            (1, 0),
+            (2, 1),
+            (3, 1),
+            (4, 1),
+            (4, 2),
+            (4, 3),
+            (4, 5),
+            (5, 1)
+        ]
+    );
+    assert_eq!(graph.order, vec![4, 2, 3, 5, 1, 0]);
+    assert_eq!(graph.cycles, Vec::<Vec<usize>>::new());
+}
+
+#[test]
+fn test_cycles() {
+    let graph = build(&[
+        ("a.sql", "CREATE VIEW ds.a AS SELECT * FROM ds.c;"),
+        ("b.sql", "CREATE VIEW ds.b AS SELECT * FROM ds.a;"),
+        ("c.sql", "CREATE VIEW ds.c AS SELECT * FROM ds.b;"),
+        ("d.sql", "SELECT * FROM ds.a;"),
+        ("e.sql", "CREATE PROCEDURE ds.e() BEGIN CALL ds.f(); END;"),
+        ("f.sql", "CREATE PROCEDURE ds.f() BEGIN CALL ds.e(); END;"),
+        ("g.sql", "CREATE TABLE ds.g (x INT64);"),
+    ]);
+    assert_eq!(graph.cycles, vec![vec![0, 1, 2], vec![4, 5]]);
+    assert_eq!(graph.order, vec![6]);
+}
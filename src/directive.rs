@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::Node;
+use crate::docs::comment_text;
+use crate::token::Token;
+use serde::Serialize;
+
+const PREFIX: &str = "bq2cst:";
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub enum DirectiveKind {
+    Disable,         // /* bq2cst:disable */
+    Enable,          // /* bq2cst:enable */
+    DisableLine,     // -- bq2cst:disable-line
+    DisableNextLine, // -- bq2cst:disable-next-line
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct Directive {
+    pub kind: DirectiveKind,
+    pub rules: Vec<String>, // empty means all rules
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize, // the last line of the comment
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct Suppression {
+    pub rules: Vec<String>, // empty means all rules
+    pub start_line: usize,
+    pub end_line: usize,
+    pub statements: Vec<usize>, // indices of the statements overlapping the range
+}
+
+/// Reads `bq2cst:` directives from comment tokens.
+/// Comments that do not start with `bq2cst:` (or use an unknown directive) are ignored.
+pub fn find_directives(tokens: &[Token]) -> Vec<Directive> {
+    let mut res = Vec::new();
+    for token in tokens {
+        if !token.is_comment() {
+            continue;
+        }
+        let text = comment_text(&token.literal);
+        let text = match text.strip_prefix(PREFIX) {
+            Some(t) => t,
+            None => continue,
+        };
+        let mut words = text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|w| !w.is_empty());
+        let kind = match words.next() {
+            Some("disable") => DirectiveKind::Disable,
+            Some("enable") => DirectiveKind::Enable,
+            Some("disable-line") => DirectiveKind::DisableLine,
+            Some("disable-next-line") => DirectiveKind::DisableNextLine,
+            _ => continue,
+        };
+        res.push(Directive {
+            kind,
+            // `--` starts a free-form description
+            rules: words
+                .take_while(|w| !w.starts_with("--"))
+                .map(|w| w.to_string())
+                .collect(),
+            line: token.line,
+            column: token.column,
+            end_line: token.line + token.literal.matches('\n').count(),
+        });
+    }
+    res
+}
+
+/// Resolves directives into line ranges (1-indexed, inclusive) and the statements they cover.
+/// `/* bq2cst:disable */` without the corresponding `enable` lasts until the end of the code.
+pub fn resolve_suppressions(tokens: &[Token], stmts: &[Node]) -> Vec<Suppression> {
+    let last_line = tokens
+        .iter()
+        .filter(|t| t.line != usize::MAX) // EOF
+        .map(|t| t.line + t.literal.matches('\n').count())
+        .max()
+        .unwrap_or(1);
+    let mut ranges: Vec<(Vec<String>, usize, usize)> = Vec::new();
+    // (rules, start_line) of disable directives which are not closed yet
+    let mut opened: Vec<(Vec<String>, usize)> = Vec::new();
+    for d in find_directives(tokens) {
+        match d.kind {
+            DirectiveKind::DisableLine => ranges.push((d.rules, d.line, d.end_line)),
+            DirectiveKind::DisableNextLine => {
+                ranges.push((d.rules, d.end_line + 1, d.end_line + 1))
+            }
+            DirectiveKind::Disable => opened.push((d.rules, d.line)),
+            DirectiveKind::Enable => {
+                let mut rest = Vec::new();
+                for (rules, start_line) in opened {
+                    if d.rules.is_empty() {
+                        ranges.push((rules, start_line, d.end_line));
+                    } else if rules.is_empty() {
+                        // `disable` (all rules) is not narrowed by `enable rule`
+                        rest.push((rules, start_line));
+                    } else {
+                        let (enabled, disabled): (Vec<String>, Vec<String>) =
+                            rules.into_iter().partition(|r| d.rules.contains(r));
+                        if !enabled.is_empty() {
+                            ranges.push((enabled, start_line, d.end_line));
+                        }
+                        if !disabled.is_empty() {
+                            rest.push((disabled, start_line));
+                        }
+                    }
+                }
+                opened = rest;
+            }
+        }
+    }
+    for (rules, start_line) in opened {
+        ranges.push((rules, start_line, last_line));
+    }
+    ranges.sort_by_key(|r| (r.1, r.2));
+
+    let stmt_lines: Vec<(usize, usize)> = stmts.iter().filter_map(token_lines).collect();
+    ranges
+        .into_iter()
+        .map(|(rules, start_line, end_line)| Suppression {
+            statements: stmt_lines
+                .iter()
+                .enumerate()
+                .filter(|(_, (s, e))| *s <= end_line && start_line <= *e)
+                .map(|(i, _)| i)
+                .collect(),
+            rules,
+            start_line,
+            end_line,
+        })
+        .collect()
+}
+
+// comments are not taken into account
+fn token_lines(node: &Node) -> Option<(usize, usize)> {
+    let mut res = node
+        .token
+        .as_ref()
+        .map(|t| (t.line, t.line + t.literal.matches('\n').count()));
+    for (_, child) in node.child_nodes() {
+        if let Some((start, end)) = token_lines(child) {
+            res = match res {
+                Some((s, e)) => Some((s.min(start), e.max(end))),
+                None => Some((start, end)),
+            };
+        }
+    }
+    res
+}
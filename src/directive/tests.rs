@@ -0,0 +1,97 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::validate::tests::parse;
+
+fn suppressions(code: &str) -> Vec<Suppression> {
+    let l = Lexer::new(code.to_string());
+    let tokens = l.tokenize_code().expect("Failed to tokenize code.");
+    resolve_suppressions(&tokens, &parse(code))
+}
+
+#[test]
+fn test_find_directives() {
+    let l = Lexer::new(
+        "\
+-- bq2cst:disable-next-line rule-a, rule-b -- reason
+SELECT 1; # bq2cst:disable-line
+/* bq2cst:disable */
+/* bq2cst:unknown */
+-- not a directive
+"
+        .to_string(),
+    );
+    let tokens = l.tokenize_code().expect("Failed to tokenize code.");
+    let directives = find_directives(&tokens);
+    assert_eq!(
+        directives,
+        vec![
+            Directive {
+                kind: DirectiveKind::DisableNextLine,
+                rules: vec!["rule-a".to_string(), "rule-b".to_string()],
+                line: 1,
+                column: 1,
+                end_line: 1,
+            },
+            Directive {
+                kind: DirectiveKind::DisableLine,
+                rules: vec![],
+                line: 2,
+                column: 11,
+                end_line: 2,
+            },
+            Directive {
+                kind: DirectiveKind::Disable,
+                rules: vec![],
+                line: 3,
+                column: 1,
+                end_line: 3,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_resolve_suppressions() {
+    let res = suppressions(
+        "\
+SELECT 1;
+-- bq2cst:disable-next-line rule-a
+SELECT
+  2;
+/* bq2cst:disable rule-a rule-b */
+SELECT 3;
+/* bq2cst:enable rule-a */
+SELECT 4;
+/* bq2cst:disable */
+SELECT 5;",
+    );
+    assert_eq!(
+        res,
+        vec![
+            Suppression {
+                rules: vec!["rule-a".to_string()],
+                start_line: 3,
+                end_line: 3,
+                statements: vec![1],
+            },
+            Suppression {
+                rules: vec!["rule-a".to_string()],
+                start_line: 5,
+                end_line: 7,
+                statements: vec![2],
+            },
+            Suppression {
+                rules: vec!["rule-b".to_string()],
+                start_line: 5,
+                end_line: 10,
+                statements: vec![2, 3, 4],
+            },
+            Suppression {
+                rules: vec![],
+                start_line: 9,
+                end_line: 10,
+                statements: vec![4],
+            },
+        ]
+    );
+}
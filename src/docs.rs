@@ -0,0 +1,259 @@
+#[cfg(test)]
+mod tests;
+
+use crate::bqtype::BqType;
+use crate::cst::Node;
+use crate::cst::NodeType;
+use crate::literal::{decode_string_literal, LiteralValue};
+use serde::Serialize;
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct ObjectDoc {
+    pub object: String,
+    pub kind: String,
+    pub line: usize,
+    pub column: usize,
+    pub comment: Option<String>,
+    pub description: Option<String>,
+    pub parameters: Vec<ColumnDoc>,
+    pub columns: Vec<ColumnDoc>,
+    pub returns: Option<String>,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct ColumnDoc {
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub comment: Option<String>,
+    pub description: Option<String>,
+    pub fields: Vec<ColumnDoc>,
+}
+
+/// Associates doc comments (the comment block right above `CREATE ...`)
+/// and `OPTIONS(description=...)` with the created objects.
+pub fn extract_docs(stmts: &Vec<Node>) -> Vec<ObjectDoc> {
+    let mut res = Vec::new();
+    for stmt in stmts {
+        let kind = match stmt.node_type {
+            NodeType::CreateFunctionStatement => {
+                if stmt.get_node("table").is_some() {
+                    "TABLE FUNCTION"
+                } else if stmt.get_node("aggregate").is_some() {
+                    "AGGREGATE FUNCTION"
+                } else {
+                    "FUNCTION"
+                }
+            }
+            NodeType::CreateProcedureStatement => "PROCEDURE",
+            NodeType::CreateTableStatement => {
+                if stmt.get_node("external").is_some() {
+                    "EXTERNAL TABLE"
+                } else if stmt.get_node("snapshot").is_some() {
+                    "SNAPSHOT TABLE"
+                } else {
+                    "TABLE"
+                }
+            }
+            NodeType::CreateViewStatement => {
+                if stmt.get_node("materialized").is_some() {
+                    "MATERIALIZED VIEW"
+                } else {
+                    "VIEW"
+                }
+            }
+            _ => continue,
+        };
+        let object = match stmt.get_node("ident").and_then(|n| n.identifier_parts()) {
+            Some(parts) => parts.join("."),
+            None => continue,
+        };
+        let token = stmt.token.clone().unwrap();
+        let mut doc = ObjectDoc {
+            object,
+            kind: kind.to_string(),
+            line: token.line,
+            column: token.column,
+            comment: doc_comment(stmt),
+            description: options_description(stmt),
+            parameters: Vec::new(),
+            columns: Vec::new(),
+            returns: None,
+        };
+        match stmt.node_type {
+            NodeType::CreateFunctionStatement | NodeType::CreateProcedureStatement => {
+                if let Some(group) = stmt.get_node("group") {
+                    doc.parameters = declaration_docs(group);
+                }
+                if let Some(returns) = stmt.get_node("returns") {
+                    doc.returns = returns.get_node("type").and_then(type_string);
+                }
+            }
+            NodeType::CreateTableStatement => {
+                if let Some(group) = stmt.get_node("column_schema_group") {
+                    doc.columns = declaration_docs(group);
+                }
+            }
+            NodeType::CreateViewStatement => {
+                if let Some(idents) = stmt
+                    .get_node("column_name_list")
+                    .and_then(|n| n.get_node_vec("idents"))
+                {
+                    for ident in idents {
+                        doc.columns.push(ColumnDoc {
+                            name: ident.identifier_parts().map(|p| p.join(".")),
+                            type_: None,
+                            comment: join_comments(ident.comments()),
+                            description: options_description(ident),
+                            fields: Vec::new(),
+                        });
+                    }
+                }
+            }
+            _ => (),
+        }
+        res.push(doc);
+    }
+    res
+}
+
+fn declaration_docs(group: &Node) -> Vec<ColumnDoc> {
+    let mut res = Vec::new();
+    let declarations = match group.get_node_vec("declarations") {
+        Some(ns) => ns,
+        None => return res,
+    };
+    for decl in declarations {
+        if decl.node_type != NodeType::TypeDeclaration {
+            continue; // constraints
+        }
+        let type_ = decl.get_node("type");
+        let fields = match type_.and_then(|t| t.get_node("type_declaration")) {
+            Some(n) if n.node_type == NodeType::GroupedTypeDeclarationOrConstraints => {
+                declaration_docs(n)
+            }
+            _ => Vec::new(),
+        };
+        let comments = decl
+            .comments()
+            .into_iter()
+            .filter(|c| fields.is_empty() || !is_inside_grouped_type(c, type_))
+            .collect();
+        res.push(ColumnDoc {
+            name: decl
+                .token
+                .as_ref()
+                .map(|t| t.literal.trim_matches('`').to_string()),
+            type_: type_.and_then(type_string),
+            comment: join_comments(comments),
+            description: type_.and_then(options_description),
+            fields,
+        });
+    }
+    res
+}
+
+// comments inside `STRUCT<...>` belong to the nested fields
+fn is_inside_grouped_type(comment: &Node, type_: Option<&Node>) -> bool {
+    let grouped = match type_.and_then(|t| t.get_node("type_declaration")) {
+        Some(n) => n,
+        None => return false,
+    };
+    let rparen = match grouped.get_node("rparen") {
+        Some(n) => n.position(),
+        None => return false,
+    };
+    let pos = comment.position();
+    grouped.position() < pos && pos < rparen
+}
+
+/// Returns the contiguous comment block right above the statement.
+fn doc_comment(stmt: &Node) -> Option<String> {
+    let comments = stmt.get_node_vec("leading_comments")?;
+    let mut next_line = stmt.token.as_ref()?.line;
+    let mut block = Vec::new();
+    for c in comments.iter().rev() {
+        let token = c.token.as_ref()?;
+        let end_line = token.line + token.literal.matches('\n').count();
+        if end_line + 1 < next_line {
+            break;
+        }
+        next_line = token.line;
+        block.push(c);
+    }
+    block.reverse();
+    join_comments(block)
+}
+
+fn join_comments(comments: Vec<&Node>) -> Option<String> {
+    let texts: Vec<String> = comments
+        .iter()
+        .map(|c| comment_text(c.literal()))
+        .filter(|t| !t.is_empty())
+        .collect();
+    if texts.is_empty() {
+        None
+    } else {
+        Some(texts.join("\n"))
+    }
+}
+
+pub fn comment_text(literal: &str) -> String {
+    if literal.starts_with("{%") {
+        String::new() // jinja statement
+    } else if let Some(body) = literal.strip_prefix("{#") {
+        let body = body.strip_suffix("#}").unwrap_or(body);
+        body.trim_matches('-').trim().to_string()
+    } else if let Some(body) = literal.strip_prefix("/*") {
+        let body = body.strip_suffix("*/").unwrap_or(body);
+        let body = body.strip_prefix('*').unwrap_or(body);
+        let lines: Vec<&str> = body
+            .lines()
+            .map(|l| {
+                let l = l.trim();
+                let l = l.strip_prefix('*').unwrap_or(l);
+                l.strip_prefix(' ').unwrap_or(l)
+            })
+            .collect();
+        lines.join("\n").trim().to_string()
+    } else {
+        let body = literal
+            .strip_prefix("--")
+            .or_else(|| literal.strip_prefix('#'))
+            .unwrap_or(literal);
+        body.trim().to_string()
+    }
+}
+
+fn options_description(node: &Node) -> Option<String> {
+    let exprs = node
+        .get_node("options")?
+        .get_node("group")?
+        .get_node_vec("exprs")?;
+    for expr in exprs {
+        if expr.node_type != NodeType::BinaryOperator {
+            continue;
+        }
+        let left = expr.get_node("left")?;
+        if left.literal().eq_ignore_ascii_case("description") {
+            return string_value(expr.get_node("right")?);
+        }
+    }
+    None
+}
+
+fn string_value(node: &Node) -> Option<String> {
+    match node.node_type {
+        NodeType::StringLiteral => match decode_string_literal(node.token.as_ref()?).ok()?.value {
+            LiteralValue::String(s) => Some(s),
+            LiteralValue::Bytes(_) => None,
+        },
+        NodeType::UnaryOperator => string_value(node.get_node("right")?),
+        _ => None,
+    }
+}
+
+// `None` if the type is unknown (e.g. templates)
+fn type_string(type_: &Node) -> Option<String> {
+    BqType::from_type(type_).map(|t| t.to_string())
+}
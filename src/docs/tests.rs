@@ -0,0 +1,131 @@
+use super::*;
+use crate::validate::tests::parse;
+
+fn docs(code: &str) -> Vec<ObjectDoc> {
+    extract_docs(&parse(code))
+}
+
+#[test]
+fn test_function_docs() {
+    let res = docs(
+        "\
+-- unrelated comment
+
+-- Adds two integers.
+-- Returns NULL if either is NULL.
+CREATE FUNCTION dataset.add(
+  x INT64, -- left operand
+  y INT64 -- right operand
+) RETURNS INT64 AS (x + y);",
+    );
+    assert_eq!(res.len(), 1);
+    let doc = &res[0];
+    assert_eq!(doc.object, "dataset.add");
+    assert_eq!(doc.kind, "FUNCTION");
+    assert_eq!((doc.line, doc.column), (5, 1));
+    assert_eq!(
+        doc.comment,
+        Some("Adds two integers.\nReturns NULL if either is NULL.".to_string())
+    );
+    assert_eq!(doc.returns, Some("INT64".to_string()));
+    assert_eq!(doc.parameters.len(), 2);
+    assert_eq!(doc.parameters[0].name, Some("x".to_string()));
+    assert_eq!(doc.parameters[0].type_, Some("INT64".to_string()));
+    assert_eq!(doc.parameters[0].comment, Some("left operand".to_string()));
+    assert_eq!(doc.parameters[1].comment, Some("right operand".to_string()));
+}
+
+#[test]
+fn test_table_docs() {
+    let res = docs(
+        "\
+/**
+ * Users of the service.
+ */
+CREATE TABLE `project.dataset`.users (
+  id INT64 NOT NULL OPTIONS(description = 'primary id'),
+  profile STRUCT<
+    name STRING, -- display name
+    tags ARRAY<STRING>
+  >,
+  PRIMARY KEY (id) NOT ENFORCED
+)
+OPTIONS(description = \"user table\");
+SELECT 1;",
+    );
+    assert_eq!(res.len(), 1);
+    let doc = &res[0];
+    assert_eq!(doc.object, "project.dataset.users");
+    assert_eq!(doc.kind, "TABLE");
+    assert_eq!(doc.comment, Some("Users of the service.".to_string()));
+    assert_eq!(doc.description, Some("user table".to_string()));
+    assert_eq!(doc.columns.len(), 2);
+    assert_eq!(doc.columns[0].description, Some("primary id".to_string()));
+    let profile = &doc.columns[1];
+    assert_eq!(
+        profile.type_,
+        Some("STRUCT<name STRING, tags ARRAY<STRING>>".to_string())
+    );
+    assert_eq!(profile.comment, None);
+    assert_eq!(profile.fields.len(), 2);
+    assert_eq!(profile.fields[0].comment, Some("display name".to_string()));
+    assert_eq!(profile.fields[1].type_, Some("ARRAY<STRING>".to_string()));
+}
+
+#[test]
+fn test_view_and_procedure_docs() {
+    let res = docs(
+        "\
+# not a doc comment
+
+CREATE VIEW v (a OPTIONS(description = 'column a'), b) AS SELECT 1, 2;
+-- Reloads data.
+CREATE PROCEDURE p(IN x NUMERIC(10, 2), OUT y ANY TYPE) BEGIN SELECT 1; END;",
+    );
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[0].kind, "VIEW");
+    assert_eq!(res[0].comment, None);
+    assert_eq!(res[0].columns[0].name, Some("a".to_string()));
+    assert_eq!(res[0].columns[0].description, Some("column a".to_string()));
+    assert_eq!(res[0].columns[1].name, Some("b".to_string()));
+    assert_eq!(res[1].kind, "PROCEDURE");
+    assert_eq!(res[1].comment, Some("Reloads data.".to_string()));
+    assert_eq!(
+        res[1].parameters[0].type_,
+        Some("NUMERIC(10, 2)".to_string())
+    );
+    assert_eq!(res[1].parameters[1].type_, Some("ANY TYPE".to_string()));
+}
+
+#[test]
+fn test_description_literals() {
+    let res = docs(
+        r#"
+CREATE TABLE t (
+  a INT64 OPTIONS(description = r'C:\path'),
+  b INT64 OPTIONS(description = r'''raw'''),
+  c INT64 OPTIONS(description = 'it\'s\n'),
+  d INT64 OPTIONS(description = """a "quoted" text"""),
+  e STRUCT<x int64, y array<string(10)>>
+);"#,
+    );
+    let descriptions: Vec<Option<String>> = res[0]
+        .columns
+        .iter()
+        .map(|c| c.description.clone())
+        .collect();
+    assert_eq!(
+        descriptions,
+        vec![
+            Some("C:\\path".to_string()),
+            Some("raw".to_string()),
+            Some("it's\n".to_string()),
+            Some("a \"quoted\" text".to_string()),
+            None,
+        ]
+    );
+    assert_eq!(
+        res[0].columns[4].type_,
+        Some("STRUCT<x INT64, y ARRAY<STRING(10)>>".to_string())
+    );
+}
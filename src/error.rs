@@ -1,11 +1,18 @@
 use crate::token::Token;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum ErrorKind {
+    Syntax,
+    LegacySql, // the code seems to be written in legacy SQL
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct BQ2CSTError {
     pub line: usize,
     pub column: usize,
     message: String,
+    pub kind: ErrorKind,
 }
 
 impl BQ2CSTError {
@@ -14,6 +21,7 @@ impl BQ2CSTError {
             line,
             column,
             message,
+            kind: ErrorKind::Syntax,
         }
     }
     pub fn from_token(token: &Token, message: String) -> Self {
@@ -21,6 +29,16 @@ impl BQ2CSTError {
             line: token.line,
             column: token.column,
             message,
+            kind: ErrorKind::Syntax,
+        }
+    }
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    pub fn legacy_sql(token: &Token, message: String) -> Self {
+        Self {
+            kind: ErrorKind::LegacySql,
+            ..Self::from_token(token, message)
         }
     }
 }
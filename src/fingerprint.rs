@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod tests;
+
+use crate::builtins::find_builtin;
+use crate::cst::{Node, NodeType};
+use serde::Serialize;
+
+// e.g. DATE '2020-01-01', JSON '{}'
+const TYPED_LITERALS: [&str; 9] = [
+    "BIGDECIMAL",
+    "BIGNUMERIC",
+    "DATE",
+    "DATETIME",
+    "DECIMAL",
+    "JSON",
+    "NUMERIC",
+    "TIME",
+    "TIMESTAMP",
+];
+
+// (position, text, whether no space is put before it)
+type Fragment = ((usize, usize), String, bool);
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct QueryFingerprint {
+    pub normalized: String, // e.g. `SELECT * FROM t WHERE x IN (?) AND y = ?`
+    pub hash: String,       // FNV-1a (64 bit) of `normalized` in 16 hexadecimal digits
+}
+
+/// Normalizes the query and returns its fingerprint. Literals and the lists of
+/// IN operators are replaced with `?`, keywords and the names of built-in
+/// functions are uppercased, and comments are dropped. The tokens are joined
+/// by a single space, except that no space is put before `,` `.` `)` `]` `;`
+/// and the parenthesis of function calls, and after `(` `[` `.`.
+///
+/// The output must not change between releases because fingerprints are
+/// stored by users (see the tests).
+pub fn fingerprint_query(stmts: &[Node]) -> QueryFingerprint {
+    let mut tokens = Vec::new();
+    for stmt in stmts {
+        walk(stmt, false, &mut tokens);
+    }
+    tokens.sort_by_key(|(position, _, _)| *position);
+    let mut normalized = String::new();
+    let mut previous = "";
+    for (_, token, call) in &tokens {
+        let glued = *call
+            || matches!(token.as_str(), "," | "." | ")" | "]" | ";")
+            || matches!(previous, "(" | "[" | ".");
+        if !normalized.is_empty() && !glued {
+            normalized.push(' ');
+        }
+        normalized.push_str(token);
+        previous = token;
+    }
+    QueryFingerprint {
+        hash: format!("{:016x}", fnv1a(normalized.as_bytes())),
+        normalized,
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// `builtin` is true if the node is the name of a built-in function.
+fn walk(node: &Node, builtin: bool, tokens: &mut Vec<Fragment>) {
+    let Some(token) = &node.token else {
+        return;
+    };
+    if token.literal.is_empty() {
+        return; // EOF
+    }
+    let position = (token.line, token.column);
+    // the keys of the children which are replaced with the placeholder
+    let replaced: &[&str] = match node.node_type {
+        NodeType::NumericLiteral | NodeType::StringLiteral | NodeType::BooleanLiteral => &[],
+        NodeType::UnaryOperator if is_literal(node) => &["right"],
+        NodeType::RangeLiteral => &["type"],
+        NodeType::IntervalLiteral if node.get_node("expr").is_some_and(is_literal) => {
+            &["expr", "date_part", "to", "to_date_part"]
+        }
+        _ => {
+            let call = matches!(
+                node.node_type,
+                NodeType::CallingFunction
+                    | NodeType::CallingTableFunction
+                    | NodeType::CallingUnnest
+            );
+            tokens.push((position, normalize(node, builtin), call));
+            for (key, child) in node.child_nodes() {
+                match (&node.node_type, key, &child.node_type) {
+                    // x IN (1, 2, 3)
+                    (NodeType::InOperator, "right", NodeType::GroupedExprs) => {
+                        in_list(child, tokens)
+                    }
+                    (NodeType::CallingFunction, "func", _) => {
+                        walk(child, is_builtin(child), tokens)
+                    }
+                    _ => walk(child, builtin, tokens),
+                }
+            }
+            return;
+        }
+    };
+    tokens.push((position, "?".to_string(), false));
+    for (key, child) in node.child_nodes() {
+        if !replaced.contains(&key) {
+            walk(child, false, tokens);
+        }
+    }
+}
+
+fn in_list(node: &Node, tokens: &mut Vec<Fragment>) {
+    let Some(token) = &node.token else {
+        return;
+    };
+    tokens.push(((token.line, token.column), token.literal.clone(), false));
+    if let Some(first) = node.get_node_vec("exprs").and_then(|exprs| exprs.first()) {
+        tokens.push((first.position(), "?".to_string(), false));
+    }
+    for (key, child) in node.child_nodes() {
+        if key != "exprs" {
+            walk(child, false, tokens);
+        }
+    }
+}
+
+// 1, 'a', TRUE, -1, DATE '2020-01-01'
+fn is_literal(node: &Node) -> bool {
+    match node.node_type {
+        NodeType::NumericLiteral | NodeType::StringLiteral | NodeType::BooleanLiteral => true,
+        NodeType::UnaryOperator => {
+            let operator = node.literal().to_uppercase();
+            match node.get_node("right").map(|r| &r.node_type) {
+                Some(NodeType::NumericLiteral) => operator == "-" || operator == "+",
+                Some(NodeType::StringLiteral) => TYPED_LITERALS.contains(&operator.as_str()),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn is_builtin(func: &Node) -> bool {
+    let Some(parts) = func.identifier_parts() else {
+        return false;
+    };
+    let parts = match parts.split_first() {
+        Some((first, rest)) if first.eq_ignore_ascii_case("SAFE") && !rest.is_empty() => rest,
+        _ => &parts[..],
+    };
+    find_builtin(&parts.join(".")).is_some()
+}
+
+fn normalize(node: &Node, builtin: bool) -> String {
+    let literal = node.literal();
+    let name = matches!(
+        node.node_type,
+        NodeType::Identifier
+            | NodeType::MultiTokenIdentifier
+            | NodeType::IdentWithOptions
+            | NodeType::WithQuery
+            | NodeType::TypeDeclaration
+            | NodeType::WindowExpr
+            | NodeType::Parameter
+            | NodeType::Template
+            | NodeType::MacroInvocation
+            | NodeType::Pattern
+            | NodeType::SqlxBlock
+            | NodeType::Unknown
+    );
+    let keyword = node.token.as_ref().is_some_and(|t| t.is_reserved_keyword());
+    // the names are case-sensitive (e.g. tables) unless they are keywords
+    if !name || keyword || (builtin && !literal.starts_with('`')) {
+        literal.to_uppercase()
+    } else {
+        literal.to_string()
+    }
+}
@@ -0,0 +1,87 @@
+use super::*;
+use crate::validate::tests::parse;
+
+fn fingerprint(code: &str) -> QueryFingerprint {
+    fingerprint_query(&parse(code))
+}
+
+#[test]
+fn test_fnv1a() {
+    // test vectors of FNV-1a (64 bit)
+    assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+    assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    assert_eq!(fnv1a(b"foobar"), 0x85944171f73967e8);
+}
+
+#[test]
+fn test_normalized() {
+    let test_cases = vec![
+        (
+            "select * from `ds.t` where x in (1, 2, 3) and y = 'a' -- comment",
+            "SELECT * FROM `ds.t` WHERE x IN (?) AND y = ?",
+        ),
+        (
+            "SELECT DATE '2020-01-01', -1.5, TRUE, NULL, @p, ? FROM ds.t",
+            "SELECT ?, ?, ?, NULL, @p, ? FROM ds.t",
+        ),
+        (
+            "SELECT INTERVAL 1 DAY, INTERVAL '1-2' YEAR TO MONTH, INTERVAL x HOUR",
+            "SELECT ?, ?, INTERVAL x HOUR",
+        ),
+        (
+            "SELECT RANGE<DATE> '[2020-01-01, UNBOUNDED)', JSON '{}', NUMERIC '1.5'",
+            "SELECT ?, ?, ?",
+        ),
+        (
+            "select count(*), safe.substr(Name, 2) as s, ds.MyFunc(x) from Tbl",
+            "SELECT COUNT(*), SAFE.SUBSTR(Name, ?) AS s, ds.MyFunc(x) FROM Tbl",
+        ),
+        (
+            "select a from t where b not in (select c from u) and d in unnest([1, 2])",
+            "SELECT a FROM t WHERE b NOT IN (SELECT c FROM u) AND d IN UNNEST([?, ?])",
+        ),
+        (
+            "/* header */\nINSERT INTO ds.t (a, b)\nVALUES (1, 'x');\nselect 1;",
+            "INSERT INTO ds.t (a, b) VALUES (?, ?); SELECT ?;",
+        ),
+    ];
+    for (code, expected) in test_cases {
+        assert_eq!(fingerprint(code).normalized, expected, "{}", code);
+    }
+}
+
+#[test]
+fn test_same_fingerprint() {
+    let codes = vec![
+        "SELECT name FROM ds.users WHERE id IN (1, 2) AND created > DATE '2020-01-01'",
+        "select name\nfrom ds.users\nwhere id in (3)\n  and created > date '2024-12-31' -- latest",
+        "SELECT name FROM ds.users /* x */ WHERE id IN (@a, @b, @c) AND created > '2020-01-01'",
+    ];
+    let fingerprints: Vec<QueryFingerprint> = codes.iter().map(|c| fingerprint(c)).collect();
+    for f in &fingerprints {
+        assert_eq!(f, &fingerprints[0]);
+    }
+    // the names of tables are case-sensitive
+    assert_ne!(
+        fingerprint("SELECT name FROM ds.Users WHERE id IN (1) AND created > '2020-01-01'"),
+        fingerprints[0]
+    );
+}
+
+// the hashes must not change between releases
+#[test]
+fn test_stable_hash() {
+    let test_cases = vec![
+        ("SELECT 1", "SELECT ?", "199e7dca63ea8858"),
+        (
+            "select * from ds.t where x in (1, 2)",
+            "SELECT * FROM ds.t WHERE x IN (?)",
+            "7658b1b0580214b6",
+        ),
+    ];
+    for (code, normalized, hash) in test_cases {
+        let f = fingerprint(code);
+        assert_eq!(f.normalized, normalized);
+        assert_eq!(f.hash, hash, "{}", code);
+    }
+}
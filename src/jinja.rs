@@ -0,0 +1,183 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::Node;
+use crate::error::BQ2CSTResult;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+// tags which have the corresponding `{% end... %}` tag
+const BLOCK_KEYWORDS: [&str; 12] = [
+    "if",
+    "for",
+    "macro",
+    "call",
+    "filter",
+    "set", // only if it is `{% set x %}...{% endset %}`
+    "raw",
+    "block",
+    "docs",
+    "materialization",
+    "test",
+    "snapshot",
+];
+
+// the contents of these blocks are not SQL
+const OPAQUE_KEYWORDS: [&str; 6] = ["macro", "set", "raw", "docs", "materialization", "test"];
+
+/// Returns the first word of `{% ... %}`.
+/// e.g. `{%- if x -%}` -> `if`
+pub fn tag_keyword(tag: &str) -> String {
+    let inner = tag.trim_start_matches("{%").trim_start_matches(['-', '+']);
+    inner
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Returns true if the tag starts a block, which ends with `{% end... %}`.
+pub fn is_block_start(tag: &str) -> bool {
+    let keyword = tag_keyword(tag);
+    if keyword == "set" {
+        // `{% set x = 1 %}` does not have `{% endset %}`
+        return !tag.contains('=');
+    }
+    BLOCK_KEYWORDS.contains(&keyword.as_str())
+}
+
+/// Returns true if the contents of the block are not SQL.
+pub fn is_opaque_block_start(tag: &str) -> bool {
+    is_block_start(tag) && OPAQUE_KEYWORDS.contains(&tag_keyword(tag).as_str())
+}
+
+// char ranges of a control block (`if` or `for`)
+struct ControlBlock {
+    branches: Vec<(usize, usize)>, // the body of each branch
+    tags: Vec<(usize, usize)>,     // `{% elif %}` and `{% else %}`
+    visible: bool,                 // false if it is in a non-first branch of another block
+}
+
+/// Returns the code in which the `n`th branch (n >= 2) of an `if` / `for` block is selected,
+/// for each such branch. The other branches are replaced with whitespaces,
+/// so that the positions of tokens are not changed.
+pub fn branch_variants(code: &str) -> Vec<String> {
+    let chars: Vec<char> = code.chars().collect();
+    let mut blocks: Vec<ControlBlock> = Vec::new();
+    let mut stack: Vec<Option<ControlBlock>> = Vec::new(); // None for other blocks
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] != '{' || i + 1 == chars.len() {
+            i += 1;
+            continue;
+        }
+        let close = match chars[i + 1] {
+            '%' => '%',
+            '#' => '#',
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        let start = i;
+        i += 2;
+        while i + 1 < chars.len() && !(chars[i] == close && chars[i + 1] == '}') {
+            i += 1;
+        }
+        i = (i + 2).min(chars.len());
+        if close == '#' {
+            continue; // comment
+        }
+        let tag: String = chars[start..i].iter().collect();
+        let keyword = tag_keyword(&tag);
+        let hidden = stack
+            .iter()
+            .any(|b| matches!(b, Some(b) if 1 < b.branches.len()));
+        if is_opaque_block_start(&tag) {
+            i = skip_block(&chars, i);
+        } else if keyword == "if" || keyword == "for" {
+            stack.push(Some(ControlBlock {
+                branches: vec![(i, i)],
+                tags: Vec::new(),
+                visible: !hidden,
+            }));
+        } else if is_block_start(&tag) {
+            stack.push(None);
+        } else if keyword == "elif" || keyword == "else" {
+            if let Some(Some(block)) = stack.last_mut() {
+                block.branches.last_mut().unwrap().1 = start;
+                block.branches.push((i, i));
+                block.tags.push((start, i));
+            }
+        } else if keyword.starts_with("end") {
+            if let Some(Some(mut block)) = stack.pop() {
+                block.branches.last_mut().unwrap().1 = start;
+                blocks.push(block);
+            }
+        }
+    }
+
+    let mut res = Vec::new();
+    for block in blocks.iter().filter(|b| b.visible) {
+        for selected in 1..block.branches.len() {
+            let mut variant = chars.clone();
+            let mut blank = |(from, to): (usize, usize)| {
+                for ch in variant[from..to].iter_mut() {
+                    if *ch != '\n' {
+                        *ch = ' ';
+                    }
+                }
+            };
+            for (n, range) in block.branches.iter().enumerate() {
+                if n != selected {
+                    blank(*range);
+                }
+            }
+            for range in &block.tags {
+                blank(*range);
+            }
+            res.push(variant.into_iter().collect());
+        }
+    }
+    res
+}
+
+// returns the index just after the corresponding `{% end... %}`
+fn skip_block(chars: &[char], mut i: usize) -> usize {
+    let mut depth = 0;
+    while i < chars.len() {
+        if chars[i] == '{' && i + 1 < chars.len() && chars[i + 1] == '%' {
+            let start = i;
+            while i + 1 < chars.len() && !(chars[i] == '%' && chars[i + 1] == '}') {
+                i += 1;
+            }
+            i = (i + 2).min(chars.len());
+            let tag: String = chars[start..i].iter().collect();
+            if is_block_start(&tag) {
+                depth += 1;
+            } else if tag_keyword(&tag).starts_with("end") {
+                if depth == 0 {
+                    return i;
+                }
+                depth -= 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    i
+}
+
+/// Parses the code once for each non-first branch of `if` / `for` blocks
+/// (see `branch_variants()`) and returns the statements of each variant.
+/// `parse()` only parses the first branch, the other branches are treated like comments.
+pub fn parse_branches(code: &str) -> BQ2CSTResult<Vec<Vec<Node>>> {
+    let mut res = Vec::new();
+    for variant in branch_variants(code) {
+        let l = Lexer::new(variant);
+        let mut p = Parser::new(l.tokenize_code()?);
+        res.push(p.parse_code()?);
+    }
+    Ok(res)
+}
@@ -0,0 +1,55 @@
+use super::*;
+
+#[test]
+fn test_tag_keyword() {
+    assert_eq!(tag_keyword("{% if x %}"), "if");
+    assert_eq!(tag_keyword("{%- ELSE -%}"), "else");
+    assert_eq!(tag_keyword("{%+endfor%}"), "endfor");
+    assert!(is_block_start("{% set x %}"));
+    assert!(!is_block_start("{% set x = 1 %}"));
+    assert!(is_opaque_block_start("{% macro m() %}"));
+    assert!(!is_opaque_block_start("{% for x in y %}"));
+}
+
+#[test]
+fn test_branch_variants() {
+    let code = "\
+SELECT {% if x %}a{% elif y %}b{% else %}c{% endif %}
+{# {% if z %}d{% else %}e{% endif %} #}
+{% macro m() %}{% if z %}d{% else %}e{% endif %}{% endmacro %}";
+    assert_eq!(
+        branch_variants(code),
+        vec![
+            "\
+SELECT {% if x %}             b           {% endif %}
+{# {% if z %}d{% else %}e{% endif %} #}
+{% macro m() %}{% if z %}d{% else %}e{% endif %}{% endmacro %}",
+            "\
+SELECT {% if x %}                        c{% endif %}
+{# {% if z %}d{% else %}e{% endif %} #}
+{% macro m() %}{% if z %}d{% else %}e{% endif %}{% endmacro %}",
+        ]
+    );
+    // nested blocks in non-first branches are not expanded
+    assert_eq!(
+        branch_variants("{% if x %}{% else %}{% if y %}{% else %}{% endif %}{% endif %}").len(),
+        1
+    );
+}
+
+#[test]
+fn test_parse_branches() {
+    let variants = parse_branches("SELECT 1 {% if x %} + 1 {% else %} + 2 {% endif %}")
+        .expect("Failed to parse branches.");
+    assert_eq!(variants.len(), 1);
+    let select = &variants[0][0];
+    let expr = &select.get_node_vec("exprs").expect("exprs is not found.")[0];
+    assert_eq!(expr.literal(), "+");
+    assert_eq!(expr.get_node("right").map(|r| r.literal()), Some("2"));
+    assert_eq!(expr.get_node("right").map(|r| r.position()), Some((1, 38)));
+    let error = match parse_branches("SELECT 1 {% if x %} + 1 {% else %}\n  NOT; {% endif %}") {
+        Ok(_) => panic!("Unexpectedly succeeded to parse branches."),
+        Err(e) => e,
+    };
+    assert_eq!((error.line, error.column), (2, 6));
+}
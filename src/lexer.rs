@@ -2,6 +2,7 @@
 mod tests;
 
 use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::jinja;
 use crate::token::Token;
 
 pub struct Lexer {
@@ -10,6 +11,8 @@ pub struct Lexer {
     line: usize,
     column: usize,
     type_declaration_depth: usize,
+    jinja_block_keywords: Vec<String>, // keywords of unclosed `{% if %}`, `{% for %}`, ...
+    sqlx: bool,                        // Dataform
     pub tokens: Vec<Token>,
 }
 
@@ -23,9 +26,17 @@ impl Lexer {
             line: 1,
             column: 1,
             type_declaration_depth: 0,
+            jinja_block_keywords: Vec::new(),
+            sqlx: false,
             tokens: Vec::new(),
         }
     }
+    /// In SQLX mode, `config { ... }` (and other blocks) and `${...}` are read as a single token.
+    pub fn new_sqlx(input: String) -> Lexer {
+        let mut l = Lexer::new(input);
+        l.sqlx = true;
+        l
+    }
     pub fn tokenize_code(mut self) -> BQ2CSTResult<Vec<Token>> {
         let mut token = self.next_token()?;
         while !token.is_none() {
@@ -111,6 +122,11 @@ impl Lexer {
                 if self.get_char(1) == Some('-') {
                     let literal = self.read_comment()?;
                     self.construct_token(line, column, literal)
+                } else if self.get_char(1) == Some('>') {
+                    // edge pattern (graph query)
+                    self.next_char()?;
+                    self.next_char()?;
+                    self.construct_token(line, column, "->".to_string())
                 } else {
                     self.next_char()?;
                     self.construct_token(line, column, ch.to_string())
@@ -205,6 +221,42 @@ impl Lexer {
                 let literal = self.read_parameter()?;
                 self.construct_token(line, column, literal)
             }
+            // macro
+            '$' if is_valid_char_of_ident(&self.get_char(1)) => {
+                let first_position = self.position;
+                self.next_char()?; // $ -> name
+                while is_valid_char_of_ident(&self.get_char(0)) {
+                    self.next_char()?;
+                }
+                let res = self.input[first_position..self.position].iter().collect();
+                self.construct_token(line, column, res)
+            }
+            // sqlx
+            '$' if self.sqlx && self.get_char(1) == Some('{') => {
+                let first_position = self.position;
+                self.next_char()?; // $ -> {
+                self.read_js_block()?;
+                let res = self.input[first_position..self.position].iter().collect();
+                self.construct_token(line, column, res)
+            }
+            _ if self.sqlx && self.is_sqlx_block() => {
+                let first_position = self.position;
+                while self.get_char(0) != Some('{') {
+                    self.next_char()?;
+                }
+                self.read_js_block()?;
+                let res = self.input[first_position..self.position].iter().collect();
+                self.construct_token(line, column, res)
+            }
+            // jinja
+            '{' if self.get_char(1) == Some('%') => {
+                let literal = self.read_jinja_statement()?;
+                self.construct_token(line, column, literal)
+            }
+            '{' if self.get_char(1) == Some('#') => {
+                let literal = self.read_jinja_comment()?;
+                self.construct_token(line, column, literal)
+            }
             // template
             '{' => {
                 let first_position = self.position;
@@ -248,7 +300,10 @@ impl Lexer {
             }
             // other
             _ => {
-                if is_valid_1st_char_of_ident(&Some(ch)) {
+                if self.is_string_prefix() {
+                    let literal = self.read_prefixed_string()?;
+                    self.construct_token(line, column, literal)
+                } else if is_valid_1st_char_of_ident(&Some(ch)) {
                     let literal = self.read_identifier()?;
                     self.construct_token(line, column, literal)
                 } else {
@@ -310,6 +365,134 @@ impl Lexer {
             .collect();
         Ok(res)
     }
+    fn is_sqlx_block(&self) -> bool {
+        // config { ... } | pre_operations { ... } | post_operations { ... } | js { ... }
+        let mut offset = 0;
+        while is_valid_char_of_ident(&self.get_char(offset)) {
+            offset += 1;
+        }
+        let ident: String = self.input[self.position..self.position + offset]
+            .iter()
+            .collect();
+        if !["config", "pre_operations", "post_operations", "js"].contains(&ident.as_str()) {
+            return false;
+        }
+        while is_whitespace(&self.get_char(offset)) {
+            offset += 1;
+        }
+        self.get_char(offset) == Some('{')
+    }
+    fn read_js_block(&mut self) -> BQ2CSTResult<()> {
+        // NOTE braces in JavaScript strings and comments are ignored
+        let mut depth = 0;
+        loop {
+            match self.get_char(0) {
+                Some('{') => depth += 1,
+                Some('}') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.next_char()?; // } ->
+                        return Ok(());
+                    }
+                }
+                Some(quote) if quote == '\'' || quote == '"' || quote == '`' => {
+                    self.next_char()?; // ' ->
+                    while self.get_char(0) != Some(quote) {
+                        if self.get_char(0) == Some('\\') {
+                            self.next_char()?;
+                        }
+                        self.next_char()?;
+                    }
+                }
+                Some('/') if self.get_char(1) == Some('/') => {
+                    while !is_end_of_line(&self.get_char(1)) {
+                        self.next_char()?;
+                    }
+                }
+                Some('/') if self.get_char(1) == Some('*') => {
+                    self.next_char()?; // / -> *
+                    self.next_char()?; // * ->
+                    while !(self.get_char(0) == Some('*') && self.get_char(1) == Some('/')) {
+                        self.next_char()?;
+                    }
+                    self.next_char()?; // * -> /
+                }
+                _ => (),
+            }
+            self.next_char()?;
+        }
+    }
+    fn read_jinja_comment(&mut self) -> BQ2CSTResult<String> {
+        let first_position = self.position;
+        while !(self.get_char(0) == Some('#') && self.get_char(1) == Some('}')) {
+            self.next_char()?;
+        }
+        self.next_char()?; // # -> }
+        self.next_char()?; // } -> next_char
+        let res = self.input[first_position..self.position].iter().collect();
+        Ok(res)
+    }
+    fn read_jinja_tag(&mut self) -> BQ2CSTResult<String> {
+        let first_position = self.position;
+        while !(self.get_char(0) == Some('%') && self.get_char(1) == Some('}')) {
+            self.next_char()?;
+        }
+        self.next_char()?; // % -> }
+        self.next_char()?; // } -> next_char
+        let res = self.input[first_position..self.position].iter().collect();
+        Ok(res)
+    }
+    fn read_jinja_statement(&mut self) -> BQ2CSTResult<String> {
+        // NOTE
+        // `{% if %}` and `{% endif %}` are treated like comments by the parser.
+        // The non-first branches (`{% else %}...`) and the blocks whose contents are not SQL
+        // (`{% macro %}...{% endmacro %}`) are read as a single token.
+        let first_position = self.position;
+        let tag = self.read_jinja_tag()?;
+        let keyword = jinja::tag_keyword(&tag);
+        if jinja::is_opaque_block_start(&tag) {
+            self.skip_jinja_block(true)?;
+        } else if jinja::is_block_start(&tag) {
+            self.jinja_block_keywords.push(keyword);
+        } else if keyword == "elif" || keyword == "else" {
+            if let Some("if") | Some("for") = self.jinja_block_keywords.last().map(|k| k.as_str()) {
+                self.skip_jinja_block(false)?;
+            }
+        } else if keyword.starts_with("end") {
+            self.jinja_block_keywords.pop();
+        }
+        let res = self.input[first_position..self.position]
+            .iter()
+            .collect::<String>()
+            .trim_end()
+            .to_string();
+        Ok(res)
+    }
+    fn skip_jinja_block(&mut self, include_end_tag: bool) -> BQ2CSTResult<()> {
+        let mut depth = 0;
+        loop {
+            if self.get_char(0) == Some('{') && self.get_char(1) == Some('%') {
+                let (position, line, column) = (self.position, self.line, self.column);
+                let tag = self.read_jinja_tag()?;
+                if jinja::is_block_start(&tag) {
+                    depth += 1;
+                } else if jinja::tag_keyword(&tag).starts_with("end") {
+                    if depth == 0 {
+                        if !include_end_tag {
+                            // `{% endif %}` is read as another token
+                            self.position = position;
+                            self.line = line;
+                            self.column = column;
+                        }
+                        return Ok(());
+                    }
+                    depth -= 1;
+                }
+            } else {
+                self.next_char()?;
+            }
+        }
+    }
     fn read_multiline_comment(&mut self) -> BQ2CSTResult<String> {
         let first_position = self.position;
         while !(self.get_char(0) == Some('*') && self.get_char(1) == Some('/')) {
@@ -355,6 +538,18 @@ impl Lexer {
     }
     fn read_number(&mut self) -> BQ2CSTResult<String> {
         let first_position = self.position;
+        if self.get_char(0) == Some('0')
+            && (self.get_char(1) == Some('x') || self.get_char(1) == Some('X'))
+            && is_hex_digit(&self.get_char(2))
+        {
+            self.next_char()?; // 0 -> x
+            self.next_char()?; // x -> 9
+            while is_hex_digit(&self.get_char(0)) {
+                self.next_char()?;
+            }
+            let res = self.input[first_position..self.position].iter().collect();
+            return Ok(res);
+        }
         while is_digit(&self.get_char(0)) {
             self.next_char()?;
         } // 9 -> .
@@ -393,6 +588,35 @@ impl Lexer {
             .collect();
         Ok(res)
     }
+    fn is_string_prefix(&self) -> bool {
+        // r'', b'', rb'', br''
+        let is_quote = |ch: Option<char>| ch == Some('\'') || ch == Some('"');
+        match self.get_char(0) {
+            Some('r') | Some('R') => match self.get_char(1) {
+                Some('b') | Some('B') => is_quote(self.get_char(2)),
+                ch => is_quote(ch),
+            },
+            Some('b') | Some('B') => match self.get_char(1) {
+                Some('r') | Some('R') => is_quote(self.get_char(2)),
+                ch => is_quote(ch),
+            },
+            _ => false,
+        }
+    }
+    fn read_prefixed_string(&mut self) -> BQ2CSTResult<String> {
+        let first_position = self.position;
+        while self.get_char(0) != Some('\'') && self.get_char(0) != Some('"') {
+            self.next_char()?; // r -> '
+        }
+        let quote = self.get_char(0);
+        if self.get_char(1) == quote && self.get_char(2) == quote {
+            self.read_multiline_string()?;
+        } else {
+            self.read_string()?;
+        }
+        let res = self.input[first_position..self.position].iter().collect();
+        Ok(res)
+    }
     fn read_string(&mut self) -> BQ2CSTResult<String> {
         let quote = self.get_char(0);
         let first_position = self.position;
@@ -424,6 +648,13 @@ fn is_digit(ch: &Option<char>) -> bool {
     }
 }
 
+fn is_hex_digit(ch: &Option<char>) -> bool {
+    match ch {
+        Some(ch) => ch.is_ascii_hexdigit(),
+        None => false,
+    }
+}
+
 fn is_end_of_line(ch: &Option<char>) -> bool {
     match ch {
         Some(ch) => ch == &'\n',
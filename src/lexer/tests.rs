@@ -188,26 +188,29 @@ SELECT
   r'''\\1''',
   r'\\\\',
   r'''\\\\''',
-  rb'xxx',",
+  rb'xxx',
+  B\"xxx\",
+  bR\"\"\"x\"\"\",
+  r 'xxx'",
             vec![
                 Token::from_str(1, 1, "SELECT"),
-                Token::from_str(2, 3, "r"),
-                Token::from_str(2, 4, "'xxx'"),
+                Token::from_str(2, 3, "r'xxx'"),
                 Token::from_str(2, 9, ","),
-                Token::from_str(3, 3, "r"),
-                Token::from_str(3, 4, "'\\1'"),
-                Token::from_str(4, 3, "r"),
-                Token::from_str(4, 4, "'''\\1'''"),
+                Token::from_str(3, 3, "r'\\1'"),
+                Token::from_str(4, 3, "r'''\\1'''"),
                 Token::from_str(4, 12, ","),
-                Token::from_str(5, 3, "r"),
-                Token::from_str(5, 4, "'\\\\'"),
+                Token::from_str(5, 3, "r'\\\\'"),
                 Token::from_str(5, 8, ","),
-                Token::from_str(6, 3, "r"),
-                Token::from_str(6, 4, "'''\\\\'''"),
+                Token::from_str(6, 3, "r'''\\\\'''"),
                 Token::from_str(6, 12, ","),
-                Token::from_str(7, 3, "rb"),
-                Token::from_str(7, 5, "'xxx'"),
+                Token::from_str(7, 3, "rb'xxx'"),
                 Token::from_str(7, 10, ","),
+                Token::from_str(8, 3, "B\"xxx\""),
+                Token::from_str(8, 9, ","),
+                Token::from_str(9, 3, "bR\"\"\"x\"\"\""),
+                Token::from_str(9, 12, ","),
+                Token::from_str(10, 3, "r"),
+                Token::from_str(10, 5, "'xxx'"),
             ],
         )),
         Box::new(ErrorTestCase::new(
@@ -243,6 +246,19 @@ SELECT 1, 01, 1.1, .1, 1.1e+1, 1.1E-1, .1e10",
                 Token::from_str(1, 40, ".1e10"),
             ],
         )),
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT 0x1F, 0XaBc, 0x",
+            vec![
+                Token::from_str(1, 1, "SELECT"),
+                Token::from_str(1, 8, "0x1F"),
+                Token::from_str(1, 12, ","),
+                Token::from_str(1, 14, "0XaBc"),
+                Token::from_str(1, 19, ","),
+                Token::from_str(1, 21, "0"),
+                Token::from_str(1, 22, "x"),
+            ],
+        )),
         // timestamp, date literal
         Box::new(SuccessTestCase::new(
             "\
@@ -347,6 +363,32 @@ SELECT {{variable}};",
                 Token::from_str(1, 20, ";"),
             ],
         )),
+        // jinja
+        Box::new(SuccessTestCase::new(
+            "\
+{# comment {{x}} #}
+{% set d = {'a': 1} %}
+SELECT {%- if x -%} a {% elif y %} b {% else %}
+  c
+{% endif %}
+{% macro m() %}{% if z %}{% endif %}{% endmacro %}",
+            vec![
+                Token::from_str(1, 1, "{# comment {{x}} #}"),
+                Token::from_str(2, 1, "{% set d = {'a': 1} %}"),
+                Token::from_str(3, 1, "SELECT"),
+                Token::from_str(3, 8, "{%- if x -%}"),
+                Token::from_str(3, 21, "a"),
+                Token::from_str(3, 23, "{% elif y %} b {% else %}\n  c"),
+                Token::from_str(5, 1, "{% endif %}"),
+                Token::from_str(6, 1, "{% macro m() %}{% if z %}{% endif %}{% endmacro %}"),
+            ],
+        )),
+        Box::new(ErrorTestCase::new(
+            "\
+SELECT {% if x %} 1 {% else %} 2",
+            1,
+            33, // unclosed block
+        )),
         // operator
         Box::new(SuccessTestCase::new(
             "\
@@ -440,6 +482,47 @@ MATCH_RECOGNIZE (
                 Token::from_str(4, 1, ")"),
             ],
         )),
+        // graph
+        Box::new(SuccessTestCase::new(
+            "(a {id: 1})-[e]->(b)<-[f]-(c)->(d), arr[0]-[1]",
+            vec![
+                // `{id: 1}` and `-[` are handled by the parser
+                Token::from_str(1, 1, "("),
+                Token::from_str(1, 2, "a"),
+                Token::from_str(1, 4, "{id: 1}"),
+                Token::from_str(1, 11, ")"),
+                Token::from_str(1, 12, "-"),
+                Token::from_str(1, 13, "["),
+                Token::from_str(1, 14, "e"),
+                Token::from_str(1, 15, "]"),
+                Token::from_str(1, 16, "->"),
+                Token::from_str(1, 18, "("),
+                Token::from_str(1, 19, "b"),
+                Token::from_str(1, 20, ")"),
+                Token::from_str(1, 21, "<"),
+                Token::from_str(1, 22, "-"),
+                Token::from_str(1, 23, "["),
+                Token::from_str(1, 24, "f"),
+                Token::from_str(1, 25, "]"),
+                Token::from_str(1, 26, "-"),
+                Token::from_str(1, 27, "("),
+                Token::from_str(1, 28, "c"),
+                Token::from_str(1, 29, ")"),
+                Token::from_str(1, 30, "->"),
+                Token::from_str(1, 32, "("),
+                Token::from_str(1, 33, "d"),
+                Token::from_str(1, 34, ")"),
+                Token::from_str(1, 35, ","),
+                Token::from_str(1, 37, "arr"),
+                Token::from_str(1, 40, "["),
+                Token::from_str(1, 41, "0"),
+                Token::from_str(1, 42, "]"),
+                Token::from_str(1, 43, "-"),
+                Token::from_str(1, 44, "["),
+                Token::from_str(1, 45, "1"),
+                Token::from_str(1, 46, "]"),
+            ],
+        )),
         // empty
         Box::new(SuccessTestCase::new("", vec![])),
     ];
@@ -447,3 +530,33 @@ MATCH_RECOGNIZE (
         t.test();
     }
 }
+
+#[test]
+fn test_tokenize_sqlx() {
+    let code = "\
+config { type: \"table\", description: '}' }
+js {
+  const x = `{`; // }
+  /* { */
+}
+SELECT ${ref(\"t\")}, $1, config
+";
+    let l = Lexer::new_sqlx(code.to_string());
+    let tokens = l.tokenize_code().expect("Failed to tokenize code.");
+    let expected_tokens = vec![
+        Token::from_str(1, 1, "config { type: \"table\", description: '}' }"),
+        Token::from_str(2, 1, "js {\n  const x = `{`; // }\n  /* { */\n}"),
+        Token::from_str(6, 1, "SELECT"),
+        Token::from_str(6, 8, "${ref(\"t\")}"),
+        Token::from_str(6, 19, ","),
+        Token::from_str(6, 21, "$1"),
+        Token::from_str(6, 23, ","),
+        Token::from_str(6, 25, "config"),
+        Token::eof(),
+    ];
+    assert_eq!(expected_tokens, tokens);
+    // `${` is not special outside SQLX mode
+    let l = Lexer::new("SELECT ${x}".to_string());
+    let tokens = l.tokenize_code().expect("Failed to tokenize code.");
+    assert_eq!(tokens[1], Token::from_str(1, 8, "$"));
+}
@@ -1,59 +1,257 @@
 // https://github.com/rustwasm/wasm-bindgen/issues/2882
 #![allow(non_upper_case_globals)]
 
+mod argcheck;
+mod bqtype;
+mod builtins;
+mod catalog;
 mod constants;
+mod controlflow;
 mod cst;
+mod dependency;
+mod directive;
+mod docs;
 mod error;
+mod fingerprint;
+mod jinja;
 mod lexer;
+mod literal;
+mod macros;
+mod numeric;
+mod params;
 mod parser;
+mod privacy;
 mod token;
+mod typecheck;
+mod typed_literal;
 mod types;
+mod udf;
 mod utils;
+mod validate;
+mod variables;
 
+use error::BQ2CSTResult;
 use serde::Serialize;
 use serde_wasm_bindgen::Serializer;
 use wasm_bindgen::prelude::*;
 
+/// Tokenizes and parses the code, then serializes the result of `f`.
+/// The error of tokenizing or parsing is serialized instead.
+fn with_stmts<T: Serialize>(
+    code: String,
+    f: impl FnOnce(Vec<cst::Node>) -> T,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    to_js(parse_code(code).map(f))
+}
+
+fn parse_code(code: String) -> BQ2CSTResult<Vec<cst::Node>> {
+    let tokens = lexer::Lexer::new(code).tokenize_code()?;
+    parser::Parser::new(tokens).parse_code()
+}
+
+fn to_js<T: Serialize>(res: BQ2CSTResult<T>) -> Result<JsValue, JsValue> {
+    let s = Serializer::json_compatible();
+    match res {
+        Ok(value) => Ok(value
+            .serialize(&s)
+            .expect("Problem converting result to json.")),
+        Err(bq2cst_error) => Err(bq2cst_error
+            .serialize(&s)
+            .expect("Problem converting error struct to json.")),
+    }
+}
+
 #[wasm_bindgen(skip_typescript)]
 pub fn parse(code: String) -> Result<JsValue, JsValue> {
+    with_stmts(code, |stmts| stmts)
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn parse_sqlx(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let l = lexer::Lexer::new_sqlx(code);
+    to_js(
+        l.tokenize_code()
+            .and_then(|tokens| parser::Parser::new(tokens).parse_code()),
+    )
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn parse_with_dynamic_sql(code: String) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
     let l = lexer::Lexer::new(code);
-    let s = Serializer::json_compatible();
-    let mut p = parser::Parser::new(match l.tokenize_code() {
-        Ok(tokens) => tokens,
-        Err(bq2cst_error) => {
-            return Err(bq2cst_error
-                .serialize(&s)
-                .expect("Problem converting error struct to json."))
-        }
-    });
-    let stmts = match p.parse_code() {
-        Ok(stmts) => stmts,
-        Err(bq2cst_error) => {
-            return Err(bq2cst_error
-                .serialize(&s)
-                .expect("Problem converting error struct to json."))
-        }
-    };
-    Ok(stmts
-        .serialize(&s)
-        .expect("Problem converting stmts to json."))
+    to_js(
+        l.tokenize_code()
+            .and_then(|tokens| parser::Parser::new_dynamic_sql(tokens).parse_code()),
+    )
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn parse_jinja_branches(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    to_js(jinja::parse_branches(&code))
 }
 
 #[wasm_bindgen(skip_typescript)]
 pub fn tokenize(code: String) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
-    let l = lexer::Lexer::new(code);
+    to_js(lexer::Lexer::new(code).tokenize_code())
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn extract_docs(code: String) -> Result<JsValue, JsValue> {
+    with_stmts(code, |stmts| docs::extract_docs(&stmts))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn get_suppressions(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    to_js(lexer::Lexer::new(code).tokenize_code().and_then(|tokens| {
+        let stmts = parser::Parser::new(tokens.clone()).parse_code()?;
+        Ok(directive::resolve_suppressions(&tokens, &stmts))
+    }))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn decode_literal(literal: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let token = token::Token::new(1, 1, literal);
+    to_js(literal::decode_string_literal(&token))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn expand_macros(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    to_js(macros::expand_macros(&code))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn collect_parameters(code: String) -> Result<JsValue, JsValue> {
+    with_stmts(code, |stmts| params::collect_parameters(&stmts))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn analyze_privacy(code: String) -> Result<JsValue, JsValue> {
+    with_stmts(code, |stmts| privacy::analyze_privacy(&stmts))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn extract_functions(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    to_js(parse_code(code).and_then(|stmts| udf::extract_functions(&stmts)))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn build_catalog(code: String) -> Result<JsValue, JsValue> {
+    with_stmts(code, |stmts| catalog::build_catalog(&stmts))
+}
+
+// the catalog built from the code, to which `schemas` are added
+fn catalog_with_schemas(
+    stmts: &[cst::Node],
+    schemas: Option<std::collections::BTreeMap<String, Vec<catalog::TableFieldSchema>>>,
+) -> catalog::Catalog {
+    let mut catalog = catalog::build_catalog(stmts);
+    for (name, schema) in schemas.unwrap_or_default() {
+        catalog.add_table(&name, schema);
+    }
+    catalog
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn validate_columns(code: String, schemas: JsValue) -> Result<JsValue, JsValue> {
+    let schemas = serde_wasm_bindgen::from_value(schemas)?;
+    with_stmts(code, |stmts| {
+        validate::validate_columns(&stmts, &catalog_with_schemas(&stmts, schemas))
+    })
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn infer_types(code: String, schemas: JsValue) -> Result<JsValue, JsValue> {
+    let schemas = serde_wasm_bindgen::from_value(schemas)?;
+    with_stmts(code, |stmts| {
+        typecheck::infer_types(&stmts, &catalog_with_schemas(&stmts, schemas))
+    })
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn check_functions(code: String) -> Result<JsValue, JsValue> {
+    with_stmts(code, |stmts| builtins::check_functions(&stmts))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn check_arguments(code: String) -> Result<JsValue, JsValue> {
+    with_stmts(code, |stmts| argcheck::check_arguments(&stmts))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn check_typed_literals(code: String) -> Result<JsValue, JsValue> {
+    with_stmts(code, |stmts| typed_literal::check_typed_literals(&stmts))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn analyze_control_flow(code: String) -> Result<JsValue, JsValue> {
+    with_stmts(code, |stmts| controlflow::analyze_control_flow(&stmts))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn check_variables(code: String) -> Result<JsValue, JsValue> {
+    with_stmts(code, |stmts| variables::check_variables(&stmts))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn build_dependency_graph(files: JsValue) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
     let s = Serializer::json_compatible();
-    let tokens = match l.tokenize_code() {
-        Ok(tokens) => tokens,
-        Err(bq2cst_error) => {
-            return Err(bq2cst_error
-                .serialize(&s)
-                .expect("Problem converting error struct to json."))
+    let files: std::collections::BTreeMap<String, String> = serde_wasm_bindgen::from_value(files)?;
+    let mut parsed = Vec::new();
+    for (file, code) in files {
+        match parse_code(code) {
+            Ok(stmts) => parsed.push((file, stmts)),
+            Err(error) => {
+                return Err(dependency::FileError { file, error }
+                    .serialize(&s)
+                    .expect("Problem converting error struct to json."))
+            }
         }
-    };
-    Ok(tokens
+    }
+    Ok(dependency::build_dependency_graph(&parsed)
         .serialize(&s)
-        .expect("Problem converting tokens to json."))
+        .expect("Problem converting dependency graph to json."))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn fingerprint_query(code: String) -> Result<JsValue, JsValue> {
+    with_stmts(code, |stmts| fingerprint::fingerprint_query(&stmts))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn numeric_value(node: JsValue) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let s = Serializer::json_compatible();
+    let node: cst::Node = serde_wasm_bindgen::from_value(node)?;
+    match numeric::numeric_value(&node) {
+        Some(Ok(value)) => Ok(value
+            .serialize(&s)
+            .expect("Problem converting numeric value to json.")),
+        Some(Err(bq2cst_error)) => Err(bq2cst_error
+            .serialize(&s)
+            .expect("Problem converting error struct to json.")),
+        None => Ok(JsValue::NULL),
+    }
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn canonical_type(node: JsValue) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let s = Serializer::json_compatible();
+    let node: cst::Node = serde_wasm_bindgen::from_value(node)?;
+    match bqtype::BqType::from_type(&node) {
+        Some(type_) => Ok(type_
+            .to_type()
+            .serialize(&s)
+            .expect("Problem converting type to json.")),
+        None => Ok(JsValue::NULL),
+    }
 }
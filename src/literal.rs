@@ -0,0 +1,191 @@
+#[cfg(test)]
+mod tests;
+
+use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::token::Token;
+use serde::Serialize;
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "value")]
+pub enum LiteralValue {
+    #[serde(rename = "STRING")]
+    String(String),
+    #[serde(rename = "BYTES")]
+    Bytes(Vec<u8>),
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct DecodedLiteral {
+    pub value: LiteralValue,
+    // (line, column) of each character (STRING) or byte (BYTES) in the source
+    pub positions: Vec<(usize, usize)>,
+}
+
+/// Decodes a (possibly prefixed) string literal token.
+/// Escape sequences are interpreted unless the literal is raw.
+pub fn decode_string_literal(token: &Token) -> BQ2CSTResult<DecodedLiteral> {
+    if !token.is_string() {
+        return Err(BQ2CSTError::from_token(
+            token,
+            format!("{} is not a string literal.", token.literal),
+        ));
+    }
+    let chars: Vec<char> = token.literal.chars().collect();
+    let prefix: String = chars
+        .iter()
+        .take_while(|c| **c != '\'' && **c != '"')
+        .collect::<String>()
+        .to_uppercase();
+    let raw = prefix.contains('R');
+    let bytes = prefix.contains('B');
+    let quote = chars[prefix.len()];
+    let quote_len = if 6 <= chars.len() - prefix.len()
+        && chars[prefix.len() + 1] == quote
+        && chars[prefix.len() + 2] == quote
+    {
+        3
+    } else {
+        1
+    };
+    let body = &chars[prefix.len() + quote_len..chars.len() - quote_len];
+    let mut line = token.line;
+    let mut column = token.column + prefix.len() + quote_len;
+    let mut positions = Vec::new(); // the position of each char in body
+    for ch in body {
+        positions.push((line, column));
+        if *ch == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+
+    let mut buf: Vec<u8> = Vec::new();
+    let mut buf_positions = Vec::new(); // the position of each byte in buf
+    let mut i = 0;
+    while i < body.len() {
+        let pos = positions[i];
+        let ch = body[i];
+        if raw || ch != '\\' {
+            push_char(&mut buf, &mut buf_positions, ch, pos);
+            i += 1;
+            continue;
+        }
+        let error = |message: &str| Err(BQ2CSTError::new(pos.0, pos.1, message.to_string()));
+        let escaped = match body.get(i + 1) {
+            Some(c) => *c,
+            None => return error("Illegal escape sequence: trailing backslash."),
+        };
+        match escaped {
+            'a' | 'b' | 'f' | 'n' | 'r' | 't' | 'v' | '\\' | '?' | '"' | '\'' | '`' => {
+                let decoded = match escaped {
+                    'a' => '\x07',
+                    'b' => '\x08',
+                    'f' => '\x0C',
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'v' => '\x0B',
+                    c => c,
+                };
+                push_char(&mut buf, &mut buf_positions, decoded, pos);
+                i += 2;
+            }
+            '0'..='7' => {
+                let digits: String = body[i + 1..].iter().take(3).collect();
+                if digits.len() != 3 || !digits.chars().all(|c| c.is_digit(8)) {
+                    return error(
+                        "Illegal escape sequence: octal escape must be followed by 3 octal digits.",
+                    );
+                }
+                match u8::from_str_radix(&digits, 8) {
+                    Ok(b) => {
+                        buf.push(b);
+                        buf_positions.push(pos);
+                    }
+                    Err(_) => {
+                        return error("Illegal escape sequence: octal value out of range.");
+                    }
+                }
+                i += 4;
+            }
+            'x' | 'X' => {
+                let digits: String = body[i + 2..].iter().take(2).collect();
+                if digits.len() != 2 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return error(
+                        "Illegal escape sequence: hex escape must be followed by 2 hex digits.",
+                    );
+                }
+                // unwrap is safe because of the check above
+                buf.push(u8::from_str_radix(&digits, 16).unwrap());
+                buf_positions.push(pos);
+                i += 4;
+            }
+            'u' | 'U' => {
+                if bytes {
+                    return error(
+                        "Illegal escape sequence: unicode escape is not allowed in bytes literals.",
+                    );
+                }
+                let len = if escaped == 'u' { 4 } else { 8 };
+                let digits: String = body[i + 2..].iter().take(len).collect();
+                if digits.len() != len || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return error(&format!(
+                        "Illegal escape sequence: \\{} must be followed by {} hex digits.",
+                        escaped, len
+                    ));
+                }
+                let decoded = match u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                {
+                    Some(c) => c,
+                    None => return error("Illegal escape sequence: invalid code point."),
+                };
+                push_char(&mut buf, &mut buf_positions, decoded, pos);
+                i += 2 + len;
+            }
+            c => return error(&format!("Illegal escape sequence: \\{}", c)),
+        }
+    }
+
+    if bytes {
+        return Ok(DecodedLiteral {
+            value: LiteralValue::Bytes(buf),
+            positions: buf_positions,
+        });
+    }
+    let string = match String::from_utf8(buf) {
+        Ok(s) => s,
+        Err(e) => {
+            let pos = buf_positions[e.utf8_error().valid_up_to()];
+            return Err(BQ2CSTError::new(
+                pos.0,
+                pos.1,
+                "String literal is not valid UTF-8.".to_string(),
+            ));
+        }
+    };
+    let char_positions = string
+        .char_indices()
+        .map(|(idx, _)| buf_positions[idx])
+        .collect();
+    Ok(DecodedLiteral {
+        value: LiteralValue::String(string),
+        positions: char_positions,
+    })
+}
+
+fn push_char(
+    buf: &mut Vec<u8>,
+    positions: &mut Vec<(usize, usize)>,
+    ch: char,
+    pos: (usize, usize),
+) {
+    let mut tmp = [0; 4];
+    for b in ch.encode_utf8(&mut tmp).bytes() {
+        buf.push(b);
+        positions.push(pos);
+    }
+}
@@ -0,0 +1,78 @@
+use super::*;
+
+fn decode(literal: &str) -> BQ2CSTResult<DecodedLiteral> {
+    decode_string_literal(&Token::from_str(1, 1, literal))
+}
+
+fn string(literal: &str) -> String {
+    match decode(literal).expect("Failed to decode literal.").value {
+        LiteralValue::String(s) => s,
+        LiteralValue::Bytes(_) => panic!("Unexpectedly decoded as bytes."),
+    }
+}
+
+fn bytes(literal: &str) -> Vec<u8> {
+    match decode(literal).expect("Failed to decode literal.").value {
+        LiteralValue::Bytes(b) => b,
+        LiteralValue::String(_) => panic!("Unexpectedly decoded as string."),
+    }
+}
+
+#[test]
+fn test_decode_string() {
+    assert_eq!(string("'abc'"), "abc");
+    assert_eq!(string("\"it's\""), "it's");
+    assert_eq!(string("'''a\nb'''"), "a\nb");
+    assert_eq!(string("'\\n\\t\\\\\\'\\`\\?'"), "\n\t\\'`?");
+    assert_eq!(string("'\\x41\\X42\\103'"), "ABC");
+    assert_eq!(string("'\\u00e9\\U0001F600'"), "é\u{1F600}");
+    assert_eq!(string("'\\xc3\\xa9'"), "é"); // UTF-8 bytes
+}
+
+#[test]
+fn test_decode_raw_string() {
+    assert_eq!(string("r'\\n'"), "\\n");
+    assert_eq!(string("R'''\\x41'''"), "\\x41");
+    assert_eq!(string("r'\\''"), "\\'");
+}
+
+#[test]
+fn test_decode_bytes() {
+    assert_eq!(bytes("b'abc'"), vec![97, 98, 99]);
+    assert_eq!(bytes("B'\\xff\\000'"), vec![255, 0]);
+    assert_eq!(bytes("b'é'"), vec![0xc3, 0xa9]);
+    assert_eq!(bytes("rb'\\x'"), vec![b'\\', b'x']);
+    assert_eq!(bytes("bR\"\"\"\\n\"\"\""), vec![b'\\', b'n']);
+}
+
+#[test]
+fn test_positions() {
+    let decoded = decode_string_literal(&Token::from_str(2, 5, "'''a\\n\nb'''")).unwrap();
+    assert_eq!(decoded.positions, vec![(2, 8), (2, 9), (2, 11), (3, 1)]);
+    let decoded = decode_string_literal(&Token::from_str(1, 1, "rb'é'")).unwrap();
+    assert_eq!(decoded.positions, vec![(1, 4), (1, 4)]);
+}
+
+#[test]
+fn test_invalid_escape() {
+    let cases = vec![
+        ("'\\q'", 1, 2),
+        ("'ab\\x4'", 1, 4),
+        ("'\\8'", 1, 2),
+        ("'\\400'", 1, 2),
+        ("'\\u12'", 1, 2),
+        ("'\\UFFFFFFFF'", 1, 2),
+        ("'\\uD800'", 1, 2),
+        ("b'\\u0041'", 1, 3),
+        ("'''\n  \\c'''", 2, 3),
+        ("'\\xff'", 1, 2), // invalid UTF-8
+        ("x", 1, 1),       // not a string
+    ];
+    for (literal, line, column) in cases {
+        let error = match decode(literal) {
+            Ok(d) => panic!("Unexpectedly decoded {}: {:?}", literal, d),
+            Err(e) => e,
+        };
+        assert_eq!((error.line, error.column), (line, column), "{}", literal);
+    }
+}
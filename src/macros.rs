@@ -0,0 +1,225 @@
+#[cfg(test)]
+mod tests;
+
+use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::lexer::Lexer;
+use crate::token::Token;
+use serde::Serialize;
+use std::collections::HashMap;
+
+const MAX_DEPTH: usize = 64;
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct MacroExpansion {
+    pub code: String,
+    // (line, column) in the original code of each character of the expanded code
+    pub positions: Vec<(usize, usize)>,
+}
+
+/// Removes `DEFINE MACRO` statements and substitutes `$name(args)` invocations.
+/// `$1`, `$2`, ... in the body of a macro are replaced with its arguments.
+pub fn expand_macros(code: &str) -> BQ2CSTResult<MacroExpansion> {
+    let tokens: Vec<Token> = Lexer::new(code.to_string())
+        .tokenize_code()?
+        .into_iter()
+        .filter(|t| t.line != usize::MAX) // EOF
+        .collect();
+    let mut expander = Expander::new(code);
+
+    // collect definitions
+    let mut segments = vec![]; // tokens except for definitions
+    let mut removed = vec![]; // char ranges of definitions
+    let mut segment_start = 0;
+    let mut at_start = true; // at the start of a statement
+    let mut i = 0;
+    while i < tokens.len() {
+        if !(at_start
+            && tokens[i].is("DEFINE")
+            && i + 2 < tokens.len()
+            && tokens[i + 1].is("MACRO"))
+        {
+            if !tokens[i].is_comment() {
+                at_start = tokens[i].is(";");
+            }
+            i += 1;
+            continue;
+        }
+        let mut end = i + 3; // index of `;` (or tokens.len())
+        while end < tokens.len() && !tokens[end].is(";") {
+            end += 1;
+        }
+        expander
+            .macros
+            .insert(tokens[i + 2].literal.clone(), tokens[i + 3..end].to_vec());
+        let last = &tokens[end.min(tokens.len() - 1)];
+        let mut range_end = expander.end(last);
+        if expander.chars.get(range_end) == Some(&'\n') {
+            range_end += 1;
+        }
+        segments.push(&tokens[segment_start..i]);
+        removed.push((expander.offset(&tokens[i]), range_end));
+        segment_start = end + 1;
+        i = end + 1;
+    }
+    if segment_start < tokens.len() {
+        segments.push(&tokens[segment_start..]);
+    }
+
+    // expand
+    let mut out = vec![];
+    let mut cursor = 0;
+    for (idx, segment) in segments.iter().enumerate() {
+        if let Some(first) = segment.first() {
+            expander.copy(cursor, expander.offset(first), &mut out);
+            expander.expand(segment, &[], 0, &mut out)?;
+            cursor = expander.end(segment.last().unwrap());
+        }
+        if let Some((start, end)) = removed.get(idx) {
+            expander.copy(cursor, *start, &mut out);
+            cursor = *end;
+        }
+    }
+    expander.copy(cursor, expander.chars.len(), &mut out);
+    Ok(MacroExpansion {
+        code: out.iter().map(|(ch, _)| ch).collect(),
+        positions: out.into_iter().map(|(_, pos)| pos).collect(),
+    })
+}
+
+type Expanded = Vec<(char, (usize, usize))>;
+
+struct Expander {
+    chars: Vec<char>,
+    positions: Vec<(usize, usize)>, // (line, column) of each char
+    line_starts: Vec<usize>,        // offset of the first char of each line
+    macros: HashMap<String, Vec<Token>>,
+}
+
+impl Expander {
+    fn new(code: &str) -> Expander {
+        let chars: Vec<char> = code.chars().collect();
+        let mut positions = Vec::new();
+        let mut line_starts = vec![0];
+        let (mut line, mut column) = (1, 1);
+        for (i, ch) in chars.iter().enumerate() {
+            positions.push((line, column));
+            if *ch == '\n' {
+                line += 1;
+                column = 1;
+                line_starts.push(i + 1);
+            } else {
+                column += 1;
+            }
+        }
+        Expander {
+            chars,
+            positions,
+            line_starts,
+            macros: HashMap::new(),
+        }
+    }
+    fn offset(&self, token: &Token) -> usize {
+        self.line_starts[token.line - 1] + token.column - 1
+    }
+    fn end(&self, token: &Token) -> usize {
+        self.offset(token) + token.literal.chars().count()
+    }
+    fn copy(&self, start: usize, end: usize, out: &mut Expanded) {
+        for i in start..end {
+            out.push((self.chars[i], self.positions[i]));
+        }
+    }
+    fn expand(
+        &self,
+        tokens: &[Token],
+        args: &[Expanded],
+        depth: usize,
+        out: &mut Expanded,
+    ) -> BQ2CSTResult<()> {
+        let mut i = 0;
+        let mut prev_end = None; // the end of the previous token
+        while i < tokens.len() {
+            let token = &tokens[i];
+            if let Some(prev_end) = prev_end {
+                self.copy(prev_end, self.offset(token), out);
+            }
+            prev_end = Some(self.end(token));
+            if !token.is_macro() {
+                self.copy(self.offset(token), self.end(token), out);
+                i += 1;
+                continue;
+            }
+            let name = &token.literal[1..];
+            if let Ok(n) = name.parse::<usize>() {
+                // positional argument
+                if 0 < depth {
+                    if let Some(arg) = args.get(n.wrapping_sub(1)) {
+                        out.extend(arg.iter().cloned());
+                    }
+                } else {
+                    self.copy(self.offset(token), self.end(token), out);
+                }
+                i += 1;
+                continue;
+            }
+            let body = match self.macros.get(name) {
+                Some(body) => body,
+                None => {
+                    return Err(BQ2CSTError::from_token(
+                        token,
+                        format!("Macro {} is not defined.", name),
+                    ))
+                }
+            };
+            if MAX_DEPTH <= depth {
+                return Err(BQ2CSTError::from_token(
+                    token,
+                    "Macro expansion is too deep.".to_string(),
+                ));
+            }
+            // arguments
+            let mut macro_args = vec![];
+            let mut last = i;
+            if i + 1 < tokens.len()
+                && tokens[i + 1].is("(")
+                && self.offset(&tokens[i + 1]) == self.end(token)
+            {
+                let mut level = 0;
+                let mut arg_start = i + 2;
+                let mut j = i + 1;
+                loop {
+                    if tokens.len() <= j {
+                        return Err(BQ2CSTError::from_token(
+                            token,
+                            "Unclosed macro invocation.".to_string(),
+                        ));
+                    }
+                    let t = &tokens[j];
+                    if t.in_(&vec!["(", "["]) {
+                        level += 1;
+                    } else if t.in_(&vec![")", "]"]) {
+                        level -= 1;
+                    }
+                    if (level == 1 && t.is(",")) || level == 0 {
+                        let mut arg = vec![];
+                        self.expand(&tokens[arg_start..j], args, depth, &mut arg)?;
+                        macro_args.push(arg);
+                        arg_start = j + 1;
+                    }
+                    if level == 0 {
+                        break;
+                    }
+                    j += 1;
+                }
+                if macro_args.len() == 1 && macro_args[0].is_empty() {
+                    macro_args.clear(); // $name()
+                }
+                prev_end = Some(self.end(&tokens[j]));
+                last = j;
+            }
+            self.expand(body, &macro_args, depth + 1, out)?;
+            i = last + 1;
+        }
+        Ok(())
+    }
+}
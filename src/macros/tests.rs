@@ -0,0 +1,47 @@
+use super::*;
+
+fn expand(code: &str) -> MacroExpansion {
+    expand_macros(code).expect("Failed to expand macros.")
+}
+
+#[test]
+fn test_expand_macros() {
+    let res = expand(
+        "\
+DEFINE MACRO tbl `project.dataset.table`;
+DEFINE MACRO add $1 + $2;
+SELECT $add(x, $add(1, 2)), '$1' FROM $tbl();
+",
+    );
+    assert_eq!(
+        res.code,
+        "SELECT x + 1 + 2, '$1' FROM `project.dataset.table`;\n"
+    );
+    assert_eq!(res.positions.len(), res.code.chars().count());
+    // `SELECT` is copied from the original code
+    assert_eq!(res.positions[0], (3, 1));
+    // `x` comes from the argument
+    assert_eq!(res.positions[7], (3, 13));
+    // `+` comes from the body of `add`
+    assert_eq!(res.positions[9], (2, 21));
+    // `1` comes from the argument of the nested invocation
+    assert_eq!(res.positions[11], (3, 21));
+    // the table name comes from the body of `tbl`
+    assert_eq!(res.positions[28], (1, 18));
+}
+
+#[test]
+fn test_expand_macros_without_definitions() {
+    let code = "SELECT 1; -- $x is not expanded in comments\n";
+    assert_eq!(expand(code).code, code);
+    let code = "SELECT $1;\n"; // positional argument outside of macro
+    assert_eq!(expand(code).code, code);
+}
+
+#[test]
+fn test_expand_macros_error() {
+    let e = expand_macros("SELECT $undefined;").unwrap_err();
+    assert_eq!((e.line, e.column), (1, 8));
+    let e = expand_macros("DEFINE MACRO loop $loop;\nSELECT $loop;").unwrap_err();
+    assert_eq!((e.line, e.column), (1, 19));
+}
@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::Node;
+use crate::cst::NodeType;
+use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::literal::{decode_string_literal, LiteralValue};
+use crate::token::Token;
+use serde::{Serialize, Serializer};
+
+// the largest integer part of BIGNUMERIC (the precision is 76.76 digits)
+const BIGNUMERIC_MAX_INTEGER_PART: &str = "578960446186580977117854925043439539266";
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "value")]
+pub enum NumericValue {
+    // INT64 is serialized as string because JavaScript number cannot represent it
+    #[serde(rename = "INT64", serialize_with = "serialize_as_string")]
+    Int64(i64),
+    #[serde(rename = "FLOAT64")]
+    Float64(f64),
+    #[serde(rename = "NUMERIC")]
+    Numeric(String),
+    #[serde(rename = "BIGNUMERIC")]
+    BigNumeric(String),
+}
+
+fn serialize_as_string<S: Serializer>(value: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
+/// Returns the value of numeric literals.
+/// `NumericLiteral` is INT64 (`1`, `0x1F`) or FLOAT64 (`1.0`, `1e3`).
+/// `-1` and `NUMERIC '1.0'` (`UnaryOperator`) are also supported.
+/// Returns `None` if the node is not a numeric literal.
+pub fn numeric_value(node: &Node) -> Option<BQ2CSTResult<NumericValue>> {
+    let token = node.token.as_ref()?;
+    match node.node_type {
+        NodeType::NumericLiteral => Some(parse_number(token, false)),
+        NodeType::UnaryOperator => {
+            let right = node.get_node("right")?;
+            match token.literal.to_uppercase().as_str() {
+                "-" if right.node_type == NodeType::NumericLiteral => {
+                    Some(parse_number(right.token.as_ref()?, true))
+                }
+                "NUMERIC" | "DECIMAL" | "BIGNUMERIC" | "BIGDECIMAL"
+                    if right.node_type == NodeType::StringLiteral =>
+                {
+                    let big = token.literal.to_uppercase().starts_with("BIG");
+                    Some(parse_numeric_string(right.token.as_ref()?, big))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn parse_number(token: &Token, negative: bool) -> BQ2CSTResult<NumericValue> {
+    let literal = token.literal.as_str();
+    let (digits, radix) = match literal
+        .strip_prefix("0x")
+        .or_else(|| literal.strip_prefix("0X"))
+    {
+        Some(hex) => (hex, 16),
+        None => (literal, 10),
+    };
+    if radix == 16 || digits.chars().all(|c| c.is_ascii_digit()) {
+        let value = match i128::from_str_radix(digits, radix) {
+            Ok(v) => Some(if negative { -v } else { v }),
+            Err(_) => None, // too many digits
+        };
+        return match value.and_then(|v| i64::try_from(v).ok()) {
+            Some(v) => Ok(NumericValue::Int64(v)),
+            None => Err(BQ2CSTError::from_token(
+                token,
+                format!(
+                    "Invalid integer literal: {} is out of range for INT64.",
+                    literal
+                ),
+            )),
+        };
+    }
+    match literal.parse::<f64>() {
+        Ok(v) if v.is_finite() => Ok(NumericValue::Float64(if negative { -v } else { v })),
+        _ => Err(BQ2CSTError::from_token(
+            token,
+            format!("Invalid floating point literal: {}", literal),
+        )),
+    }
+}
+
+fn parse_numeric_string(token: &Token, big: bool) -> BQ2CSTResult<NumericValue> {
+    let type_ = if big { "BIGNUMERIC" } else { "NUMERIC" };
+    let error = || {
+        Err(BQ2CSTError::from_token(
+            token,
+            format!("Invalid {} literal: {}", type_, token.literal),
+        ))
+    };
+    let value = match decode_string_literal(token)?.value {
+        LiteralValue::String(s) => s,
+        LiteralValue::Bytes(_) => return error(),
+    };
+    let value = value.trim();
+    let (sign, unsigned) = match value.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(idx) => match unsigned[idx + 1..].parse::<i64>() {
+            Ok(exp) => (&unsigned[..idx], exp),
+            Err(_) => return error(),
+        },
+        None => (unsigned, 0),
+    };
+    let (int_part, frac_part) = match mantissa.split_once('.') {
+        Some((i, f)) => (i, f),
+        None => (mantissa, ""),
+    };
+    if int_part.is_empty() && frac_part.is_empty()
+        || !int_part.chars().all(|c| c.is_ascii_digit())
+        || !frac_part.chars().all(|c| c.is_ascii_digit())
+    {
+        return error();
+    }
+    // move the decimal point according to the exponent
+    let digits = format!("{}{}", int_part, frac_part);
+    let point = int_part.len() as i64 + exponent;
+    if (digits.len() as i64) < point - 100 {
+        // obviously out of range
+        return Err(BQ2CSTError::from_token(
+            token,
+            format!(
+                "Invalid {} literal: {} is out of range.",
+                type_, token.literal
+            ),
+        ));
+    }
+    let (int_part, frac_part) = if point < -100 {
+        ("".to_string(), "".to_string()) // too small to be represented
+    } else if point <= 0 {
+        (
+            "".to_string(),
+            format!("{}{}", "0".repeat(-point as usize), digits),
+        )
+    } else if (digits.len() as i64) <= point {
+        (
+            format!("{}{}", digits, "0".repeat(point as usize - digits.len())),
+            "".to_string(),
+        )
+    } else {
+        (
+            digits[..point as usize].to_string(),
+            digits[point as usize..].to_string(),
+        )
+    };
+    let int_part = match int_part.trim_start_matches('0') {
+        "" => "0",
+        s => s,
+    };
+    let frac_part = frac_part.trim_end_matches('0');
+    let in_range = if big {
+        int_part.len() < BIGNUMERIC_MAX_INTEGER_PART.len()
+            || int_part.len() == BIGNUMERIC_MAX_INTEGER_PART.len()
+                && int_part <= BIGNUMERIC_MAX_INTEGER_PART
+    } else {
+        int_part.len() <= 29
+    };
+    if !in_range {
+        return Err(BQ2CSTError::from_token(
+            token,
+            format!(
+                "Invalid {} literal: {} is out of range.",
+                type_, token.literal
+            ),
+        ));
+    }
+    let sign = if int_part == "0" && frac_part.is_empty() {
+        ""
+    } else {
+        sign
+    };
+    let normalized = if frac_part.is_empty() {
+        format!("{}{}", sign, int_part)
+    } else {
+        format!("{}{}.{}", sign, int_part, frac_part)
+    };
+    if big {
+        Ok(NumericValue::BigNumeric(normalized))
+    } else {
+        Ok(NumericValue::Numeric(normalized))
+    }
+}
@@ -0,0 +1,94 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::validate::tests::parse;
+
+// returns the value of the 1st expression of `SELECT expr`
+fn value(expr: &str) -> BQ2CSTResult<NumericValue> {
+    let stmts = parse(&format!("SELECT {}", expr));
+    let exprs = stmts[0].get_node_vec("exprs").unwrap();
+    numeric_value(&exprs[0]).expect("Not a numeric literal.")
+}
+
+#[test]
+fn test_int64() {
+    assert_eq!(value("1").unwrap(), NumericValue::Int64(1));
+    assert_eq!(value("0x1F").unwrap(), NumericValue::Int64(31));
+    assert_eq!(value("-0X1f").unwrap(), NumericValue::Int64(-31));
+    assert_eq!(
+        value("9223372036854775807").unwrap(),
+        NumericValue::Int64(i64::MAX)
+    );
+    assert_eq!(
+        value("-9223372036854775808").unwrap(),
+        NumericValue::Int64(i64::MIN)
+    );
+    assert_eq!(
+        value("0x7FFFFFFFFFFFFFFF").unwrap(),
+        NumericValue::Int64(i64::MAX)
+    );
+}
+
+#[test]
+fn test_int64_out_of_range() {
+    for expr in vec![
+        "9223372036854775808",
+        "-9223372036854775809",
+        "0x8000000000000000",
+        "123456789012345678901234567890123456789012345678901234567890",
+    ] {
+        let error = match value(expr) {
+            Ok(v) => panic!("Unexpectedly succeeded: {:?}", v),
+            Err(e) => e,
+        };
+        // the position of the digits (after `-`)
+        let column = if expr.starts_with('-') { 9 } else { 8 };
+        assert_eq!((error.line, error.column), (1, column));
+    }
+}
+
+#[test]
+fn test_float64() {
+    assert_eq!(value("1.5").unwrap(), NumericValue::Float64(1.5));
+    assert_eq!(value(".5e1").unwrap(), NumericValue::Float64(5.0));
+    assert_eq!(value("-1E-1").unwrap(), NumericValue::Float64(-0.1));
+    assert!(value("1e999").is_err());
+}
+
+#[test]
+fn test_numeric() {
+    assert_eq!(
+        value("NUMERIC '1.50'").unwrap(),
+        NumericValue::Numeric("1.5".to_string())
+    );
+    assert_eq!(
+        value("DECIMAL \"-0012.3e2\"").unwrap(),
+        NumericValue::Numeric("-1230".to_string())
+    );
+    assert_eq!(
+        value("NUMERIC '-0.0'").unwrap(),
+        NumericValue::Numeric("0".to_string())
+    );
+    assert_eq!(
+        value("NUMERIC ' 1e-3'").unwrap(),
+        NumericValue::Numeric("0.001".to_string())
+    );
+    assert_eq!(
+        value("BIGNUMERIC '578960446186580977117854925043439539266.3'").unwrap(),
+        NumericValue::BigNumeric("578960446186580977117854925043439539266.3".to_string())
+    );
+    assert!(value("NUMERIC '1e29'").is_err());
+    assert!(value("NUMERIC 'abc'").is_err());
+    assert!(value("NUMERIC '.'").is_err());
+    assert!(value("BIGDECIMAL '578960446186580977117854925043439539267'").is_err());
+}
+
+#[test]
+fn test_parse_out_of_range() {
+    // out-of-range literals are reported by `numeric_value()`, not by the parser
+    for code in vec!["SELECT 9223372036854775808", "SELECT 0xFFFFFFFFFFFFFFFF"] {
+        let l = Lexer::new(code.to_string());
+        let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."));
+        assert!(p.parse_code().is_ok(), "{}", code);
+    }
+}
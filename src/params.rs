@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{Node, NodeType};
+use serde::Serialize;
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub enum ParameterKind {
+    Named,          // @name
+    Positional,     // ?
+    SystemVariable, // @@name
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct ParameterUsage {
+    pub kind: ParameterKind,
+    pub name: Option<String>, // without `@` (or `@@`) and back quotes
+    pub index: Option<usize>, // 0-based index of `?`
+    pub context: String,      // e.g. LIMIT, IN UNNEST, WHERE
+    pub statement: usize,     // index of the statement
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct ParameterInventory {
+    pub named: Vec<String>, // in order of first appearance
+    pub positional_count: usize,
+    pub mixed: bool, // both named and positional parameters are used
+    pub system_variables: Vec<String>,
+    pub usages: Vec<ParameterUsage>,
+}
+
+/// Lists the query parameters and system variables used in the statements.
+pub fn collect_parameters(stmts: &[Node]) -> ParameterInventory {
+    let mut inventory = ParameterInventory {
+        named: Vec::new(),
+        positional_count: 0,
+        mixed: false,
+        system_variables: Vec::new(),
+        usages: Vec::new(),
+    };
+    for (idx, stmt) in stmts.iter().enumerate() {
+        let context = stmt.literal().to_uppercase();
+        walk(stmt, &context, idx, &mut inventory);
+    }
+    inventory.mixed = !inventory.named.is_empty() && 0 < inventory.positional_count;
+    inventory
+}
+
+fn walk(node: &Node, context: &str, statement: usize, inventory: &mut ParameterInventory) {
+    if node.node_type == NodeType::Parameter {
+        record(node, context, statement, inventory);
+    }
+    for (key, child) in node.child_nodes() {
+        let upper = node.literal().to_uppercase();
+        let child_context = match (&node.node_type, key) {
+            (NodeType::InOperator, "right") if child.node_type == NodeType::CallingUnnest => {
+                "IN UNNEST"
+            }
+            _ if child.node_type == NodeType::CallingUnnest => "UNNEST",
+            (NodeType::LimitClause, "expr") => "LIMIT",
+            (NodeType::KeywordWithExpr, "expr") => upper.as_str(),
+            (NodeType::SelectStatement, "exprs") => "SELECT",
+            (NodeType::SetStatement, "expr") => "SET",
+            _ => context,
+        };
+        walk(child, child_context, statement, inventory);
+    }
+}
+
+fn record(node: &Node, context: &str, statement: usize, inventory: &mut ParameterInventory) {
+    let literal = node.literal();
+    let (kind, name, index) = if literal == "?" {
+        inventory.positional_count += 1;
+        (
+            ParameterKind::Positional,
+            None,
+            Some(inventory.positional_count - 1),
+        )
+    } else if let Some(name) = literal.strip_prefix("@@") {
+        let name = name.trim_matches('`').to_string();
+        if !inventory.system_variables.contains(&name) {
+            inventory.system_variables.push(name.clone());
+        }
+        (ParameterKind::SystemVariable, Some(name), None)
+    } else {
+        let name = literal
+            .trim_start_matches('@')
+            .trim_matches('`')
+            .to_string();
+        if !inventory.named.contains(&name) {
+            inventory.named.push(name.clone());
+        }
+        (ParameterKind::Named, Some(name), None)
+    };
+    let token = node.token.as_ref().unwrap();
+    inventory.usages.push(ParameterUsage {
+        kind,
+        name,
+        index,
+        context: context.to_string(),
+        statement,
+        line: token.line,
+        column: token.column,
+    });
+}
@@ -0,0 +1,71 @@
+use super::*;
+use crate::validate::tests::parse;
+
+fn inventory(code: &str) -> ParameterInventory {
+    collect_parameters(&parse(code))
+}
+
+#[test]
+fn test_collect_parameters() {
+    let inv = inventory(
+        "\
+SET @@time_zone = 'Asia/Tokyo';
+SELECT @`from`, @@time_zone
+FROM t
+WHERE id IN UNNEST(@ids) AND d < @from
+LIMIT @n;
+",
+    );
+    assert_eq!(inv.named, vec!["from", "ids", "n"]);
+    assert_eq!(inv.positional_count, 0);
+    assert!(!inv.mixed);
+    assert_eq!(inv.system_variables, vec!["time_zone"]);
+    let usages: Vec<(&str, &str, usize, usize, usize)> = inv
+        .usages
+        .iter()
+        .map(|u| {
+            (
+                u.name.as_deref().unwrap(),
+                u.context.as_str(),
+                u.statement,
+                u.line,
+                u.column,
+            )
+        })
+        .collect();
+    assert_eq!(
+        usages,
+        vec![
+            ("time_zone", "SET", 0, 1, 5),
+            ("from", "SELECT", 1, 2, 8),
+            ("time_zone", "SELECT", 1, 2, 17),
+            ("ids", "IN UNNEST", 1, 4, 20),
+            ("from", "WHERE", 1, 4, 34),
+            ("n", "LIMIT", 1, 5, 7),
+        ]
+    );
+}
+
+#[test]
+fn test_collect_positional_parameters() {
+    let inv = inventory("SELECT ? FROM UNNEST(?) LIMIT ? OFFSET ?");
+    assert!(inv.named.is_empty());
+    assert_eq!(inv.positional_count, 4);
+    let usages: Vec<(Option<usize>, &str)> = inv
+        .usages
+        .iter()
+        .map(|u| (u.index, u.context.as_str()))
+        .collect();
+    assert_eq!(
+        usages,
+        vec![
+            (Some(0), "SELECT"),
+            (Some(1), "UNNEST"),
+            (Some(2), "LIMIT"),
+            (Some(3), "OFFSET"),
+        ]
+    );
+    // mixed
+    assert!(inventory("SELECT ?, @x").mixed);
+    assert!(!inventory("SELECT ?, @@time_zone").mixed);
+}
@@ -5,14 +5,36 @@ use crate::cst::ContentType;
 use crate::cst::Node;
 use crate::cst::NodeType;
 use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::lexer::Lexer;
+use crate::literal::{self, LiteralValue};
 use crate::token::Token;
 
+// the first keyword of each operator of graph query
+const GQL_KEYWORDS: [&str; 15] = [
+    "MATCH",
+    "OPTIONAL",
+    "LET",
+    "FILTER",
+    "ORDER",
+    "LIMIT",
+    "OFFSET",
+    "SKIP",
+    "WITH",
+    "FOR",
+    "RETURN",
+    "NEXT",
+    "UNION",
+    "INTERSECT",
+    "EXCEPT",
+];
+
 #[derive(Clone)]
 pub struct Parser {
     position: usize,
     leading_comment_indices: Vec<usize>,
     trailing_comment_indices: Vec<usize>,
     tokens: Vec<Token>,
+    dynamic_sql: bool, // parse the SQL of EXECUTE IMMEDIATE
 }
 
 impl Parser {
@@ -22,6 +44,7 @@ impl Parser {
             leading_comment_indices: Vec::new(),
             trailing_comment_indices: Vec::new(),
             tokens,
+            dynamic_sql: false,
         };
         while p.tokens[p.position].is_comment() {
             p.leading_comment_indices.push(p.position);
@@ -39,7 +62,21 @@ impl Parser {
         }
         p
     }
+    pub fn new_dynamic_sql(tokens: Vec<Token>) -> Parser {
+        let mut p = Parser::new(tokens);
+        p.dynamic_sql = true;
+        p
+    }
     pub fn parse_code(&mut self) -> BQ2CSTResult<Vec<Node>> {
+        // #legacySQL | #standardSQL
+        for token in self.tokens.iter().take_while(|t| t.is_comment()) {
+            if token.literal.trim().eq_ignore_ascii_case("#legacySQL") {
+                return Err(BQ2CSTError::legacy_sql(
+                    token,
+                    "Legacy SQL is not supported.".to_string(),
+                ));
+            }
+        }
         let mut stmts: Vec<Node> = Vec::new();
         while !self.is_eof(0) {
             let stmt = self.parse_statement(true)?;
@@ -464,10 +501,7 @@ impl Parser {
                 }
                 "DATE" | "TIME" | "DATETIME" | "TIMESTAMP" | "NUMERIC" | "BIGNUMERIC"
                 | "DECIMAL" | "BIGDECIMAL" | "JSON" => {
-                    if self.get_token(1)?.is_string()
-                        || self.get_token(1)?.in_(&vec!["b", "r", "br", "rb"])
-                            && self.get_token(2)?.is_string()
-                    {
+                    if self.get_token(1)?.is_string() {
                         left.node_type = NodeType::UnaryOperator;
                         self.next_token()?; // -> expr
                         let right = self.parse_expr(002, false, false, false, true)?;
@@ -494,14 +528,6 @@ impl Parser {
                     let right = self.parse_expr(002, false, true, false, true)?;
                     left.push_node("right", right);
                 }
-                "B" | "R" | "BR" | "RB" => {
-                    if self.get_token(1)?.is_string() {
-                        self.next_token()?; // R -> 'string'
-                        let right = self.parse_expr(001, false, false, false, true)?;
-                        left.push_node("right", right);
-                        left.node_type = NodeType::UnaryOperator;
-                    }
-                }
                 "WITH" => {
                     if !self.get_token(1)?.is("(") {
                         left = self.parse_select_statement(false, true)?;
@@ -561,6 +587,9 @@ impl Parser {
                 _ => (),
             };
         }
+        if !after_dot && self.get_token(0)?.is_macro() {
+            left = self.parse_macro_invocation()?;
+        }
         // infix
         while self.get_precedence(1)? < precedence {
             match self.get_token(1)?.literal.to_uppercase().as_str() {
@@ -1130,6 +1159,7 @@ impl Parser {
                             return self.parse_create_search_index_statement(semicolon)
                         }
                         "MODEL" => return self.parse_create_model_statement(semicolon),
+                        "PROPERTY" => return self.parse_create_property_graph_statement(semicolon),
                         _ => {
                             offset += 1;
                             if 5 < offset {
@@ -1212,6 +1242,8 @@ impl Parser {
             "RAISE" => self.parse_raise_statement(semicolon)?,
             "RETURN" => self.parse_single_token_statement(semicolon)?,
             "CALL" => self.parse_call_statement(semicolon)?,
+            // graph
+            "GRAPH" => self.parse_graph_statement(semicolon)?,
             // DEBUG
             "ASSERT" => self.parse_assert_satement(semicolon)?,
             // other
@@ -1222,6 +1254,20 @@ impl Parser {
                     self.parse_export_model_statement(semicolon)?
                 }
             }
+            // macro
+            "DEFINE" if self.get_token(1)?.is("MACRO") => {
+                self.parse_define_macro_statement(semicolon)?
+            }
+            _ if self.get_token(0)?.is_macro() => {
+                let mut macro_ = self.parse_macro_invocation()?;
+                if self.get_token(1)?.is(";") && semicolon {
+                    self.next_token()?; // -> ;
+                    macro_.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
+                }
+                macro_
+            }
+            // sqlx
+            _ if self.get_token(0)?.is_sqlx_block() => self.construct_node(NodeType::SqlxBlock)?,
             _ => self.parse_labeled_statement(semicolon)?,
         };
         Ok(node)
@@ -1277,6 +1323,26 @@ impl Parser {
                 left = self.parse_expr(usize::MAX, false, false, false, true)?;
                 left.node_type = NodeType::CallingUnnest;
             }
+            "GRAPH_TABLE" if self.get_token(1)?.is("(") => {
+                left = self.parse_graph_table_expr()?;
+            }
+            // legacy SQL
+            "[" => {
+                return Err(BQ2CSTError::legacy_sql(
+                    self.get_token(0)?,
+                    "Legacy SQL table reference like [project:dataset.table] is not supported."
+                        .to_string(),
+                ));
+            }
+            "TABLE_DATE_RANGE" | "TABLE_QUERY" if self.get_token(1)?.is("(") => {
+                return Err(BQ2CSTError::legacy_sql(
+                    self.get_token(0)?,
+                    format!(
+                        "Legacy SQL table wildcard function {} is not supported.",
+                        self.get_token(0)?.literal
+                    ),
+                ));
+            }
             _ => {
                 // tvf or identifier
                 left = self.parse_expr(usize::MAX, false, true, false, true)?;
@@ -1878,6 +1944,26 @@ impl Parser {
         config.push_node("rparen", self.construct_node(NodeType::Symbol)?);
         Ok(config)
     }
+    fn push_privacy_clause(&mut self, mut node: Node) -> BQ2CSTResult<Node> {
+        // WITH DIFFERENTIAL_PRIVACY OPTIONS (...) | WITH AGGREGATION_THRESHOLD OPTIONS (...)
+        let (node_type, key) = if self.get_token(1)?.is("AGGREGATION_THRESHOLD") {
+            (
+                NodeType::AggregationThresholdClause,
+                "aggregation_threshold",
+            )
+        } else {
+            (NodeType::DifferentialPrivacyClause, "differential_privacy")
+        };
+        let mut with = self.construct_node(node_type)?;
+        self.next_token()?; // -> DIFFERENTIAL_PRIVACY | AGGREGATION_THRESHOLD
+        with.push_node(key, self.construct_node(NodeType::Keyword)?);
+        if self.get_token(1)?.is("OPTIONS") {
+            self.next_token()?; // -> OPTIONS
+            with.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+        };
+        node.push_node(key, with);
+        Ok(node)
+    }
     fn parse_by_name_clause(&mut self) -> BQ2CSTResult<Node> {
         let mut by = self.construct_node(NodeType::KeywordSequence)?;
         self.next_token()?; // -> NAME
@@ -2086,20 +2172,10 @@ impl Parser {
         // SELECT
         let mut node = self.construct_node(NodeType::SelectStatement)?;
 
-        // WITH DIFFERENTIAL_PRIVACY
+        // WITH DIFFERENTIAL_PRIVACY | WITH AGGREGATION_THRESHOLD
         if self.get_token(1)?.is("WITH") && !self.get_token(2)?.is("(") {
             self.next_token()?; // -> WITH
-            let mut with = self.construct_node(NodeType::DifferentialPrivacyClause)?;
-            self.next_token()?; // -> differential_privacy
-            with.push_node(
-                "differential_privacy",
-                self.construct_node(NodeType::Keyword)?,
-            );
-            if self.get_token(1)?.is("OPTIONS") {
-                self.next_token()?; // -> OPTIONS
-                with.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
-            };
-            node.push_node("differential_privacy", with);
+            node = self.push_privacy_clause(node)?;
         }
 
         // DISTINCT
@@ -2265,7 +2341,20 @@ impl Parser {
         let mut pipe = self.construct_node(NodeType::PipeStatement)?;
         pipe.push_node("left", left);
         self.next_token()?; // -> SELECT | LIMIT | ...
+        pipe.push_node("right", self.parse_pipe_operator()?);
+
+        if self.get_token(1)?.is("|>") {
+            self.next_token()?; // -> |>
+            return self.parse_pipe_statement(pipe);
+        }
 
+        if self.get_token(1)?.is(";") {
+            self.next_token()?; // -> ;
+            pipe.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
+        };
+        Ok(pipe)
+    }
+    fn parse_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
         let operator = match self.get_token(0)?.literal.to_uppercase().as_str() {
             "EXTEND" | "SET" | "DROP" | "RENAME" | "AS" | "WHERE" | "CALL" => {
                 self.parse_base_pipe_operator(false)?
@@ -2290,6 +2379,15 @@ impl Parser {
             "WITH" => self.parse_with_pipe_operator()?,
             "MATCH_RECOGNIZE" => self.parse_match_recognize_pipe_operator()?,
             "DISTINCT" => self.construct_node(NodeType::Keyword)?,
+            "ASSERT" => self.parse_assert_pipe_operator()?,
+            "LOG" => self.parse_log_pipe_operator()?,
+            "DESCRIBE" | "STATIC_DESCRIBE" => {
+                self.construct_node(NodeType::DescribePipeOperator)?
+            }
+            "WINDOW" => self.parse_window_pipe_operator()?,
+            "RECURSIVE" => self.parse_recursive_union_pipe_operator()?,
+            "FORK" | "TEE" => self.parse_fork_pipe_operator()?,
+            "IF" => self.parse_if_pipe_operator()?,
             _ => {
                 return Err(BQ2CSTError::from_token(
                     self.get_token(0)?,
@@ -2297,23 +2395,14 @@ impl Parser {
                 ))
             }
         };
-        pipe.push_node("right", operator);
-
-        if self.get_token(1)?.is("|>") {
-            self.next_token()?; // -> |>
-            return self.parse_pipe_statement(pipe);
-        }
-
-        if self.get_token(1)?.is(";") {
-            self.next_token()?; // -> ;
-            pipe.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
-        };
-        Ok(pipe)
+        Ok(operator)
     }
     fn parse_select_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
         let mut operator = self.construct_node(NodeType::SelectPipeOperator)?;
-
-        // WITH DIFFERENTIAL_PRIVACY seems not supported
+        if self.get_token(1)?.is("WITH") && !self.get_token(2)?.is("(") {
+            self.next_token()?; // -> WITH
+            operator = self.push_privacy_clause(operator)?;
+        }
         let mut keywords: Vec<Node> = vec![];
         if self.get_token(1)?.in_(&vec!["ALL", "DISTINCT"]) {
             self.next_token()?; // -> ALL | DISTINCT
@@ -2364,6 +2453,10 @@ impl Parser {
     }
     fn parse_aggregate_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
         let mut operator = self.construct_node(NodeType::AggregatePipeOperator)?;
+        if self.get_token(1)?.is("WITH") && !self.get_token(2)?.is("(") {
+            self.next_token()?; // -> WITH
+            operator = self.push_privacy_clause(operator)?;
+        }
         self.next_token()?; // -> expr
         let exprs = self.parse_exprs(&vec![";", "GROUP"], true, true)?;
         operator.push_node_vec("exprs", exprs);
@@ -2498,6 +2591,133 @@ impl Parser {
         operator.node_type = NodeType::MatchRecognizePipeOperator;
         Ok(operator)
     }
+    fn parse_assert_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+        let mut operator = self.construct_node(NodeType::AssertPipeOperator)?;
+        self.next_token()?; // -> expr
+
+        // the first expr is the condition, the others are payload
+        let exprs = self.parse_exprs(&vec![";"], false, false)?;
+        operator.push_node_vec("exprs", exprs);
+        Ok(operator)
+    }
+    fn parse_log_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+        let mut operator = self.construct_node(NodeType::LogPipeOperator)?;
+        if self.get_token(1)?.is("(") {
+            self.next_token()?; // -> (
+            operator.push_node("subpipeline", self.parse_subpipeline()?);
+        }
+        Ok(operator)
+    }
+    fn parse_window_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+        let mut operator = self.construct_node(NodeType::WindowPipeOperator)?;
+        self.next_token()?; // -> expr
+        let exprs = self.parse_exprs(&vec![";"], true, false)?;
+        operator.push_node_vec("exprs", exprs);
+        Ok(operator)
+    }
+    fn parse_recursive_union_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+        let mut operator = self.construct_node(NodeType::RecursiveUnionPipeOperator)?;
+        self.next_token()?; // -> UNION
+        operator.push_node("union", self.construct_node(NodeType::Keyword)?);
+        self.next_token()?; // -> ALL | DISTINCT
+        operator.push_node("keywords", self.construct_node(NodeType::Keyword)?);
+        if self.get_token(1)?.is("BY") {
+            self.next_token()?; // -> BY
+            operator.push_node("by", self.parse_by_name_clause()?);
+        } else if self.get_token(1)?.in_(&vec!["STRICT", "CORRESPONDING"]) {
+            self.next_token()?; // -> STRICT | CORRESPONDING
+            operator.push_node("corresponding", self.parse_corresponding_clause()?);
+        }
+        self.next_token()?; // -> (
+        if self.get_token(1)?.is("|>") {
+            operator.push_node("subpipeline", self.parse_subpipeline()?);
+        } else {
+            let query = self.parse_expr(usize::MAX, false, false, false, false)?;
+            operator.push_node_vec("exprs", vec![query]);
+        }
+        operator = self.push_trailing_alias(operator)?;
+        Ok(operator)
+    }
+    // TEE is also supported
+    fn parse_fork_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+        let mut operator = self.construct_node(NodeType::ForkPipeOperator)?;
+        let mut subpipelines = Vec::new();
+        loop {
+            self.next_token()?; // -> (
+            let mut subpipeline = self.parse_subpipeline()?;
+            if self.get_token(1)?.is(",") {
+                self.next_token()?; // -> ,
+                subpipeline.push_node("comma", self.construct_node(NodeType::Symbol)?);
+                subpipelines.push(subpipeline);
+            } else {
+                subpipelines.push(subpipeline);
+                break;
+            }
+        }
+        operator.push_node_vec("subpipelines", subpipelines);
+        Ok(operator)
+    }
+    fn parse_if_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+        let mut if_ = self.construct_node(NodeType::IfPipeOperator)?;
+        self.next_token()?; // -> condition
+        if_.push_node(
+            "condition",
+            self.parse_expr(usize::MAX, false, false, false, true)?,
+        );
+        self.next_token()?; // -> THEN
+        if_.push_node("then", self.parse_keyword_with_subpipeline()?);
+
+        let mut elseifs = Vec::new();
+        while self.get_token(1)?.is("ELSEIF") {
+            self.next_token()?; // -> ELSEIF
+            let mut elseif = self.construct_node(NodeType::ElseIfClause)?;
+            self.next_token()?; // -> condition
+            elseif.push_node(
+                "condition",
+                self.parse_expr(usize::MAX, false, false, false, true)?,
+            );
+            self.next_token()?; // -> THEN
+            elseif.push_node("then", self.parse_keyword_with_subpipeline()?);
+            elseifs.push(elseif);
+        }
+        if !elseifs.is_empty() {
+            if_.push_node_vec("elseifs", elseifs);
+        }
+
+        if self.get_token(1)?.is("ELSE") {
+            self.next_token()?; // -> ELSE
+            if_.push_node("else", self.parse_keyword_with_subpipeline()?);
+        }
+        Ok(if_)
+    }
+    fn parse_keyword_with_subpipeline(&mut self) -> BQ2CSTResult<Node> {
+        let mut keyword = self.construct_node(NodeType::KeywordWithExpr)?;
+        self.next_token()?; // -> (
+        keyword.push_node("expr", self.parse_subpipeline()?);
+        Ok(keyword)
+    }
+    fn parse_subpipeline(&mut self) -> BQ2CSTResult<Node> {
+        // (|> WHERE x |> SELECT y)
+        let mut subpipeline = self.construct_node(NodeType::Subpipeline)?;
+        if self.get_token(1)?.is("|>") {
+            self.next_token()?; // -> |>
+            let mut pipe = self.construct_node(NodeType::PipeStatement)?;
+            self.next_token()?; // -> SELECT | LIMIT | ...
+            pipe.push_node("right", self.parse_pipe_operator()?);
+            while self.get_token(1)?.is("|>") {
+                self.next_token()?; // -> |>
+                let mut next_pipe = self.construct_node(NodeType::PipeStatement)?;
+                next_pipe.push_node("left", pipe);
+                self.next_token()?; // -> SELECT | LIMIT | ...
+                next_pipe.push_node("right", self.parse_pipe_operator()?);
+                pipe = next_pipe;
+            }
+            subpipeline.push_node("stmt", pipe);
+        }
+        self.next_token()?; // -> )
+        subpipeline.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+        Ok(subpipeline)
+    }
     fn parse_base_pipe_operator(&mut self, keywords: bool) -> BQ2CSTResult<Node> {
         let mut operator = self.construct_node(NodeType::BasePipeOperator)?;
         // NOTE: for now, single keyword is only allowed
@@ -3325,6 +3545,39 @@ impl Parser {
         }
         Ok(create)
     }
+    fn parse_create_property_graph_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+        let mut create = self.construct_node(NodeType::CreatePropertyGraphStatement)?;
+        if self.get_token(1)?.is("OR") {
+            self.next_token()?; // -> OR
+            create.push_node_vec("or_replace", self.parse_n_keywords(2)?);
+        }
+        self.next_token()?; // -> PROPERTY
+        let mut what = self.construct_node(NodeType::KeywordSequence)?;
+        self.next_token()?; // -> GRAPH
+        what.push_node("next_keyword", self.construct_node(NodeType::Keyword)?);
+        create.push_node("what", what);
+        if self.get_token(1)?.is("IF") {
+            self.next_token()?; // -> IF
+            create.push_node_vec("if_not_exists", self.parse_n_keywords(3)?);
+        }
+        self.next_token()?; // -> ident
+        create.push_node("ident", self.parse_identifier()?);
+        self.next_token()?; // -> NODE
+        create.push_node("node_tables", self.parse_graph_element_tables(false)?);
+        if self.get_token(1)?.is("EDGE") {
+            self.next_token()?; // -> EDGE
+            create.push_node("edge_tables", self.parse_graph_element_tables(true)?);
+        }
+        if self.get_token(1)?.is("OPTIONS") {
+            self.next_token()?; // -> OPTIONS
+            create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+        }
+        if self.get_token(1)?.is(";") && semicolon {
+            self.next_token()?; // -> ;
+            create.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
+        }
+        Ok(create)
+    }
     fn parse_alter_schema_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
         let mut alter = self.construct_node(NodeType::AlterSchemaStatement)?;
         self.next_token()?; // -> SCHEMA
@@ -3805,10 +4058,10 @@ impl Parser {
             self.next_token()?; // -> TABLE
             drop.push_node("table", self.construct_node(NodeType::Keyword)?)
         }
-        self.next_token()?; // -> SCHEMA, TABLE, VIEW, FUNCTION, PROCEDURE, SEARCH
-        if self.get_token(0)?.is("SEARCH") {
+        self.next_token()?; // -> SCHEMA, TABLE, VIEW, FUNCTION, PROCEDURE, SEARCH, PROPERTY
+        if self.get_token(0)?.in_(&vec!["SEARCH", "PROPERTY"]) {
             let mut what = self.construct_node(NodeType::KeywordSequence)?;
-            self.next_token()?; // -> INDEX
+            self.next_token()?; // -> INDEX | GRAPH
             what.push_node("next_keyword", self.construct_node(NodeType::Keyword)?);
             drop.push_node("what", what);
         } else {
@@ -3981,10 +4234,18 @@ impl Parser {
         self.next_token()?; // EXECUTE -> IMMEDIATE
         execute.push_node("immediate", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // IMMEDIATE -> sql_expr
-        execute.push_node(
-            "sql_expr",
-            self.parse_expr(usize::MAX, false, false, false, true)?,
-        );
+        let sql_expr = self.parse_expr(usize::MAX, false, false, false, true)?;
+        if self.dynamic_sql {
+            let stmts = constant_sql(&sql_expr)
+                .and_then(|chars| chars.map(|chars| parse_dynamic_sql(&chars)).transpose());
+            match stmts {
+                Ok(Some(stmts)) if !stmts.is_empty() => execute.push_node_vec("stmts", stmts),
+                Ok(_) => (),
+                // the error is recorded so that the other statements can be parsed
+                Err(e) => execute.push_error("error", e),
+            }
+        }
+        execute.push_node("sql_expr", sql_expr);
         if self.get_token(1)?.is("INTO") {
             self.next_token()?; // sql_expr -> INTO
             let mut into = self.construct_node(NodeType::KeywordWithExprs)?;
@@ -4017,6 +4278,42 @@ impl Parser {
         }
         Ok(execute)
     }
+    fn parse_define_macro_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+        let mut define = self.construct_node(NodeType::DefineMacroStatement)?;
+        self.next_token()?; // -> MACRO
+        define.push_node("what", self.construct_node(NodeType::Keyword)?);
+        self.next_token()?; // -> ident
+        define.push_node("ident", self.construct_node(NodeType::Identifier)?);
+        // the body is an arbitrary sequence of tokens
+        let mut body = Vec::new();
+        while !self.get_token(1)?.is(";") && !self.is_eof(1) {
+            self.next_token()?; // -> token
+            body.push(self.construct_node(NodeType::Unknown)?);
+        }
+        if !body.is_empty() {
+            define.push_node_vec("body", body);
+        }
+        if self.get_token(1)?.is(";") && semicolon {
+            self.next_token()?; // -> ;
+            define.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
+        }
+        Ok(define)
+    }
+    fn parse_macro_invocation(&mut self) -> BQ2CSTResult<Node> {
+        let mut macro_ = self.construct_node(NodeType::MacroInvocation)?;
+        if self.get_token(1)?.is("(") {
+            self.next_token()?; // -> (
+            let mut args = self.construct_node(NodeType::GroupedExprs)?;
+            if !self.get_token(1)?.is(")") {
+                self.next_token()?; // -> exprs
+                args.push_node_vec("exprs", self.parse_exprs(&vec![")"], false, false)?);
+            }
+            self.next_token()?; // -> )
+            args.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+            macro_.push_node("args", args);
+        }
+        Ok(macro_)
+    }
     fn parse_begin_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
         let mut begin = self.construct_node(NodeType::BeginStatement)?;
         let mut stmts = Vec::new();
@@ -4453,4 +4750,513 @@ impl Parser {
         }
         Ok(load)
     }
+    // ----- graph -----
+    fn parse_graph_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+        let mut graph = self.construct_node(NodeType::GraphStatement)?;
+        self.next_token()?; // -> ident
+        graph.push_node("ident", self.parse_identifier()?);
+        graph.push_node_vec("operators", self.parse_graph_operators()?);
+        if self.get_token(1)?.is(";") && semicolon {
+            self.next_token()?; // -> ;
+            graph.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
+        }
+        Ok(graph)
+    }
+    fn parse_graph_operators(&mut self) -> BQ2CSTResult<Vec<Node>> {
+        let mut operators = Vec::new();
+        while self.get_token(1)?.in_(&GQL_KEYWORDS.to_vec()) {
+            self.next_token()?; // -> MATCH | LET | ...
+            let operator = match self.get_token(0)?.literal.to_uppercase().as_str() {
+                "MATCH" | "OPTIONAL" => self.parse_graph_match_operator()?,
+                "FILTER" | "LET" | "LIMIT" | "OFFSET" | "SKIP" | "FOR" | "ORDER" | "WITH"
+                | "RETURN" => {
+                    let mut operator = self.construct_node(NodeType::BasePipeOperator)?;
+                    if self
+                        .get_token(1)?
+                        .in_(&vec!["WHERE", "BY", "ALL", "DISTINCT"])
+                    {
+                        self.next_token()?; // -> WHERE | BY | ALL | DISTINCT
+                        operator.push_node("keywords", self.construct_node(NodeType::Keyword)?);
+                    }
+                    let mut exprs = Vec::new();
+                    loop {
+                        self.next_token()?; // -> expr
+                        let mut expr = self.parse_expr(usize::MAX, false, false, false, true)?;
+                        // NOTE GQL keywords (e.g. NEXT) are not reserved
+                        if self.get_token(1)?.is("AS")
+                            || self.get_token(1)?.is_identifier()
+                                && !self.get_token(1)?.in_(&GQL_KEYWORDS.to_vec())
+                        {
+                            expr = self.push_trailing_alias(expr)?;
+                        }
+                        if self.get_token(1)?.is(",") {
+                            self.next_token()?; // -> ,
+                            expr.push_node("comma", self.construct_node(NodeType::Symbol)?);
+                            exprs.push(expr);
+                        } else {
+                            exprs.push(expr);
+                            break;
+                        }
+                    }
+                    operator.push_node_vec("exprs", exprs);
+                    if self.get_token(1)?.is("GROUP") {
+                        self.next_token()?; // -> GROUP
+                        operator.push_node("groupby", self.parse_groupby_exprs(true)?);
+                    }
+                    operator
+                }
+                "UNION" | "INTERSECT" | "EXCEPT" => {
+                    let mut operator = self.construct_node(NodeType::KeywordSequence)?;
+                    self.next_token()?; // -> ALL | DISTINCT
+                    operator.push_node("next_keyword", self.construct_node(NodeType::Keyword)?);
+                    operator
+                }
+                _ => self.construct_node(NodeType::Keyword)?, // NEXT
+            };
+            operators.push(operator);
+        }
+        Ok(operators)
+    }
+    fn parse_graph_match_operator(&mut self) -> BQ2CSTResult<Node> {
+        let mut optional = None;
+        if self.get_token(0)?.is("OPTIONAL") {
+            optional = Some(self.construct_node(NodeType::Keyword)?);
+            self.next_token()?; // -> MATCH
+        }
+        let mut match_ = self.construct_node(NodeType::GraphMatchOperator)?;
+        if let Some(optional) = optional {
+            match_.push_node("optional", optional);
+        }
+        let mut patterns = Vec::new();
+        loop {
+            self.next_token()?; // -> (
+            patterns.push(self.parse_graph_node_pattern()?);
+            while self.is_graph_edge_start()? {
+                self.next_token()?; // -> -[ | -> | ...
+                patterns.push(self.parse_graph_edge_pattern()?);
+                self.next_token()?; // -> (
+                patterns.push(self.parse_graph_node_pattern()?);
+            }
+            if self.get_token(1)?.is(",") {
+                self.next_token()?; // -> ,
+                let comma = self.construct_node(NodeType::Symbol)?;
+                patterns.last_mut().unwrap().push_node("comma", comma);
+            } else {
+                break;
+            }
+        }
+        match_.push_node_vec("patterns", patterns);
+        if self.get_token(1)?.is("WHERE") {
+            self.next_token()?; // -> WHERE
+            match_.push_node("where", self.parse_graph_where_clause()?);
+        }
+        Ok(match_)
+    }
+    fn is_graph_edge_start(&self) -> BQ2CSTResult<bool> {
+        Ok(self.get_token(1)?.in_(&vec!["->", "-", "<"]))
+    }
+    fn is_adjacent(&self) -> BQ2CSTResult<bool> {
+        // returns true if there is no space between the current token and the next one
+        let curr_token = self.get_token(0)?;
+        let next_token = self.get_token(1)?;
+        Ok(curr_token.line == next_token.line
+            && curr_token.column + curr_token.literal.chars().count() == next_token.column)
+    }
+    fn merge_next_symbol(&mut self, node: &mut Node) -> BQ2CSTResult<()> {
+        // e.g. `<` and `-` -> `<-`
+        // NOTE the lexer does not combine them because `x-[1][OFFSET(0)]` is not a graph query
+        self.next_token()?;
+        let literal = self.get_token(0)?.literal.clone();
+        node.token.as_mut().unwrap().literal.push_str(&literal);
+        Ok(())
+    }
+    fn parse_graph_node_pattern(&mut self) -> BQ2CSTResult<Node> {
+        let mut node = self.construct_node(NodeType::GraphNodePattern)?;
+        self.parse_graph_element_filler(&mut node, ")")?;
+        self.next_token()?; // -> )
+        node.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+        Ok(node)
+    }
+    fn parse_graph_edge_pattern(&mut self) -> BQ2CSTResult<Node> {
+        let mut edge = self.construct_node(NodeType::GraphEdgePattern)?;
+        if self.get_token(0)?.is("<")
+            && self.get_token(1)?.in_(&vec!["-", "->"])
+            && self.is_adjacent()?
+        {
+            self.merge_next_symbol(&mut edge)?; // <- | <->
+        }
+        if edge.literal().ends_with('-') && self.get_token(1)?.is("[") && self.is_adjacent()? {
+            self.merge_next_symbol(&mut edge)?; // -[ | <-[
+        }
+        if edge.literal().ends_with('[') {
+            self.parse_graph_element_filler(&mut edge, "]")?;
+            self.next_token()?; // -> ]
+            let mut rbracket = self.construct_node(NodeType::Symbol)?;
+            if self.get_token(1)?.in_(&vec!["-", "->"]) && self.is_adjacent()? {
+                self.merge_next_symbol(&mut rbracket)?; // ]- | ]->
+            }
+            edge.push_node("rbracket", rbracket);
+        }
+        if self.get_token(1)?.is("{") {
+            self.next_token()?; // -> {
+            edge.push_node("quantifier", self.parse_quantifier()?);
+        }
+        Ok(edge)
+    }
+    fn parse_graph_element_filler(&mut self, element: &mut Node, end: &str) -> BQ2CSTResult<()> {
+        // (var IS label {k: v} WHERE cond)
+        if self.get_token(1)?.is_identifier() && !self.get_token(1)?.is("WHERE") {
+            self.next_token()?; // -> var
+            element.push_node("var", self.construct_node(NodeType::Identifier)?);
+        }
+        if self.get_token(1)?.in_(&vec![":", "IS"]) {
+            self.next_token()?; // -> : | IS
+            let mut label = self.construct_node(NodeType::KeywordWithExpr)?;
+            self.next_token()?; // -> label expression
+            label.push_node("expr", self.parse_graph_label_expr(usize::MAX)?);
+            element.push_node("label", label);
+        }
+        if self.get_token(1)?.is_template() {
+            self.split_property_spec()?;
+        }
+        if self.get_token(1)?.is("{") {
+            self.next_token()?; // -> {
+            element.push_node("properties", self.parse_graph_property_spec()?);
+        }
+        if self.get_token(1)?.is("WHERE") {
+            self.next_token()?; // -> WHERE
+            element.push_node("where", self.parse_graph_where_clause()?);
+        }
+        if !self.get_token(1)?.is(end) {
+            return Err(BQ2CSTError::from_token(
+                self.get_token(1)?,
+                format!("Expected `{}` but got: {:?}", end, self.get_token(1)?),
+            ));
+        }
+        Ok(())
+    }
+    fn split_property_spec(&mut self) -> BQ2CSTResult<()> {
+        // the lexer reads `{id: 1}` as a template, which is split into tokens here
+        let idx = self.get_offset_index(1)?;
+        let template = self.tokens[idx].clone();
+        let chars: Vec<char> = template.literal.chars().collect();
+        if chars.len() < 2 || chars[0] != '{' || ['{', '%', '#'].contains(&chars[1]) {
+            return Ok(()); // {{ variable }}, {% ... %}, {# ... #}
+        }
+        let inner: String = chars[1..chars.len() - 1].iter().collect();
+        // the position of the token in `inner` -> the position in the original code
+        let map = |line: usize, column: usize| {
+            if line == 1 {
+                (template.line, template.column + column)
+            } else {
+                (template.line + line - 1, column)
+            }
+        };
+        let mut tokens = Lexer::new(inner.clone()).tokenize_code().map_err(|mut e| {
+            (e.line, e.column) = map(e.line, e.column);
+            e
+        })?;
+        tokens.pop(); // EOF
+        for token in &mut tokens {
+            (token.line, token.column) = map(token.line, token.column);
+        }
+        let rbrace = match inner.rfind('\n') {
+            Some(i) => (
+                template.line + inner.matches('\n').count(),
+                inner[i + 1..].chars().count() + 1,
+            ),
+            None => (template.line, template.column + chars.len() - 1),
+        };
+        tokens.insert(
+            0,
+            Token::new(template.line, template.column, "{".to_string()),
+        );
+        tokens.push(Token::new(rbrace.0, rbrace.1, "}".to_string()));
+        self.tokens.splice(idx..idx + 1, tokens);
+        Ok(())
+    }
+    fn parse_graph_label_expr(&mut self, precedence: usize) -> BQ2CSTResult<Node> {
+        // precedence: ! > & > |
+        let mut left = match self.get_token(0)?.literal.as_str() {
+            "%" => self.construct_node(NodeType::Symbol)?,
+            "!" => {
+                let mut not = self.construct_node(NodeType::UnaryOperator)?;
+                self.next_token()?; // -> label
+                not.push_node("right", self.parse_graph_label_expr(1)?);
+                not
+            }
+            "(" => {
+                let mut group = self.construct_node(NodeType::GroupedExpr)?;
+                self.next_token()?; // -> label
+                group.push_node("expr", self.parse_graph_label_expr(usize::MAX)?);
+                self.next_token()?; // -> )
+                group.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+                group
+            }
+            _ => self.construct_node(NodeType::Identifier)?,
+        };
+        loop {
+            let next_precedence = match self.get_token(1)?.literal.as_str() {
+                "&" => 2,
+                "|" => 3,
+                _ => break,
+            };
+            if precedence <= next_precedence {
+                break;
+            }
+            self.next_token()?; // -> & | |
+            let mut binary = self.construct_node(NodeType::BinaryOperator)?;
+            binary.push_node("left", left);
+            self.next_token()?; // -> label
+            binary.push_node("right", self.parse_graph_label_expr(next_precedence)?);
+            left = binary;
+        }
+        Ok(left)
+    }
+    fn parse_graph_property_spec(&mut self) -> BQ2CSTResult<Node> {
+        let mut spec = self.construct_node(NodeType::GraphPropertySpec)?;
+        let mut exprs = Vec::new();
+        while !self.get_token(1)?.is("}") {
+            self.next_token()?; // -> key
+            let key = self.construct_node(NodeType::Identifier)?;
+            self.next_token()?; // -> :
+            let mut colon = self.construct_node(NodeType::BinaryOperator)?;
+            colon.push_node("left", key);
+            self.next_token()?; // -> value
+            colon.push_node(
+                "right",
+                self.parse_expr(usize::MAX, false, false, false, false)?,
+            );
+            if self.get_token(1)?.is(",") {
+                self.next_token()?; // -> ,
+                colon.push_node("comma", self.construct_node(NodeType::Symbol)?);
+            }
+            exprs.push(colon);
+        }
+        spec.push_node_vec("exprs", exprs);
+        self.next_token()?; // -> }
+        spec.push_node("rbrace", self.construct_node(NodeType::Symbol)?);
+        Ok(spec)
+    }
+    fn parse_graph_where_clause(&mut self) -> BQ2CSTResult<Node> {
+        let mut where_ = self.construct_node(NodeType::KeywordWithExpr)?;
+        self.next_token()?; // -> expr
+        where_.push_node(
+            "expr",
+            self.parse_expr(usize::MAX, false, false, false, true)?,
+        );
+        Ok(where_)
+    }
+    fn parse_graph_table_expr(&mut self) -> BQ2CSTResult<Node> {
+        let mut graph_table = self.construct_node(NodeType::GraphTableExpr)?;
+        self.next_token()?; // -> (
+        let mut config = self.construct_node(NodeType::GraphTableConfig)?;
+        self.next_token()?; // -> ident
+        config.push_node("ident", self.parse_identifier()?);
+        config.push_node_vec("operators", self.parse_graph_operators()?);
+        if self.get_token(1)?.is("COLUMNS") {
+            self.next_token()?; // -> COLUMNS
+            config.push_node("columns", self.parse_keyword_with_grouped_exprs(true)?);
+        }
+        self.next_token()?; // -> )
+        config.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+        graph_table.push_node("config", config);
+        Ok(graph_table)
+    }
+    fn parse_graph_element_tables(&mut self, edge: bool) -> BQ2CSTResult<Node> {
+        // NODE TABLES (...) | EDGE TABLES (...)
+        let mut node_or_edge = self.construct_node(NodeType::KeywordSequence)?;
+        self.next_token()?; // -> TABLES
+        let mut tables = self.construct_node(NodeType::KeywordWithGroupedXXX)?;
+        self.next_token()?; // -> (
+        let mut group = self.construct_node(NodeType::GroupedExprs)?;
+        let mut exprs = Vec::new();
+        while !self.get_token(1)?.is(")") {
+            self.next_token()?; // -> table
+            exprs.push(self.parse_graph_element_table(edge)?);
+        }
+        group.push_node_vec("exprs", exprs);
+        self.next_token()?; // -> )
+        group.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+        tables.push_node("group", group);
+        node_or_edge.push_node("next_keyword", tables);
+        Ok(node_or_edge)
+    }
+    fn parse_graph_element_table(&mut self, edge: bool) -> BQ2CSTResult<Node> {
+        let mut table = self.parse_identifier()?;
+        if self.get_token(1)?.is("AS")
+            || self.get_token(1)?.is_identifier()
+                && !self.get_token(1)?.in_(&vec![
+                    "KEY",
+                    "SOURCE",
+                    "DESTINATION",
+                    "LABEL",
+                    "PROPERTIES",
+                ])
+        {
+            table = self.push_trailing_alias(table)?;
+        }
+        if self.get_token(1)?.is("KEY") {
+            self.next_token()?; // -> KEY
+            table.push_node("key", self.parse_keyword_with_grouped_exprs(false)?);
+        }
+        if edge {
+            self.next_token()?; // -> SOURCE
+            table.push_node("source", self.parse_constraint()?);
+            self.next_token()?; // -> DESTINATION
+            table.push_node("destination", self.parse_constraint()?);
+        }
+        let mut labels = Vec::new();
+        while self.get_token(1)?.in_(&vec!["DEFAULT", "LABEL"]) {
+            self.next_token()?; // -> DEFAULT | LABEL
+            let mut default = None;
+            if self.get_token(0)?.is("DEFAULT") {
+                default = Some(self.construct_node(NodeType::Keyword)?);
+                self.next_token()?; // -> LABEL
+            }
+            let mut label = self.construct_node(NodeType::GraphLabel)?;
+            if let Some(default) = default {
+                label.push_node("default", default);
+            } else {
+                self.next_token()?; // -> name
+                label.push_node("name", self.construct_node(NodeType::Identifier)?);
+            }
+            if self.get_token(1)?.in_(&vec!["PROPERTIES", "NO"]) {
+                self.next_token()?; // -> PROPERTIES | NO
+                label.push_node("properties", self.parse_graph_properties()?);
+            }
+            labels.push(label);
+        }
+        if !labels.is_empty() {
+            table.push_node_vec("labels", labels);
+        } else if self.get_token(1)?.in_(&vec!["PROPERTIES", "NO"]) {
+            self.next_token()?; // -> PROPERTIES | NO
+            table.push_node("properties", self.parse_graph_properties()?);
+        }
+        if self.get_token(1)?.is(",") {
+            self.next_token()?; // -> ,
+            table.push_node("comma", self.construct_node(NodeType::Symbol)?);
+        }
+        Ok(table)
+    }
+    fn parse_graph_properties(&mut self) -> BQ2CSTResult<Node> {
+        // PROPERTIES (expr, ...) | PROPERTIES [ARE] ALL COLUMNS [EXCEPT (col, ...)] | NO PROPERTIES
+        if self.get_token(0)?.is("PROPERTIES") && self.get_token(1)?.is("(") {
+            return self.parse_keyword_with_grouped_exprs(true);
+        }
+        let mut keywords = vec![self.construct_node(NodeType::Keyword)?];
+        while self
+            .get_token(1)?
+            .in_(&vec!["PROPERTIES", "ARE", "ALL", "COLUMNS"])
+        {
+            self.next_token()?; // -> PROPERTIES | ARE | ALL | COLUMNS
+            keywords.push(self.construct_node(NodeType::Keyword)?);
+        }
+        let mut temp = keywords.pop().unwrap();
+        if self.get_token(1)?.is("EXCEPT") {
+            temp.node_type = NodeType::KeywordSequence;
+            self.next_token()?; // -> EXCEPT
+            temp.push_node(
+                "next_keyword",
+                self.parse_keyword_with_grouped_exprs(false)?,
+            );
+        }
+        while let Some(mut kw) = keywords.pop() {
+            kw.node_type = NodeType::KeywordSequence;
+            kw.push_node("next_keyword", temp);
+            temp = kw
+        }
+        Ok(temp)
+    }
+}
+
+// each char of a constant SQL and its position in the original code
+type PositionedChars = Vec<(char, (usize, usize))>;
+
+/// Concatenates string literals and placeholders (`?` or `@param`) if the
+/// expression is a constant string, `CONCAT()` or `||` of them.
+fn constant_sql(expr: &Node) -> BQ2CSTResult<Option<PositionedChars>> {
+    let parts: Vec<&Node> = match expr.node_type {
+        NodeType::StringLiteral => {
+            let decoded = literal::decode_string_literal(expr.token.as_ref().unwrap())?;
+            return match decoded.value {
+                LiteralValue::String(value) => {
+                    Ok(Some(value.chars().zip(decoded.positions).collect()))
+                }
+                LiteralValue::Bytes(_) => Ok(None),
+            };
+        }
+        NodeType::Parameter => {
+            let token = expr.token.as_ref().unwrap();
+            return Ok(Some(
+                token
+                    .literal
+                    .chars()
+                    .enumerate()
+                    .map(|(i, ch)| (ch, (token.line, token.column + i)))
+                    .collect(),
+            ));
+        }
+        NodeType::BinaryOperator if expr.literal() == "||" => {
+            match (expr.get_node("left"), expr.get_node("right")) {
+                (Some(left), Some(right)) => vec![left, right],
+                _ => return Ok(None),
+            }
+        }
+        NodeType::CallingFunction
+            if expr.get_node("func").map(|f| f.literal().to_uppercase())
+                == Some("CONCAT".to_string()) =>
+        {
+            match expr.get_node_vec("args") {
+                Some(args) => args.iter().collect(),
+                None => return Ok(None),
+            }
+        }
+        _ => return Ok(None),
+    };
+    let mut res = Vec::new();
+    for part in parts {
+        match constant_sql(part)? {
+            Some(mut chars) => res.append(&mut chars),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(res))
+}
+
+/// Parses a dynamic SQL. The positions of the tokens (and errors) are mapped
+/// into the original code.
+fn parse_dynamic_sql(chars: &PositionedChars) -> BQ2CSTResult<Vec<Node>> {
+    let code: String = chars.iter().map(|(ch, _)| ch).collect();
+    let mut line_starts = vec![0];
+    for (i, (ch, _)) in chars.iter().enumerate() {
+        if *ch == '\n' {
+            line_starts.push(i + 1);
+        }
+    }
+    let map = |line: usize, column: usize| -> Option<(usize, usize)> {
+        let start = line_starts.get(line.checked_sub(1)?)?;
+        chars
+            .get(start + column.checked_sub(1)?)
+            .map(|(_, pos)| *pos)
+    };
+    let map_error = |mut e: BQ2CSTError| {
+        let pos = map(e.line, e.column).or(chars.last().map(|(_, pos)| *pos));
+        if let Some((line, column)) = pos {
+            e.line = line;
+            e.column = column;
+        }
+        e
+    };
+    let mut tokens = Lexer::new(code).tokenize_code().map_err(map_error)?;
+    for token in &mut tokens {
+        if let Some((line, column)) = map(token.line, token.column) {
+            token.line = line;
+            token.column = column;
+        }
+    }
+    let mut p = Parser::new_dynamic_sql(tokens);
+    let mut stmts = p.parse_code().map_err(map_error)?;
+    stmts.pop(); // EOF
+    Ok(stmts)
 }
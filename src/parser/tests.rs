@@ -7,6 +7,7 @@ mod tests_dcl;
 mod tests_ddl;
 mod tests_debug;
 mod tests_dml;
+mod tests_graph;
 mod tests_ml;
 mod tests_other;
 mod tests_pipe;
@@ -21,6 +22,8 @@ struct SuccessTestCase {
     code: String,
     expected_output: String,
     target_idx: usize,
+    sqlx: bool,
+    dynamic_sql: bool,
 }
 
 impl SuccessTestCase {
@@ -29,14 +32,37 @@ impl SuccessTestCase {
             code: code.to_string(),
             expected_output: expected_output.to_string(),
             target_idx,
+            sqlx: false,
+            dynamic_sql: false,
+        }
+    }
+    pub fn new_sqlx(code: &str, expected_output: &str, target_idx: usize) -> Self {
+        Self {
+            sqlx: true,
+            ..Self::new(code, expected_output, target_idx)
+        }
+    }
+    pub fn new_dynamic_sql(code: &str, expected_output: &str, target_idx: usize) -> Self {
+        Self {
+            dynamic_sql: true,
+            ..Self::new(code, expected_output, target_idx)
         }
     }
 }
 
 impl TestCase for SuccessTestCase {
     fn test(&self) {
-        let l = Lexer::new(self.code.clone());
-        let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."));
+        let l = if self.sqlx {
+            Lexer::new_sqlx(self.code.clone())
+        } else {
+            Lexer::new(self.code.clone())
+        };
+        let tokens = l.tokenize_code().expect("Failed to tokenize code.");
+        let mut p = if self.dynamic_sql {
+            Parser::new_dynamic_sql(tokens)
+        } else {
+            Parser::new(tokens)
+        };
         let stmts = p.parse_code().expect("Failed to parse code.");
         println!(
             "\
@@ -101,11 +101,9 @@ exprs:
     self: , (Symbol)
   right:
     self: 1 (NumericLiteral)
-- self: r (UnaryOperator)
+- self: r'xxx' (StringLiteral)
   comma:
     self: , (Symbol)
-  right:
-    self: 'xxx' (StringLiteral)
 - self: DATE (UnaryOperator)
   comma:
     self: , (Symbol)
@@ -115,9 +113,7 @@ exprs:
   comma:
     self: , (Symbol)
   right:
-    self: r (UnaryOperator)
-    right:
-      self: '2020-01-01' (StringLiteral)
+    self: r'2020-01-01' (StringLiteral)
 - self: NOT (UnaryOperator)
   comma:
     self: , (Symbol)
@@ -1983,6 +1979,7 @@ exprs:
 SELECT
   {{variable}},
   {variable},
+  {name: value},
 ",
             "\
 self: SELECT (SelectStatement)
@@ -1993,6 +1990,54 @@ exprs:
 - self: {variable} (Template)
   comma:
     self: , (Symbol)
+- self: {name: value} (Template)
+  comma:
+    self: , (Symbol)
+",
+            0,
+        )),
+        // ----- jinja -----
+        Box::new(SuccessTestCase::new(
+            "\
+{# dbt model #}
+SELECT
+  {% for c in cols %}
+  {{c}},
+  {% endfor %}
+  1
+FROM t
+WHERE true {% if x %} AND a {% else %} AND b {% endif %}
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: {{c}} (Template)
+  comma:
+    self: , (Symbol)
+  leading_comments:
+  - self: {% for c in cols %} (Comment)
+- self: 1 (NumericLiteral)
+  leading_comments:
+  - self: {% endfor %} (Comment)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: t (Identifier)
+leading_comments:
+- self: {# dbt model #} (Comment)
+where:
+  self: WHERE (KeywordWithExpr)
+  expr:
+    self: AND (BinaryOperator)
+    left:
+      self: true (BooleanLiteral)
+      trailing_comments:
+      - self: {% if x %} (Comment)
+    right:
+      self: a (Identifier)
+      trailing_comments:
+      - self: {% else %} AND b (Comment)
+      - self: {% endif %} (Comment)
 ",
             0,
         )),
@@ -2041,9 +2041,7 @@ self: CREATE (CreateProcedureStatement)
 as:
   self: AS (KeywordWithExpr)
   expr:
-    self: r (UnaryOperator)
-    right:
-      self: 'code' (StringLiteral)
+    self: r'code' (StringLiteral)
 group:
   self: ( (GroupedTypeDeclarationOrConstraints)
   rparen:
@@ -3359,6 +3357,220 @@ set:
   self: SET (Keyword)
 what:
   self: RESERVATION (Keyword)
+",
+            0,
+        )),
+        // ----- CREATE PROPERTY GRAPH statement -----
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE OR REPLACE PROPERTY GRAPH IF NOT EXISTS dataset.graph
+NODE TABLES (
+  dataset.person AS p KEY (id) LABEL Person PROPERTIES (name, age AS years) LABEL Entity NO PROPERTIES,
+  dataset.account DEFAULT LABEL PROPERTIES ARE ALL COLUMNS EXCEPT (secret)
+)
+EDGE TABLES (
+  dataset.owns
+    SOURCE KEY (pid) REFERENCES person (id)
+    DESTINATION KEY (aid) REFERENCES account (id)
+    LABEL Owns
+)
+OPTIONS (description = 'graph');
+",
+            "\
+self: CREATE (CreatePropertyGraphStatement)
+edge_tables:
+  self: EDGE (KeywordSequence)
+  next_keyword:
+    self: TABLES (KeywordWithGroupedXXX)
+    group:
+      self: ( (GroupedExprs)
+      exprs:
+      - self: . (DotOperator)
+        destination:
+          self: DESTINATION (Constraint)
+          columns:
+            self: ( (GroupedExprs)
+            exprs:
+            - self: aid (Identifier)
+            rparen:
+              self: ) (Symbol)
+          key:
+            self: KEY (Keyword)
+          references:
+            self: REFERENCES (KeywordWithExpr)
+            expr:
+              self: ( (CallingFunction)
+              args:
+              - self: id (Identifier)
+              func:
+                self: account (Identifier)
+              rparen:
+                self: ) (Symbol)
+        labels:
+        - self: LABEL (GraphLabel)
+          name:
+            self: Owns (Identifier)
+        left:
+          self: dataset (Identifier)
+        right:
+          self: owns (Identifier)
+        source:
+          self: SOURCE (Constraint)
+          columns:
+            self: ( (GroupedExprs)
+            exprs:
+            - self: pid (Identifier)
+            rparen:
+              self: ) (Symbol)
+          key:
+            self: KEY (Keyword)
+          references:
+            self: REFERENCES (KeywordWithExpr)
+            expr:
+              self: ( (CallingFunction)
+              args:
+              - self: id (Identifier)
+              func:
+                self: person (Identifier)
+              rparen:
+                self: ) (Symbol)
+      rparen:
+        self: ) (Symbol)
+ident:
+  self: . (DotOperator)
+  left:
+    self: dataset (Identifier)
+  right:
+    self: graph (Identifier)
+if_not_exists:
+- self: IF (Keyword)
+- self: NOT (Keyword)
+- self: EXISTS (Keyword)
+node_tables:
+  self: NODE (KeywordSequence)
+  next_keyword:
+    self: TABLES (KeywordWithGroupedXXX)
+    group:
+      self: ( (GroupedExprs)
+      exprs:
+      - self: . (DotOperator)
+        alias:
+          self: p (Identifier)
+        as:
+          self: AS (Keyword)
+        comma:
+          self: , (Symbol)
+        key:
+          self: KEY (KeywordWithGroupedXXX)
+          group:
+            self: ( (GroupedExprs)
+            exprs:
+            - self: id (Identifier)
+            rparen:
+              self: ) (Symbol)
+        labels:
+        - self: LABEL (GraphLabel)
+          name:
+            self: Person (Identifier)
+          properties:
+            self: PROPERTIES (KeywordWithGroupedXXX)
+            group:
+              self: ( (GroupedExprs)
+              exprs:
+              - self: name (Identifier)
+                comma:
+                  self: , (Symbol)
+              - self: age (Identifier)
+                alias:
+                  self: years (Identifier)
+                as:
+                  self: AS (Keyword)
+              rparen:
+                self: ) (Symbol)
+        - self: LABEL (GraphLabel)
+          name:
+            self: Entity (Identifier)
+          properties:
+            self: NO (KeywordSequence)
+            next_keyword:
+              self: PROPERTIES (Keyword)
+        left:
+          self: dataset (Identifier)
+        right:
+          self: person (Identifier)
+      - self: . (DotOperator)
+        labels:
+        - self: LABEL (GraphLabel)
+          default:
+            self: DEFAULT (Keyword)
+          properties:
+            self: PROPERTIES (KeywordSequence)
+            next_keyword:
+              self: ARE (KeywordSequence)
+              next_keyword:
+                self: ALL (KeywordSequence)
+                next_keyword:
+                  self: COLUMNS (KeywordSequence)
+                  next_keyword:
+                    self: EXCEPT (KeywordWithGroupedXXX)
+                    group:
+                      self: ( (GroupedExprs)
+                      exprs:
+                      - self: secret (Identifier)
+                      rparen:
+                        self: ) (Symbol)
+        left:
+          self: dataset (Identifier)
+        right:
+          self: account (Identifier)
+      rparen:
+        self: ) (Symbol)
+options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      left:
+        self: description (Identifier)
+      right:
+        self: 'graph' (StringLiteral)
+    rparen:
+      self: ) (Symbol)
+or_replace:
+- self: OR (Keyword)
+- self: REPLACE (Keyword)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: PROPERTY (KeywordSequence)
+  next_keyword:
+    self: GRAPH (Keyword)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE PROPERTY GRAPH graph NODE TABLES (person)
+",
+            "\
+self: CREATE (CreatePropertyGraphStatement)
+ident:
+  self: graph (Identifier)
+node_tables:
+  self: NODE (KeywordSequence)
+  next_keyword:
+    self: TABLES (KeywordWithGroupedXXX)
+    group:
+      self: ( (GroupedExprs)
+      exprs:
+      - self: person (Identifier)
+      rparen:
+        self: ) (Symbol)
+what:
+  self: PROPERTY (KeywordSequence)
+  next_keyword:
+    self: GRAPH (Keyword)
 ",
             0,
         )),
@@ -3466,6 +3678,30 @@ what:
   self: SEARCH (KeywordSequence)
   next_keyword:
     self: INDEX (Keyword)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+DROP PROPERTY GRAPH IF EXISTS dataset.graph;
+",
+            "\
+self: DROP (DropStatement)
+ident:
+  self: . (DotOperator)
+  left:
+    self: dataset (Identifier)
+  right:
+    self: graph (Identifier)
+if_exists:
+- self: IF (Keyword)
+- self: EXISTS (Keyword)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: PROPERTY (KeywordSequence)
+  next_keyword:
+    self: GRAPH (Keyword)
 ",
             0,
         )),
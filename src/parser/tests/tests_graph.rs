@@ -0,0 +1,485 @@
+use super::*;
+
+#[test]
+fn test_parse_code_graph() {
+    let test_cases: Vec<Box<dyn TestCase>> = vec![
+        Box::new(SuccessTestCase::new(
+            "\
+GRAPH dataset.graph
+MATCH (p:Person {name: 'a', age: 1})-[o:Owns WHERE o.x > 1]->(a:Account|Entity), (a)<-[e]-(b IS !%)
+WHERE p.age > 1
+RETURN p.name AS name, a.id;
+",
+            "\
+self: GRAPH (GraphStatement)
+ident:
+  self: . (DotOperator)
+  left:
+    self: dataset (Identifier)
+  right:
+    self: graph (Identifier)
+operators:
+- self: MATCH (GraphMatchOperator)
+  patterns:
+  - self: ( (GraphNodePattern)
+    label:
+      self: : (KeywordWithExpr)
+      expr:
+        self: Person (Identifier)
+    properties:
+      self: { (GraphPropertySpec)
+      exprs:
+      - self: : (BinaryOperator)
+        comma:
+          self: , (Symbol)
+        left:
+          self: name (Identifier)
+        right:
+          self: 'a' (StringLiteral)
+      - self: : (BinaryOperator)
+        left:
+          self: age (Identifier)
+        right:
+          self: 1 (NumericLiteral)
+      rbrace:
+        self: } (Symbol)
+    rparen:
+      self: ) (Symbol)
+    var:
+      self: p (Identifier)
+  - self: -[ (GraphEdgePattern)
+    label:
+      self: : (KeywordWithExpr)
+      expr:
+        self: Owns (Identifier)
+    rbracket:
+      self: ]-> (Symbol)
+    var:
+      self: o (Identifier)
+    where:
+      self: WHERE (KeywordWithExpr)
+      expr:
+        self: > (BinaryOperator)
+        left:
+          self: . (DotOperator)
+          left:
+            self: o (Identifier)
+          right:
+            self: x (Identifier)
+        right:
+          self: 1 (NumericLiteral)
+  - self: ( (GraphNodePattern)
+    comma:
+      self: , (Symbol)
+    label:
+      self: : (KeywordWithExpr)
+      expr:
+        self: | (BinaryOperator)
+        left:
+          self: Account (Identifier)
+        right:
+          self: Entity (Identifier)
+    rparen:
+      self: ) (Symbol)
+    var:
+      self: a (Identifier)
+  - self: ( (GraphNodePattern)
+    rparen:
+      self: ) (Symbol)
+    var:
+      self: a (Identifier)
+  - self: <-[ (GraphEdgePattern)
+    rbracket:
+      self: ]- (Symbol)
+    var:
+      self: e (Identifier)
+  - self: ( (GraphNodePattern)
+    label:
+      self: IS (KeywordWithExpr)
+      expr:
+        self: ! (UnaryOperator)
+        right:
+          self: % (Symbol)
+    rparen:
+      self: ) (Symbol)
+    var:
+      self: b (Identifier)
+  where:
+    self: WHERE (KeywordWithExpr)
+    expr:
+      self: > (BinaryOperator)
+      left:
+        self: . (DotOperator)
+        left:
+          self: p (Identifier)
+        right:
+          self: age (Identifier)
+      right:
+        self: 1 (NumericLiteral)
+- self: RETURN (BasePipeOperator)
+  exprs:
+  - self: . (DotOperator)
+    alias:
+      self: name (Identifier)
+    as:
+      self: AS (Keyword)
+    comma:
+      self: , (Symbol)
+    left:
+      self: p (Identifier)
+    right:
+      self: name (Identifier)
+  - self: . (DotOperator)
+    left:
+      self: a (Identifier)
+    right:
+      self: id (Identifier)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+GRAPH graph
+OPTIONAL MATCH (a)<-(b)-(c)<->(d)-[IS A & (B | C)]->{1, 3}(e)
+LET x = 1
+FILTER WHERE x = 1
+RETURN DISTINCT a, b
+ORDER BY a DESC
+LIMIT 10
+NEXT
+MATCH (n) RETURN n
+UNION ALL
+MATCH (n) RETURN n
+",
+            "\
+self: GRAPH (GraphStatement)
+ident:
+  self: graph (Identifier)
+operators:
+- self: MATCH (GraphMatchOperator)
+  optional:
+    self: OPTIONAL (Keyword)
+  patterns:
+  - self: ( (GraphNodePattern)
+    rparen:
+      self: ) (Symbol)
+    var:
+      self: a (Identifier)
+  - self: <- (GraphEdgePattern)
+  - self: ( (GraphNodePattern)
+    rparen:
+      self: ) (Symbol)
+    var:
+      self: b (Identifier)
+  - self: - (GraphEdgePattern)
+  - self: ( (GraphNodePattern)
+    rparen:
+      self: ) (Symbol)
+    var:
+      self: c (Identifier)
+  - self: <-> (GraphEdgePattern)
+  - self: ( (GraphNodePattern)
+    rparen:
+      self: ) (Symbol)
+    var:
+      self: d (Identifier)
+  - self: -[ (GraphEdgePattern)
+    label:
+      self: IS (KeywordWithExpr)
+      expr:
+        self: & (BinaryOperator)
+        left:
+          self: A (Identifier)
+        right:
+          self: ( (GroupedExpr)
+          expr:
+            self: | (BinaryOperator)
+            left:
+              self: B (Identifier)
+            right:
+              self: C (Identifier)
+          rparen:
+            self: ) (Symbol)
+    quantifier:
+      self: { (PatternQuantifier)
+      comma:
+        self: , (Symbol)
+      max:
+        self: 3 (NumericLiteral)
+      min:
+        self: 1 (NumericLiteral)
+      rbrace:
+        self: } (Symbol)
+    rbracket:
+      self: ]-> (Symbol)
+  - self: ( (GraphNodePattern)
+    rparen:
+      self: ) (Symbol)
+    var:
+      self: e (Identifier)
+- self: LET (BasePipeOperator)
+  exprs:
+  - self: = (BinaryOperator)
+    left:
+      self: x (Identifier)
+    right:
+      self: 1 (NumericLiteral)
+- self: FILTER (BasePipeOperator)
+  exprs:
+  - self: = (BinaryOperator)
+    left:
+      self: x (Identifier)
+    right:
+      self: 1 (NumericLiteral)
+  keywords:
+    self: WHERE (Keyword)
+- self: RETURN (BasePipeOperator)
+  exprs:
+  - self: a (Identifier)
+    comma:
+      self: , (Symbol)
+  - self: b (Identifier)
+  keywords:
+    self: DISTINCT (Keyword)
+- self: ORDER (BasePipeOperator)
+  exprs:
+  - self: a (Identifier)
+    order:
+      self: DESC (Keyword)
+  keywords:
+    self: BY (Keyword)
+- self: LIMIT (BasePipeOperator)
+  exprs:
+  - self: 10 (NumericLiteral)
+- self: NEXT (Keyword)
+- self: MATCH (GraphMatchOperator)
+  patterns:
+  - self: ( (GraphNodePattern)
+    rparen:
+      self: ) (Symbol)
+    var:
+      self: n (Identifier)
+- self: RETURN (BasePipeOperator)
+  exprs:
+  - self: n (Identifier)
+- self: UNION (KeywordSequence)
+  next_keyword:
+    self: ALL (Keyword)
+- self: MATCH (GraphMatchOperator)
+  patterns:
+  - self: ( (GraphNodePattern)
+    rparen:
+      self: ) (Symbol)
+    var:
+      self: n (Identifier)
+- self: RETURN (BasePipeOperator)
+  exprs:
+  - self: n (Identifier)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT *
+FROM GRAPH_TABLE(
+  dataset.graph
+  MATCH (a)-[e]->(b)
+  COLUMNS (a.id, b.id AS bid)
+) AS gt
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: * (Asterisk)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: GRAPH_TABLE (GraphTableExpr)
+    alias:
+      self: gt (Identifier)
+    as:
+      self: AS (Keyword)
+    config:
+      self: ( (GraphTableConfig)
+      columns:
+        self: COLUMNS (KeywordWithGroupedXXX)
+        group:
+          self: ( (GroupedExprs)
+          exprs:
+          - self: . (DotOperator)
+            comma:
+              self: , (Symbol)
+            left:
+              self: a (Identifier)
+            right:
+              self: id (Identifier)
+          - self: . (DotOperator)
+            alias:
+              self: bid (Identifier)
+            as:
+              self: AS (Keyword)
+            left:
+              self: b (Identifier)
+            right:
+              self: id (Identifier)
+          rparen:
+            self: ) (Symbol)
+      ident:
+        self: . (DotOperator)
+        left:
+          self: dataset (Identifier)
+        right:
+          self: graph (Identifier)
+      operators:
+      - self: MATCH (GraphMatchOperator)
+        patterns:
+        - self: ( (GraphNodePattern)
+          rparen:
+            self: ) (Symbol)
+          var:
+            self: a (Identifier)
+        - self: -[ (GraphEdgePattern)
+          rbracket:
+            self: ]-> (Symbol)
+          var:
+            self: e (Identifier)
+        - self: ( (GraphNodePattern)
+          rparen:
+            self: ) (Symbol)
+          var:
+            self: b (Identifier)
+      rparen:
+        self: ) (Symbol)
+",
+            0,
+        )),
+        // not edge patterns
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT arr[0]-1, x<-1, x-[0]
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: - (BinaryOperator)
+  comma:
+    self: , (Symbol)
+  left:
+    self: [ (AccessOperator)
+    left:
+      self: arr (Identifier)
+    right:
+      self: 0 (NumericLiteral)
+    rparen:
+      self: ] (Symbol)
+  right:
+    self: 1 (NumericLiteral)
+- self: < (BinaryOperator)
+  comma:
+    self: , (Symbol)
+  left:
+    self: x (Identifier)
+  right:
+    self: - (UnaryOperator)
+    right:
+      self: 1 (NumericLiteral)
+- self: - (BinaryOperator)
+  left:
+    self: x (Identifier)
+  right:
+    self: [ (ArrayLiteral)
+    exprs:
+    - self: 0 (NumericLiteral)
+    rparen:
+      self: ] (Symbol)
+",
+            0,
+        )),
+        // not a graph query
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT COUNT(*)-[1,2][OFFSET(0)], (a)<-[1][OFFSET(0)] FROM t
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: - (BinaryOperator)
+  comma:
+    self: , (Symbol)
+  left:
+    self: ( (CallingFunction)
+    args:
+    - self: * (Asterisk)
+    func:
+      self: COUNT (Identifier)
+    rparen:
+      self: ) (Symbol)
+  right:
+    self: [ (AccessOperator)
+    left:
+      self: [ (ArrayLiteral)
+      exprs:
+      - self: 1 (NumericLiteral)
+        comma:
+          self: , (Symbol)
+      - self: 2 (NumericLiteral)
+      rparen:
+        self: ] (Symbol)
+    right:
+      self: ( (CallingFunction)
+      args:
+      - self: 0 (NumericLiteral)
+      func:
+        self: OFFSET (Identifier)
+      rparen:
+        self: ) (Symbol)
+    rparen:
+      self: ] (Symbol)
+- self: < (BinaryOperator)
+  left:
+    self: ( (GroupedExpr)
+    expr:
+      self: a (Identifier)
+    rparen:
+      self: ) (Symbol)
+  right:
+    self: - (UnaryOperator)
+    right:
+      self: [ (AccessOperator)
+      left:
+        self: [ (ArrayLiteral)
+        exprs:
+        - self: 1 (NumericLiteral)
+        rparen:
+          self: ] (Symbol)
+      right:
+        self: ( (CallingFunction)
+        args:
+        - self: 0 (NumericLiteral)
+        func:
+          self: OFFSET (Identifier)
+        rparen:
+          self: ) (Symbol)
+      rparen:
+        self: ] (Symbol)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: t (Identifier)
+",
+            0,
+        )),
+        Box::new(ErrorTestCase::new(
+            "\
+GRAPH graph MATCH (a b c)
+",
+            1,
+            22,
+        )),
+    ];
+    for t in test_cases {
+        t.test();
+    }
+}
@@ -354,8 +354,223 @@ overwrite_partitions:
 ",
             0,
         )),
+        // ----- macro -----
+        Box::new(SuccessTestCase::new(
+            "\
+DEFINE MACRO add $1 + $2;
+",
+            "\
+self: DEFINE (DefineMacroStatement)
+body:
+- self: $1 (Unknown)
+- self: + (Unknown)
+- self: $2 (Unknown)
+ident:
+  self: add (Identifier)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: MACRO (Keyword)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+DEFINE MACRO empty;
+",
+            "\
+self: DEFINE (DefineMacroStatement)
+ident:
+  self: empty (Identifier)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: MACRO (Keyword)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+DEFINE MACRO tbl `project.dataset.table`;
+SELECT $add(x, 1), $cols FROM $tbl() AS t WHERE $1;
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: $add (MacroInvocation)
+  args:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: x (Identifier)
+      comma:
+        self: , (Symbol)
+    - self: 1 (NumericLiteral)
+    rparen:
+      self: ) (Symbol)
+  comma:
+    self: , (Symbol)
+- self: $cols (MacroInvocation)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: $tbl (MacroInvocation)
+    alias:
+      self: t (Identifier)
+    args:
+      self: ( (GroupedExprs)
+      rparen:
+        self: ) (Symbol)
+    as:
+      self: AS (Keyword)
+semicolon:
+  self: ; (Symbol)
+where:
+  self: WHERE (KeywordWithExpr)
+  expr:
+    self: $1 (MacroInvocation)
+",
+            1,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+$query();
+",
+            "\
+self: $query (MacroInvocation)
+args:
+  self: ( (GroupedExprs)
+  rparen:
+    self: ) (Symbol)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+    ];
+    for t in test_cases {
+        t.test();
+    }
+}
+
+#[test]
+fn test_parse_code_sqlx() {
+    let test_cases = vec![
+        Box::new(SuccessTestCase::new_sqlx(
+            "\
+config {
+  type: 'table',
+  tags: ['{x}'],
+}
+SELECT 1
+",
+            "\
+self: config {
+  type: 'table',
+  tags: ['{x}'],
+} (SqlxBlock)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new_sqlx(
+            "\
+config { type: 'table' }
+SELECT ${self()}.a FROM ${ref(\"t\")} WHERE x = ${when(incremental(), 1, 2)}
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: . (DotOperator)
+  left:
+    self: ${self()} (Template)
+  right:
+    self: a (Identifier)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: ${ref(\"t\")} (Identifier)
+where:
+  self: WHERE (KeywordWithExpr)
+  expr:
+    self: = (BinaryOperator)
+    left:
+      self: x (Identifier)
+    right:
+      self: ${when(incremental(), 1, 2)} (Template)
+",
+            1,
+        )),
+        Box::new(SuccessTestCase::new_sqlx(
+            "\
+pre_operations {
+  DECLARE x INT64 DEFAULT 1;
+}
+post_operations { ${\"GRANT\"} }
+SELECT * FROM ${ref(\"dataset\", \"t\")} AS t JOIN ${ref({name: \"u\"})} USING(id)
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: * (Asterisk)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: JOIN (JoinOperator)
+    left:
+      self: ${ref(\"dataset\", \"t\")} (Identifier)
+      alias:
+        self: t (Identifier)
+      as:
+        self: AS (Keyword)
+    right:
+      self: ${ref({name: \"u\"})} (Identifier)
+    using:
+      self: ( (CallingFunction)
+      args:
+      - self: id (Identifier)
+      func:
+        self: USING (Identifier)
+      rparen:
+        self: ) (Symbol)
+",
+            2,
+        )),
     ];
     for t in test_cases {
         t.test();
     }
 }
+
+#[test]
+fn test_parse_code_legacy_sql() {
+    use crate::error::ErrorKind;
+    let test_cases = vec![
+        ("#legacySQL\nSELECT * FROM [p:d.t]", (1, 1)),
+        ("-- header\n  #LEGACYSQL \nSELECT 1", (2, 3)),
+        ("SELECT x FROM [bigquery-public-data:samples.shakespeare]", (1, 15)),
+        (
+            "SELECT * FROM t JOIN TABLE_DATE_RANGE([d.t_], TIMESTAMP('2020-01-01'), CURRENT_TIMESTAMP())",
+            (1, 22),
+        ),
+    ];
+    for (code, (line, column)) in test_cases {
+        let l = Lexer::new(code.to_string());
+        let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."));
+        let error = p
+            .parse_code()
+            .expect_err("Unexpectedly successed to parse code.");
+        assert_eq!(error.kind, ErrorKind::LegacySql, "{}", code);
+        assert_eq!((error.line, error.column), (line, column), "{}", code);
+    }
+    // standard SQL
+    for code in ["#standardSQL\nSELECT 1", "SELECT [1, 2][OFFSET(0)] FROM t"] {
+        let l = Lexer::new(code.to_string());
+        let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."));
+        assert!(p.parse_code().is_ok(), "{}", code);
+    }
+    let l = Lexer::new("SELECT 1 FROM".to_string());
+    let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."));
+    let error = p
+        .parse_code()
+        .expect_err("Unexpectedly successed to parse code.");
+    assert_eq!(error.kind, ErrorKind::Syntax);
+}
@@ -506,6 +506,47 @@ right:
           - self: b (Identifier)
         rparen:
           self: ) (Symbol)
+",
+            0,
+        )),
+        // DIFFERENTIAL_PRIVACY
+        Box::new(SuccessTestCase::new(
+            "\
+FROM t
+|> SELECT WITH DIFFERENTIAL_PRIVACY OPTIONS(epsilon = 1) SUM(x)
+",
+            "\
+self: |> (PipeStatement)
+left:
+  self: FROM (FromStatement)
+  expr:
+    self: t (Identifier)
+right:
+  self: SELECT (SelectPipeOperator)
+  differential_privacy:
+    self: WITH (DifferentialPrivacyClause)
+    differential_privacy:
+      self: DIFFERENTIAL_PRIVACY (Keyword)
+    options:
+      self: OPTIONS (KeywordWithGroupedXXX)
+      group:
+        self: ( (GroupedExprs)
+        exprs:
+        - self: = (BinaryOperator)
+          left:
+            self: epsilon (Identifier)
+          right:
+            self: 1 (NumericLiteral)
+        rparen:
+          self: ) (Symbol)
+  exprs:
+  - self: ( (CallingFunction)
+    args:
+    - self: x (Identifier)
+    func:
+      self: SUM (Identifier)
+    rparen:
+      self: ) (Symbol)
 ",
             0,
         )),
@@ -624,6 +665,53 @@ right:
       order:
         self: DESC (Keyword)
     - self: col2 (Identifier)
+",
+            0,
+        )),
+        // AGGREGATION_THRESHOLD
+        Box::new(SuccessTestCase::new(
+            "\
+FROM t
+|> AGGREGATE WITH AGGREGATION_THRESHOLD OPTIONS(threshold = 3) COUNT(*) GROUP BY col1
+",
+            "\
+self: |> (PipeStatement)
+left:
+  self: FROM (FromStatement)
+  expr:
+    self: t (Identifier)
+right:
+  self: AGGREGATE (AggregatePipeOperator)
+  aggregation_threshold:
+    self: WITH (AggregationThresholdClause)
+    aggregation_threshold:
+      self: AGGREGATION_THRESHOLD (Keyword)
+    options:
+      self: OPTIONS (KeywordWithGroupedXXX)
+      group:
+        self: ( (GroupedExprs)
+        exprs:
+        - self: = (BinaryOperator)
+          left:
+            self: threshold (Identifier)
+          right:
+            self: 3 (NumericLiteral)
+        rparen:
+          self: ) (Symbol)
+  exprs:
+  - self: ( (CallingFunction)
+    args:
+    - self: * (Asterisk)
+    func:
+      self: COUNT (Identifier)
+    rparen:
+      self: ) (Symbol)
+  groupby:
+    self: GROUP (GroupByExprs)
+    by:
+      self: BY (Keyword)
+    exprs:
+    - self: col1 (Identifier)
 ",
             0,
         )),
@@ -1204,6 +1292,399 @@ right:
       self: USING (Identifier)
     rparen:
       self: ) (Symbol)
+",
+            0,
+        )),
+        // ----- assert pipe operator -----
+        Box::new(SuccessTestCase::new(
+            "\
+FROM t
+|> ASSERT x > 0, 'x must be positive', x
+",
+            "\
+self: |> (PipeStatement)
+left:
+  self: FROM (FromStatement)
+  expr:
+    self: t (Identifier)
+right:
+  self: ASSERT (AssertPipeOperator)
+  exprs:
+  - self: > (BinaryOperator)
+    comma:
+      self: , (Symbol)
+    left:
+      self: x (Identifier)
+    right:
+      self: 0 (NumericLiteral)
+  - self: 'x must be positive' (StringLiteral)
+    comma:
+      self: , (Symbol)
+  - self: x (Identifier)
+",
+            0,
+        )),
+        // ----- log pipe operator -----
+        Box::new(SuccessTestCase::new(
+            "\
+FROM t
+|> LOG
+|> LOG (
+  |> WHERE x > 0
+  |> SELECT x
+)
+|> LOG ()
+",
+            "\
+self: |> (PipeStatement)
+left:
+  self: |> (PipeStatement)
+  left:
+    self: |> (PipeStatement)
+    left:
+      self: FROM (FromStatement)
+      expr:
+        self: t (Identifier)
+    right:
+      self: LOG (LogPipeOperator)
+  right:
+    self: LOG (LogPipeOperator)
+    subpipeline:
+      self: ( (Subpipeline)
+      rparen:
+        self: ) (Symbol)
+      stmt:
+        self: |> (PipeStatement)
+        left:
+          self: |> (PipeStatement)
+          right:
+            self: WHERE (BasePipeOperator)
+            exprs:
+            - self: > (BinaryOperator)
+              left:
+                self: x (Identifier)
+              right:
+                self: 0 (NumericLiteral)
+        right:
+          self: SELECT (SelectPipeOperator)
+          exprs:
+          - self: x (Identifier)
+right:
+  self: LOG (LogPipeOperator)
+  subpipeline:
+    self: ( (Subpipeline)
+    rparen:
+      self: ) (Symbol)
+",
+            0,
+        )),
+        // ----- describe pipe operator -----
+        Box::new(SuccessTestCase::new(
+            "\
+FROM t
+|> DESCRIBE
+|> STATIC_DESCRIBE;
+",
+            "\
+self: |> (PipeStatement)
+left:
+  self: |> (PipeStatement)
+  left:
+    self: FROM (FromStatement)
+    expr:
+      self: t (Identifier)
+  right:
+    self: DESCRIBE (DescribePipeOperator)
+right:
+  self: STATIC_DESCRIBE (DescribePipeOperator)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+        // ----- window pipe operator -----
+        Box::new(SuccessTestCase::new(
+            "\
+FROM t
+|> WINDOW SUM(x) OVER (ORDER BY y) AS total, RANK() OVER w
+",
+            "\
+self: |> (PipeStatement)
+left:
+  self: FROM (FromStatement)
+  expr:
+    self: t (Identifier)
+right:
+  self: WINDOW (WindowPipeOperator)
+  exprs:
+  - self: ( (CallingFunction)
+    alias:
+      self: total (Identifier)
+    args:
+    - self: x (Identifier)
+    as:
+      self: AS (Keyword)
+    comma:
+      self: , (Symbol)
+    func:
+      self: SUM (Identifier)
+    over:
+      self: OVER (OverClause)
+      window:
+        self: ( (WindowSpecification)
+        orderby:
+          self: ORDER (XXXByExprs)
+          by:
+            self: BY (Keyword)
+          exprs:
+          - self: y (Identifier)
+        rparen:
+          self: ) (Symbol)
+    rparen:
+      self: ) (Symbol)
+  - self: ( (CallingFunction)
+    func:
+      self: RANK (Identifier)
+    over:
+      self: OVER (OverClause)
+      window:
+        self: w (Identifier)
+    rparen:
+      self: ) (Symbol)
+",
+            0,
+        )),
+        // ----- recursive union pipe operator -----
+        Box::new(SuccessTestCase::new(
+            "\
+FROM t
+|> RECURSIVE UNION ALL (
+  |> WHERE depth < 10
+  |> SET depth = depth + 1
+) AS r
+",
+            "\
+self: |> (PipeStatement)
+left:
+  self: FROM (FromStatement)
+  expr:
+    self: t (Identifier)
+right:
+  self: RECURSIVE (RecursiveUnionPipeOperator)
+  alias:
+    self: r (Identifier)
+  as:
+    self: AS (Keyword)
+  keywords:
+    self: ALL (Keyword)
+  subpipeline:
+    self: ( (Subpipeline)
+    rparen:
+      self: ) (Symbol)
+    stmt:
+      self: |> (PipeStatement)
+      left:
+        self: |> (PipeStatement)
+        right:
+          self: WHERE (BasePipeOperator)
+          exprs:
+          - self: < (BinaryOperator)
+            left:
+              self: depth (Identifier)
+            right:
+              self: 10 (NumericLiteral)
+      right:
+        self: SET (BasePipeOperator)
+        exprs:
+        - self: = (BinaryOperator)
+          left:
+            self: depth (Identifier)
+          right:
+            self: + (BinaryOperator)
+            left:
+              self: depth (Identifier)
+            right:
+              self: 1 (NumericLiteral)
+  union:
+    self: UNION (Keyword)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+FROM t
+|> RECURSIVE UNION DISTINCT BY NAME (SELECT * FROM r) r
+",
+            "\
+self: |> (PipeStatement)
+left:
+  self: FROM (FromStatement)
+  expr:
+    self: t (Identifier)
+right:
+  self: RECURSIVE (RecursiveUnionPipeOperator)
+  alias:
+    self: r (Identifier)
+  by:
+    self: BY (KeywordSequence)
+    next_keyword:
+      self: NAME (Keyword)
+  exprs:
+  - self: ( (GroupedStatement)
+    rparen:
+      self: ) (Symbol)
+    stmt:
+      self: SELECT (SelectStatement)
+      exprs:
+      - self: * (Asterisk)
+      from:
+        self: FROM (KeywordWithExpr)
+        expr:
+          self: r (Identifier)
+  keywords:
+    self: DISTINCT (Keyword)
+  union:
+    self: UNION (Keyword)
+",
+            0,
+        )),
+        // ----- fork and tee pipe operator -----
+        Box::new(SuccessTestCase::new(
+            "\
+FROM t
+|> FORK (|> WHERE x = 1), (
+  |> AGGREGATE COUNT(*)
+)
+|> TEE (|> LIMIT 1)
+",
+            "\
+self: |> (PipeStatement)
+left:
+  self: |> (PipeStatement)
+  left:
+    self: FROM (FromStatement)
+    expr:
+      self: t (Identifier)
+  right:
+    self: FORK (ForkPipeOperator)
+    subpipelines:
+    - self: ( (Subpipeline)
+      comma:
+        self: , (Symbol)
+      rparen:
+        self: ) (Symbol)
+      stmt:
+        self: |> (PipeStatement)
+        right:
+          self: WHERE (BasePipeOperator)
+          exprs:
+          - self: = (BinaryOperator)
+            left:
+              self: x (Identifier)
+            right:
+              self: 1 (NumericLiteral)
+    - self: ( (Subpipeline)
+      rparen:
+        self: ) (Symbol)
+      stmt:
+        self: |> (PipeStatement)
+        right:
+          self: AGGREGATE (AggregatePipeOperator)
+          exprs:
+          - self: ( (CallingFunction)
+            args:
+            - self: * (Asterisk)
+            func:
+              self: COUNT (Identifier)
+            rparen:
+              self: ) (Symbol)
+right:
+  self: TEE (ForkPipeOperator)
+  subpipelines:
+  - self: ( (Subpipeline)
+    rparen:
+      self: ) (Symbol)
+    stmt:
+      self: |> (PipeStatement)
+      right:
+        self: LIMIT (LimitPipeOperator)
+        exprs:
+        - self: 1 (NumericLiteral)
+",
+            0,
+        )),
+        // ----- if pipe operator -----
+        Box::new(SuccessTestCase::new(
+            "\
+FROM t
+|> IF @flag THEN (
+  |> WHERE x = 1
+) ELSEIF @other THEN (|> WHERE x = 2) ELSE (
+  |> WHERE x = 3
+)
+",
+            "\
+self: |> (PipeStatement)
+left:
+  self: FROM (FromStatement)
+  expr:
+    self: t (Identifier)
+right:
+  self: IF (IfPipeOperator)
+  condition:
+    self: @flag (Parameter)
+  else:
+    self: ELSE (KeywordWithExpr)
+    expr:
+      self: ( (Subpipeline)
+      rparen:
+        self: ) (Symbol)
+      stmt:
+        self: |> (PipeStatement)
+        right:
+          self: WHERE (BasePipeOperator)
+          exprs:
+          - self: = (BinaryOperator)
+            left:
+              self: x (Identifier)
+            right:
+              self: 3 (NumericLiteral)
+  elseifs:
+  - self: ELSEIF (ElseIfClause)
+    condition:
+      self: @other (Parameter)
+    then:
+      self: THEN (KeywordWithExpr)
+      expr:
+        self: ( (Subpipeline)
+        rparen:
+          self: ) (Symbol)
+        stmt:
+          self: |> (PipeStatement)
+          right:
+            self: WHERE (BasePipeOperator)
+            exprs:
+            - self: = (BinaryOperator)
+              left:
+                self: x (Identifier)
+              right:
+                self: 2 (NumericLiteral)
+  then:
+    self: THEN (KeywordWithExpr)
+    expr:
+      self: ( (Subpipeline)
+      rparen:
+        self: ) (Symbol)
+      stmt:
+        self: |> (PipeStatement)
+        right:
+          self: WHERE (BasePipeOperator)
+          exprs:
+          - self: = (BinaryOperator)
+            left:
+              self: x (Identifier)
+            right:
+              self: 1 (NumericLiteral)
 ",
             0,
         )),
@@ -1144,3 +1144,162 @@ then:
         t.test();
     }
 }
+
+#[test]
+fn test_parse_code_dynamic_sql() {
+    let test_cases = vec![
+        Box::new(SuccessTestCase::new_dynamic_sql(
+            "\
+EXECUTE IMMEDIATE 'SELECT 1; SELECT 2' INTO x;
+",
+            "\
+self: EXECUTE (ExecuteStatement)
+immediate:
+  self: IMMEDIATE (Keyword)
+into:
+  self: INTO (KeywordWithExprs)
+  exprs:
+  - self: x (Identifier)
+semicolon:
+  self: ; (Symbol)
+sql_expr:
+  self: 'SELECT 1; SELECT 2' (StringLiteral)
+stmts:
+- self: SELECT (SelectStatement)
+  exprs:
+  - self: 1 (NumericLiteral)
+  semicolon:
+    self: ; (Symbol)
+- self: SELECT (SelectStatement)
+  exprs:
+  - self: 2 (NumericLiteral)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new_dynamic_sql(
+            "\
+EXECUTE IMMEDIATE CONCAT('SELECT * FROM t WHERE id = ', ?) USING 1;
+",
+            "\
+self: EXECUTE (ExecuteStatement)
+immediate:
+  self: IMMEDIATE (Keyword)
+semicolon:
+  self: ; (Symbol)
+sql_expr:
+  self: ( (CallingFunction)
+  args:
+  - self: 'SELECT * FROM t WHERE id = ' (StringLiteral)
+    comma:
+      self: , (Symbol)
+  - self: ? (Parameter)
+  func:
+    self: CONCAT (Identifier)
+  rparen:
+    self: ) (Symbol)
+stmts:
+- self: SELECT (SelectStatement)
+  exprs:
+  - self: * (Asterisk)
+  from:
+    self: FROM (KeywordWithExpr)
+    expr:
+      self: t (Identifier)
+  where:
+    self: WHERE (KeywordWithExpr)
+    expr:
+      self: = (BinaryOperator)
+      left:
+        self: id (Identifier)
+      right:
+        self: ? (Parameter)
+using:
+  self: USING (KeywordWithExprs)
+  exprs:
+  - self: 1 (NumericLiteral)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new_dynamic_sql(
+            "\
+EXECUTE IMMEDIATE 'SELECT ' || 'x FROM ' || @tbl;
+",
+            "\
+self: EXECUTE (ExecuteStatement)
+immediate:
+  self: IMMEDIATE (Keyword)
+semicolon:
+  self: ; (Symbol)
+sql_expr:
+  self: || (BinaryOperator)
+  left:
+    self: || (BinaryOperator)
+    left:
+      self: 'SELECT ' (StringLiteral)
+    right:
+      self: 'x FROM ' (StringLiteral)
+  right:
+    self: @tbl (Parameter)
+stmts:
+- self: SELECT (SelectStatement)
+  exprs:
+  - self: x (Identifier)
+  from:
+    self: FROM (KeywordWithExpr)
+    expr:
+      self: @tbl (Identifier)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new_dynamic_sql(
+            "\
+EXECUTE IMMEDIATE sql_variable;
+",
+            "\
+self: EXECUTE (ExecuteStatement)
+immediate:
+  self: IMMEDIATE (Keyword)
+semicolon:
+  self: ; (Symbol)
+sql_expr:
+  self: sql_variable (Identifier)
+",
+            0,
+        )),
+    ];
+    for t in test_cases {
+        t.test();
+    }
+}
+
+#[test]
+fn test_parse_code_dynamic_sql_position() {
+    let code = "\
+EXECUTE IMMEDIATE '''SELECT
+  \\x61 FROM t''';
+EXECUTE IMMEDIATE CONCAT('SELECT ', ?, ' FROM');
+";
+    let l = Lexer::new(code.to_string());
+    let mut p = Parser::new_dynamic_sql(l.tokenize_code().expect("Failed to tokenize code."));
+    let stmts = p.parse_code().expect("Failed to parse code.");
+    // the error of the dynamic SQL does not prevent the other statements from being parsed
+    assert!(stmts[0].get_node_vec("stmts").is_some());
+    let Some(ContentType::Error(error)) = stmts[1].children.get("error") else {
+        panic!("the error of the dynamic SQL is not recorded");
+    };
+    // the last char of ' FROM'
+    assert_eq!((error.line, error.column), (3, 45));
+
+    let code = code.split_once(";\n").unwrap().0;
+    let l = Lexer::new(code.to_string());
+    let mut p = Parser::new_dynamic_sql(l.tokenize_code().expect("Failed to tokenize code."));
+    let stmts = p.parse_code().expect("Failed to parse code.");
+    let select = &stmts[0].get_node_vec("stmts").unwrap()[0];
+    assert_eq!(select.position(), (1, 22));
+    let a = &select.get_node_vec("exprs").unwrap()[0];
+    // `\x61` is decoded to `a`
+    assert_eq!(a.literal(), "a");
+    assert_eq!(a.position(), (2, 3));
+    let table = select.get_node("from").unwrap().get_node("expr").unwrap();
+    assert_eq!(table.position(), (2, 13));
+}
@@ -753,6 +753,62 @@ differential_privacy:
         self: ) (Symbol)
 exprs:
 - self: col1 (Identifier)
+",
+            0,
+        )),
+        // AGGREGATION_THRESHOLD
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT WITH AGGREGATION_THRESHOLD OPTIONS(threshold = 50, privacy_unit_column = id) col1, COUNT(*)
+FROM t
+GROUP BY col1
+",
+            "\
+self: SELECT (SelectStatement)
+aggregation_threshold:
+  self: WITH (AggregationThresholdClause)
+  aggregation_threshold:
+    self: AGGREGATION_THRESHOLD (Keyword)
+  options:
+    self: OPTIONS (KeywordWithGroupedXXX)
+    group:
+      self: ( (GroupedExprs)
+      exprs:
+      - self: = (BinaryOperator)
+        comma:
+          self: , (Symbol)
+        left:
+          self: threshold (Identifier)
+        right:
+          self: 50 (NumericLiteral)
+      - self: = (BinaryOperator)
+        left:
+          self: privacy_unit_column (Identifier)
+        right:
+          self: id (Identifier)
+      rparen:
+        self: ) (Symbol)
+exprs:
+- self: col1 (Identifier)
+  comma:
+    self: , (Symbol)
+- self: ( (CallingFunction)
+  args:
+  - self: * (Asterisk)
+  func:
+    self: COUNT (Identifier)
+  rparen:
+    self: ) (Symbol)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: t (Identifier)
+groupby:
+  self: GROUP (GroupByExprs)
+  by:
+    self: BY (Keyword)
+  exprs:
+  - self: col1 (Identifier)
 ",
             0,
         )),
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{Node, NodeType};
+use serde::Serialize;
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub enum PrivacyKind {
+    AggregationThreshold, // WITH AGGREGATION_THRESHOLD
+    DifferentialPrivacy,  // WITH DIFFERENTIAL_PRIVACY
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct PrivacyPolicy {
+    pub kind: PrivacyKind,
+    pub clause: String,                      // e.g. SELECT, |> AGGREGATE
+    pub privacy_unit_column: Option<String>, // e.g. t.user_id
+    pub threshold: Option<String>,           // only for AGGREGATION_THRESHOLD
+    pub options: Vec<(String, String)>,      // lowercased key and source text of the value
+    pub statement: usize,                    // index of the statement
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Lists the privacy policies (aggregation threshold or differential privacy)
+/// applied by the queries in the statements.
+pub fn analyze_privacy(stmts: &[Node]) -> Vec<PrivacyPolicy> {
+    let mut policies = Vec::new();
+    for (idx, stmt) in stmts.iter().enumerate() {
+        walk(stmt, idx, &mut policies);
+    }
+    policies
+}
+
+fn walk(node: &Node, statement: usize, policies: &mut Vec<PrivacyPolicy>) {
+    for key in ["aggregation_threshold", "differential_privacy"] {
+        if let Some(clause) = node.get_node(key) {
+            if matches!(
+                clause.node_type,
+                NodeType::AggregationThresholdClause | NodeType::DifferentialPrivacyClause
+            ) {
+                policies.push(policy(node, clause, statement));
+            }
+        }
+    }
+    for (_, child) in node.child_nodes() {
+        walk(child, statement, policies);
+    }
+}
+
+fn policy(query: &Node, clause: &Node, statement: usize) -> PrivacyPolicy {
+    let kind = match clause.node_type {
+        NodeType::AggregationThresholdClause => PrivacyKind::AggregationThreshold,
+        _ => PrivacyKind::DifferentialPrivacy,
+    };
+    let keyword = query.literal().to_uppercase();
+    let clause_name = match query.node_type {
+        NodeType::SelectStatement => keyword,
+        _ => format!("|> {}", keyword),
+    };
+    let mut options = Vec::new();
+    let exprs = clause
+        .get_node("options")
+        .and_then(|o| o.get_node("group"))
+        .and_then(|g| g.get_node_vec("exprs"));
+    for expr in exprs.into_iter().flatten() {
+        if expr.node_type != NodeType::BinaryOperator || expr.literal() != "=" {
+            continue;
+        }
+        let (Some(left), Some(right)) = (expr.get_node("left"), expr.get_node("right")) else {
+            continue;
+        };
+        options.push((left.literal().to_lowercase(), source_text(right)));
+    }
+    let find = |key: &str| {
+        options
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+    };
+    let privacy_unit_column = find("privacy_unit_column");
+    let threshold = match kind {
+        PrivacyKind::AggregationThreshold => find("threshold"),
+        PrivacyKind::DifferentialPrivacy => None,
+    };
+    let (line, column) = clause.position();
+    PrivacyPolicy {
+        kind,
+        clause: clause_name,
+        privacy_unit_column,
+        threshold,
+        options,
+        statement,
+        line,
+        column,
+    }
+}
+
+fn source_text(node: &Node) -> String {
+    if let Some(parts) = node.identifier_parts() {
+        return parts.join(".");
+    }
+    match node.node_type {
+        NodeType::UnaryOperator => match node.get_node("right") {
+            Some(right) => format!("{}{}", node.literal(), source_text(right)),
+            None => node.literal().to_string(),
+        },
+        _ => node.literal().to_string(),
+    }
+}
@@ -0,0 +1,88 @@
+use super::*;
+use crate::validate::tests::parse;
+
+fn policies(code: &str) -> Vec<PrivacyPolicy> {
+    analyze_privacy(&parse(code))
+}
+
+#[test]
+fn test_analyze_privacy() {
+    let res = policies(
+        "\
+SELECT WITH AGGREGATION_THRESHOLD OPTIONS(threshold = 50, privacy_unit_column = t.user_id)
+  col1, COUNT(*)
+FROM t
+GROUP BY col1;
+SELECT 1;
+FROM t
+|> AGGREGATE WITH AGGREGATION_THRESHOLD OPTIONS(THRESHOLD = 3) COUNT(*) GROUP BY col1;
+SELECT * FROM (
+  SELECT WITH DIFFERENTIAL_PRIVACY OPTIONS(epsilon = 1e1, delta = -1, privacy_unit_column = id)
+    SUM(x)
+  FROM t
+);
+",
+    );
+    let summary: Vec<(
+        PrivacyKind,
+        &str,
+        Option<&str>,
+        Option<&str>,
+        usize,
+        usize,
+        usize,
+    )> = res
+        .iter()
+        .map(|p| {
+            (
+                p.kind.clone(),
+                p.clause.as_str(),
+                p.privacy_unit_column.as_deref(),
+                p.threshold.as_deref(),
+                p.statement,
+                p.line,
+                p.column,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                PrivacyKind::AggregationThreshold,
+                "SELECT",
+                Some("t.user_id"),
+                Some("50"),
+                0,
+                1,
+                8
+            ),
+            (
+                PrivacyKind::AggregationThreshold,
+                "|> AGGREGATE",
+                None,
+                Some("3"),
+                2,
+                7,
+                14
+            ),
+            (
+                PrivacyKind::DifferentialPrivacy,
+                "SELECT",
+                Some("id"),
+                None,
+                3,
+                9,
+                10
+            ),
+        ]
+    );
+    assert_eq!(
+        res[2].options,
+        vec![
+            ("epsilon".to_string(), "1e1".to_string()),
+            ("delta".to_string(), "-1".to_string()),
+            ("privacy_unit_column".to_string(), "id".to_string()),
+        ]
+    );
+}
@@ -30,9 +30,25 @@ impl Token {
         }
     }
     pub fn is_string(&self) -> bool {
-        if self.quoted_by('"') {
+        // r'', b'', rb'', br''
+        let prefix = self
+            .literal
+            .chars()
+            .take_while(|c| c.eq_ignore_ascii_case(&'r') || c.eq_ignore_ascii_case(&'b'))
+            .collect::<String>()
+            .to_lowercase();
+        if !["", "r", "b", "rb", "br"].contains(&prefix.as_str()) {
+            return false;
+        }
+        let prefix_len = prefix.len();
+        let unprefixed = Token::new(
+            self.line,
+            self.column,
+            self.literal[prefix_len..].to_string(),
+        );
+        if unprefixed.quoted_by('"') {
             true
-        } else if self.quoted_by('\'') {
+        } else if unprefixed.quoted_by('\'') {
             true
         } else {
             false
@@ -83,14 +99,42 @@ impl Token {
         }
     }
     pub fn is_template(&self) -> bool {
+        if self.is_jinja_statement() || self.is_jinja_comment() {
+            return false;
+        }
+        if self.literal.starts_with("${") {
+            return true; // sqlx
+        }
         let mut iterator = self.literal.chars();
         match iterator.next() {
             Some('{') => return true,
             _ => return false,
         }
     }
+    pub fn is_macro(&self) -> bool {
+        // $name | $1
+        let mut iterator = self.literal.chars();
+        iterator.next() == Some('$') && iterator.next().is_some_and(|c| c != '{')
+    }
+    pub fn is_sqlx_block(&self) -> bool {
+        // config { ... }
+        self.literal.ends_with('}')
+            && ["config", "pre_operations", "post_operations", "js"]
+                .iter()
+                .any(|kw| match self.literal.strip_prefix(kw) {
+                    Some(rest) => rest.trim_start().starts_with('{'),
+                    None => false,
+                })
+    }
+    pub fn is_jinja_statement(&self) -> bool {
+        self.literal.starts_with("{%")
+    }
+    pub fn is_jinja_comment(&self) -> bool {
+        self.literal.starts_with("{#")
+    }
     pub fn is_numeric(&self) -> bool {
-        let re = Regex::new(r"^([0-9]+|([0-9]*\.[0-9]+))([eE][\+\-]?[0-9]+)?$").unwrap();
+        let re = Regex::new(r"^(0[xX][0-9a-fA-F]+|([0-9]+|([0-9]*\.[0-9]+))([eE][\+\-]?[0-9]+)?)$")
+            .unwrap();
         re.is_match(self.literal.as_str())
     }
     pub fn is_boolean(&self) -> bool {
@@ -106,6 +150,10 @@ impl Token {
         false
     }
     pub fn is_comment(&self) -> bool {
+        // jinja statements are also treated as comments
+        if self.is_jinja_statement() || self.is_jinja_comment() {
+            return true;
+        }
         let mut iter = self.literal.chars();
         let first_char = match iter.next() {
             Some(c) => match c {
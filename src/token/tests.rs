@@ -6,6 +6,17 @@ fn test_is_string() {
     assert!(Token::from_str0("'''abc'''").is_string());
     assert!(Token::from_str0("\"abc\"").is_string());
     assert!(Token::from_str0("\"\"\"abc\"\"\"").is_string());
+    assert!(Token::from_str0("r'abc'").is_string());
+    assert!(Token::from_str0("B\"abc\"").is_string());
+    assert!(Token::from_str0("rb'''abc'''").is_string());
+    assert!(Token::from_str0("Br'abc'").is_string());
+
+    // invalid
+    assert!(!Token::from_str0("rbr'abc'").is_string());
+    assert!(!Token::from_str0("rr'abc'").is_string());
+    assert!(!Token::from_str0("bb'abc'").is_string());
+    assert!(!Token::from_str0("x'abc'").is_string());
+    assert!(!Token::from_str0("r").is_string());
 }
 
 #[test]
@@ -39,11 +50,16 @@ fn test_is_numeric() {
     assert!(Token::from_str0("10.11E10").is_numeric());
     assert!(Token::from_str0("10.11E+10").is_numeric());
     assert!(Token::from_str0("10.11E-10").is_numeric());
+    assert!(Token::from_str0("0x1F").is_numeric());
+    assert!(Token::from_str0("0Xabc").is_numeric());
 
     // invalid
     assert!(!Token::from_str0("e10").is_numeric());
     assert!(!Token::from_str0("xxx").is_numeric());
     assert!(!Token::from_str0("x01").is_numeric());
+    assert!(!Token::from_str0("0x").is_numeric());
+    assert!(!Token::from_str0("0x1.1").is_numeric());
+    assert!(!Token::from_str0("0x1e10g").is_numeric());
 }
 
 #[test]
@@ -0,0 +1,381 @@
+#[cfg(test)]
+mod tests;
+
+use crate::bqtype::{supertype, BqType, ScalarType as S};
+use crate::catalog::Catalog;
+use crate::cst::{Node, NodeType};
+use crate::numeric::{numeric_value, NumericValue};
+use crate::validate::{self, Diagnostic, DiagnosticKind};
+use serde::Serialize;
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct ExprType {
+    #[serde(rename = "type")]
+    pub type_: String, // e.g. INT64, ARRAY<STRUCT<a STRING>>
+    pub literal: String, // literal of the token of the node (e.g. `+`, `(`, `col`)
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct TypeReport {
+    pub types: Vec<ExprType>,
+    pub diagnostics: Vec<Diagnostic>, // only TypeMismatch
+}
+
+/// Infers the type of each expression in the queries using the column types
+/// in the catalog, and reports the expressions which have no valid type.
+pub fn infer_types(stmts: &[Node], catalog: &Catalog) -> TypeReport {
+    let (diagnostics, types) = validate::walk(stmts, catalog, true);
+    TypeReport {
+        types,
+        diagnostics: diagnostics
+            .into_iter()
+            .filter(|d| d.kind == DiagnosticKind::TypeMismatch)
+            .collect(),
+    }
+}
+
+/// The type of the literal node. `None` for NULL or non-literal nodes.
+pub fn literal_type(node: &Node) -> Option<BqType> {
+    let type_ = match node.node_type {
+        NodeType::NumericLiteral => match numeric_value(node)? {
+            Ok(NumericValue::Int64(_)) => S::Int64,
+            _ => S::Float64,
+        },
+        NodeType::StringLiteral => {
+            let prefix = node.literal().to_uppercase();
+            if prefix.starts_with('B') || prefix.starts_with("RB") {
+                S::Bytes
+            } else {
+                S::String
+            }
+        }
+        NodeType::BooleanLiteral => S::Bool,
+        NodeType::IntervalLiteral => S::Interval,
+        NodeType::RangeLiteral => return BqType::from_type(node.get_node("type")?),
+        _ => return None,
+    };
+    Some(type_.into())
+}
+
+/// `literal` is true if the operand is a string literal, which can be coerced
+/// to date and time types (e.g. `date_col = '2024-01-01'`).
+pub struct Operand {
+    pub type_: Option<BqType>,
+    pub literal: bool,
+}
+
+fn comparable(left: &Operand, right: &Operand) -> Option<BqType> {
+    let (Some(l), Some(r)) = (&left.type_, &right.type_) else {
+        return None;
+    };
+    if let Some(type_) = supertype(l, r) {
+        return Some(type_);
+    }
+    let coercible = |t: &BqType| t.is_datetime() || matches!(t, BqType::Range(_));
+    if left.literal && l.scalar() == Some(S::String) && coercible(r) {
+        return Some(r.clone());
+    }
+    if right.literal && r.scalar() == Some(S::String) && coercible(l) {
+        return Some(l.clone());
+    }
+    None
+}
+
+/// Returns the result type of the binary operator, or the error message.
+pub fn binary_type(op: &str, left: &Operand, right: &Operand) -> Result<Option<BqType>, String> {
+    let op = op.to_uppercase();
+    let (Some(l), Some(r)) = (&left.type_, &right.type_) else {
+        return Ok(match op.as_str() {
+            "=" | "!=" | "<>" | "<" | ">" | "<=" | ">=" | "AND" | "OR" | "LIKE" | "IS" => {
+                Some(S::Bool.into())
+            }
+            _ => None,
+        });
+    };
+    let error = || {
+        format!(
+            "No matching signature for operator {} for argument types: {}, {}.",
+            op, l, r
+        )
+    };
+    let type_ = match (op.as_str(), l.scalar(), r.scalar()) {
+        ("=" | "!=" | "<>" | "<" | ">" | "<=" | ">=", _, _) => {
+            let type_ = comparable(left, right).ok_or_else(error)?;
+            let equatable = !matches!(type_, BqType::Array(_))
+                && !matches!(type_.scalar(), Some(S::Json | S::Geography));
+            let valid = match op.as_str() {
+                "=" | "!=" | "<>" => equatable,
+                _ => equatable && !matches!(type_, BqType::Struct(_)),
+            };
+            if !valid {
+                return Err(error());
+            }
+            S::Bool.into()
+        }
+        ("AND" | "OR", Some(S::Bool), Some(S::Bool))
+        | ("LIKE", Some(S::String), Some(S::String))
+        | ("LIKE", Some(S::Bytes), Some(S::Bytes))
+        | ("IS", _, _) => S::Bool.into(),
+        ("||", Some(S::String), Some(S::String)) | ("||", Some(S::Bytes), Some(S::Bytes)) => {
+            l.clone()
+        }
+        ("||", _, _) if matches!((l, r), (BqType::Array(_), BqType::Array(_))) => {
+            supertype(l, r).ok_or_else(error)?
+        }
+        ("+" | "-" | "*" | "/", _, _) => arithmetic_type(&op, l, r).ok_or_else(error)?,
+        ("&" | "|" | "^", Some(S::Int64), Some(S::Int64))
+        | ("&" | "|" | "^", Some(S::Bytes), Some(S::Bytes))
+        | ("<<" | ">>", Some(S::Int64 | S::Bytes), Some(S::Int64)) => l.clone(),
+        _ => return Err(error()),
+    };
+    Ok(Some(type_))
+}
+
+fn arithmetic_type(op: &str, l: &BqType, r: &BqType) -> Option<BqType> {
+    if l.is_numeric() && r.is_numeric() {
+        let type_ = supertype(l, r)?;
+        if op == "/" && type_.scalar() == Some(S::Int64) {
+            return Some(S::Float64.into());
+        }
+        return Some(type_);
+    }
+    let type_ = match (op, l.scalar()?, r.scalar()?) {
+        ("+" | "-", S::Date, S::Int64) | ("+", S::Int64, S::Date) => S::Date,
+        ("-", S::Date, S::Date)
+        | ("-", S::Datetime, S::Datetime)
+        | ("-", S::Timestamp, S::Timestamp)
+        | ("+" | "-", S::Interval, S::Interval)
+        | ("*" | "/", S::Interval, S::Int64)
+        | ("*", S::Int64, S::Interval) => S::Interval,
+        ("+" | "-", S::Date | S::Datetime, S::Interval)
+        | ("+", S::Interval, S::Date | S::Datetime) => S::Datetime,
+        ("+" | "-", S::Timestamp, S::Interval) | ("+", S::Interval, S::Timestamp) => S::Timestamp,
+        _ => return None,
+    };
+    Some(type_.into())
+}
+
+/// Returns the result type of the unary operator (including typed literals such as
+/// `DATE '2024-01-01'`), or the error message.
+pub fn unary_type(op: &str, right: Option<&BqType>) -> Result<Option<BqType>, String> {
+    let op = op.to_uppercase();
+    if let Some(type_) = S::from_name(&op) {
+        return Ok(Some(type_.into())); // typed literal
+    }
+    let Some(r) = right else {
+        return Ok(match op.as_str() {
+            "NOT" => Some(S::Bool.into()),
+            _ => None,
+        });
+    };
+    let valid = match (op.as_str(), r.scalar()) {
+        ("-" | "+", Some(s)) => s.is_numeric() || s == S::Interval,
+        ("~", Some(s)) => matches!(s, S::Int64 | S::Bytes),
+        ("NOT", Some(s)) => s == S::Bool,
+        ("-" | "+" | "~" | "NOT", None) => false,
+        _ => return Ok(None),
+    };
+    if !valid {
+        return Err(format!(
+            "No matching signature for operator {} for argument types: {}.",
+            op, r
+        ));
+    }
+    Ok(Some(r.clone()))
+}
+
+/// The common type of the values (e.g. THEN clauses of CASE expression).
+/// Unknown types are ignored.
+pub fn common_type(types: &[Option<BqType>]) -> Result<Option<BqType>, String> {
+    let mut res: Option<BqType> = None;
+    for type_ in types.iter().flatten() {
+        res = match res {
+            None => Some(type_.clone()),
+            Some(current) => match supertype(&current, type_) {
+                Some(t) => Some(t),
+                None => {
+                    return Err(format!(
+                        "No common supertype for {} and {}.",
+                        current, type_
+                    ))
+                }
+            },
+        };
+    }
+    Ok(res)
+}
+
+/// Returns the result type of the built-in function (`SAFE.` prefix is removed),
+/// or the error message. `None` if the function or the argument types are unknown.
+pub fn function_type(name: &str, args: &[Option<BqType>]) -> Result<Option<BqType>, String> {
+    let name = name.to_uppercase();
+    let name = name.strip_prefix("SAFE.").unwrap_or(&name);
+    let arg = |idx: usize| args.get(idx).cloned().flatten();
+    let scalar = |idx: usize| arg(idx).and_then(|t| t.scalar());
+    let error = |kind: &str| {
+        let types: Vec<String> = args
+            .iter()
+            .map(|a| match a {
+                Some(t) => t.to_string(),
+                None => "?".to_string(),
+            })
+            .collect();
+        format!(
+            "No matching signature for {} {} for argument types: {}.",
+            kind,
+            name,
+            types.join(", ")
+        )
+    };
+    let array = |element: S| BqType::Array(Box::new(element.into()));
+    let type_ = match name {
+        "COUNT"
+        | "COUNTIF"
+        | "ROW_NUMBER"
+        | "RANK"
+        | "DENSE_RANK"
+        | "NTILE"
+        | "APPROX_COUNT_DISTINCT"
+        | "ARRAY_LENGTH"
+        | "LENGTH"
+        | "CHAR_LENGTH"
+        | "CHARACTER_LENGTH"
+        | "BYTE_LENGTH"
+        | "STRPOS"
+        | "INSTR"
+        | "DATE_DIFF"
+        | "DATETIME_DIFF"
+        | "TIMESTAMP_DIFF"
+        | "TIME_DIFF"
+        | "UNIX_DATE"
+        | "UNIX_SECONDS"
+        | "UNIX_MILLIS"
+        | "UNIX_MICROS"
+        | "FARM_FINGERPRINT"
+        | "DIV" => S::Int64.into(),
+        "CUME_DIST" | "PERCENT_RANK" | "RAND" | "STDDEV" | "STDDEV_SAMP" | "STDDEV_POP"
+        | "VARIANCE" | "VAR_SAMP" | "VAR_POP" | "CORR" | "COVAR_POP" | "COVAR_SAMP" | "SQRT"
+        | "POW" | "POWER" | "LN" | "LOG" | "LOG10" | "EXP" => S::Float64.into(),
+        "SUM" | "AVG" => match (arg(0), scalar(0)) {
+            (Some(_), Some(S::Int64)) if name == "AVG" => S::Float64.into(),
+            (Some(t), Some(s)) if s.is_numeric() || s == S::Interval => t,
+            (Some(_), _) => return Err(error("aggregate function")),
+            (None, _) => return Ok(None),
+        },
+        "MIN" | "MAX" | "ANY_VALUE" | "FIRST_VALUE" | "LAST_VALUE" | "NTH_VALUE" | "LAG"
+        | "LEAD" | "NULLIF" | "CAST" | "SAFE_CAST" | "EXTRACT" | "ARRAY_REVERSE"
+        | "ARRAY_CONCAT_AGG" => return Ok(arg(0)),
+        "ARRAY_AGG" => match arg(0) {
+            Some(t) => BqType::Array(Box::new(t)),
+            None => return Ok(None),
+        },
+        "ARRAY" => match arg(0) {
+            Some(BqType::Array(_)) | None => return Ok(None), // ARRAY of ARRAY is not allowed
+            Some(t) => BqType::Array(Box::new(t)),
+        },
+        "STRING_AGG" => match (arg(0), scalar(0)) {
+            (Some(t), Some(S::String | S::Bytes)) => t,
+            (Some(_), _) => return Err(error("aggregate function")),
+            (None, _) => return Ok(None),
+        },
+        "LOGICAL_AND" | "LOGICAL_OR" => match (arg(0), scalar(0)) {
+            (None, _) | (_, Some(S::Bool)) => S::Bool.into(),
+            _ => return Err(error("aggregate function")),
+        },
+        "ABS" | "SIGN" | "MOD" | "ROUND" | "TRUNC" | "CEIL" | "CEILING" | "FLOOR" => {
+            match (arg(0), scalar(0)) {
+                (_, Some(S::Int64)) if !matches!(name, "ABS" | "SIGN" | "MOD") => S::Float64.into(),
+                (Some(t), Some(s)) if s.is_numeric() => t,
+                (Some(_), _) => return Err(error("function")),
+                (None, _) => return Ok(None),
+            }
+        }
+        "CONCAT" => {
+            let known: Vec<&BqType> = args.iter().flatten().collect();
+            match known.first() {
+                Some(t)
+                    if matches!(t.scalar(), Some(S::String | S::Bytes))
+                        && known.iter().all(|k| k == t) =>
+                {
+                    (*t).clone()
+                }
+                Some(_) => return Err(error("function")),
+                None => S::String.into(),
+            }
+        }
+        "LOWER" | "UPPER" | "TRIM" | "LTRIM" | "RTRIM" | "SUBSTR" | "SUBSTRING" | "REPLACE"
+        | "REVERSE" | "LPAD" | "RPAD" | "LEFT" | "RIGHT" | "REPEAT" | "REGEXP_EXTRACT"
+        | "REGEXP_REPLACE" => match (arg(0), scalar(0)) {
+            (Some(t), Some(S::String | S::Bytes)) => t,
+            (Some(_), _) => return Err(error("function")),
+            (None, _) => return Ok(None),
+        },
+        "SPLIT" => BqType::Array(Box::new(arg(0).unwrap_or(S::String.into()))),
+        "FORMAT"
+        | "FORMAT_DATE"
+        | "FORMAT_DATETIME"
+        | "FORMAT_TIME"
+        | "FORMAT_TIMESTAMP"
+        | "TO_JSON_STRING"
+        | "JSON_VALUE"
+        | "JSON_EXTRACT_SCALAR"
+        | "GENERATE_UUID"
+        | "SESSION_USER"
+        | "TO_HEX"
+        | "TO_BASE64"
+        | "ARRAY_TO_STRING"
+        | "STRING"
+        | "INITCAP" => S::String.into(),
+        "REGEXP_CONTAINS" | "STARTS_WITH" | "ENDS_WITH" | "IS_NAN" | "IS_INF" | "EXISTS" => {
+            S::Bool.into()
+        }
+        "DATE"
+        | "CURRENT_DATE"
+        | "DATE_ADD"
+        | "DATE_SUB"
+        | "DATE_TRUNC"
+        | "PARSE_DATE"
+        | "DATE_FROM_UNIX_DATE"
+        | "LAST_DAY" => S::Date.into(),
+        "DATETIME" | "CURRENT_DATETIME" | "DATETIME_ADD" | "DATETIME_SUB" | "DATETIME_TRUNC"
+        | "PARSE_DATETIME" => S::Datetime.into(),
+        "TIME" | "CURRENT_TIME" | "TIME_ADD" | "TIME_SUB" | "TIME_TRUNC" | "PARSE_TIME" => {
+            S::Time.into()
+        }
+        "TIMESTAMP" | "CURRENT_TIMESTAMP" | "TIMESTAMP_ADD" | "TIMESTAMP_SUB"
+        | "TIMESTAMP_TRUNC" | "PARSE_TIMESTAMP" | "TIMESTAMP_SECONDS" | "TIMESTAMP_MILLIS"
+        | "TIMESTAMP_MICROS" => S::Timestamp.into(),
+        "TO_JSON" | "PARSE_JSON" => S::Json.into(),
+        "JSON_QUERY" | "JSON_EXTRACT" => match scalar(0) {
+            Some(S::Json) => S::Json.into(),
+            _ => S::String.into(),
+        },
+        "JSON_VALUE_ARRAY" => array(S::String),
+        "JSON_QUERY_ARRAY" => match scalar(0) {
+            Some(S::Json) => array(S::Json),
+            _ => array(S::String),
+        },
+        "GENERATE_ARRAY" => match common_type(&args[..args.len().min(2)]) {
+            Ok(Some(t)) if t.is_numeric() => BqType::Array(Box::new(t)),
+            Ok(None) => return Ok(None),
+            _ => return Err(error("function")),
+        },
+        "GENERATE_DATE_ARRAY" => array(S::Date),
+        "GENERATE_TIMESTAMP_ARRAY" => array(S::Timestamp),
+        "SAFE_DIVIDE" => match (arg(0), arg(1)) {
+            (Some(l), Some(r)) => arithmetic_type("/", &l, &r).ok_or_else(|| error("function"))?,
+            _ => return Ok(None),
+        },
+        "IF" => {
+            if arg(0).is_some() && scalar(0) != Some(S::Bool) {
+                return Err(error("function"));
+            }
+            return common_type(args.get(1..).unwrap_or_default()).map_err(|_| error("function"));
+        }
+        "IFNULL" | "COALESCE" => return common_type(args).map_err(|_| error("function")),
+        _ => return Ok(None),
+    };
+    Ok(Some(type_))
+}
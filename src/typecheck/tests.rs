@@ -0,0 +1,232 @@
+use super::*;
+use crate::catalog::build_catalog;
+use crate::validate::tests::{diagnostic_tuples, parse};
+
+fn report(code: &str) -> TypeReport {
+    let stmts = parse(code);
+    let catalog = build_catalog(&stmts);
+    infer_types(&stmts, &catalog)
+}
+
+fn types(code: &str) -> Vec<(String, String)> {
+    report(code)
+        .types
+        .into_iter()
+        .map(|t| (t.literal, t.type_))
+        .collect()
+}
+
+fn errors(code: &str) -> Vec<(String, usize, usize)> {
+    diagnostic_tuples(report(code).diagnostics)
+        .into_iter()
+        .map(|(_, message, line, column)| (message, line, column))
+        .collect()
+}
+
+#[test]
+fn test_infer_types_literals() {
+    let res = types(
+        "SELECT 1, 1.5, 'a', b'a', TRUE, NULL, DATE '2024-01-01', NUMERIC '1', -2, \
+INTERVAL 1 DAY, RANGE<DATE> '[2024-01-01, UNBOUNDED)', [1, 2.5], STRUCT(1 AS a, 'b'), \
+ARRAY<INT64>[], CAST('1' AS STRUCT<a STRING(10)>)",
+    );
+    let expected = [
+        ("1", "INT64"),
+        ("1.5", "FLOAT64"),
+        ("'a'", "STRING"),
+        ("b'a'", "BYTES"),
+        ("TRUE", "BOOL"),
+        ("'2024-01-01'", "STRING"),
+        ("DATE", "DATE"),
+        ("'1'", "STRING"),
+        ("NUMERIC", "NUMERIC"),
+        ("2", "INT64"),
+        ("-", "INT64"),
+        ("1", "INT64"),
+        ("INTERVAL", "INTERVAL"),
+        ("'[2024-01-01, UNBOUNDED)'", "RANGE<DATE>"),
+        ("1", "INT64"),
+        ("2.5", "FLOAT64"),
+        ("[", "ARRAY<FLOAT64>"),
+        ("1", "INT64"),
+        ("'b'", "STRING"),
+        ("(", "STRUCT<a INT64, STRING>"),
+        ("[", "ARRAY<INT64>"),
+        ("'1'", "STRING"),
+        ("AS", "STRUCT<a STRING>"),
+        ("(", "STRUCT<a STRING>"),
+    ];
+    assert_eq!(
+        res,
+        expected
+            .iter()
+            .map(|(l, t)| (l.to_string(), t.to_string()))
+            .collect::<Vec<(String, String)>>()
+    );
+}
+
+#[test]
+fn test_infer_types_expressions() {
+    let res = types(
+        "\
+CREATE TABLE ds.t (i INT64, n NUMERIC, s STRING, d DATE, arr ARRAY<STRUCT<x FLOAT64>>);
+SELECT
+  i / 2,
+  n + i,
+  d + INTERVAL 1 DAY,
+  CASE WHEN i > 0 THEN i ELSE n END,
+  arr[OFFSET(0)].x,
+  SUM(n),
+  AVG(i),
+  ARRAY_AGG(s),
+  IF(TRUE, 1, 2.0),
+  (SELECT MAX(d) FROM ds.t),
+FROM ds.t",
+    )
+    .into_iter()
+    .filter(|(literal, _)| {
+        matches!(
+            literal.as_str(),
+            "/" | "+" | "CASE" | "." | "(" | "ARRAY_AGG" | "IF"
+        )
+    })
+    .collect::<Vec<(String, String)>>();
+    let expected = [
+        ("/", "FLOAT64"),
+        ("+", "NUMERIC"),
+        ("+", "DATETIME"),
+        ("CASE", "NUMERIC"),
+        (".", "FLOAT64"),
+        ("(", "NUMERIC"),
+        ("(", "FLOAT64"),
+        ("(", "ARRAY<STRING>"),
+        ("(", "FLOAT64"),
+        ("(", "DATE"),
+        ("(", "DATE"),
+    ];
+    assert_eq!(
+        res,
+        expected
+            .iter()
+            .map(|(l, t)| (l.to_string(), t.to_string()))
+            .collect::<Vec<(String, String)>>()
+    );
+}
+
+#[test]
+fn test_infer_types_errors() {
+    let res = errors(
+        "\
+CREATE TABLE ds.t (i INT64, s STRING, d DATE, b BOOL);
+SELECT s = i, SUM(s), d = '2024-01-01', i IN (1, 'a'), -s, CONCAT(s, i) FROM ds.t
+WHERE i;
+SELECT CASE WHEN b THEN 1 ELSE 'x' END, NOT i, b AND 1, [1, 'a'], s BETWEEN 1 AND 2 FROM ds.t;
+SELECT ARRAY<INT64>[i, d, 1.5], ARRAY<FLOAT64>[i] FROM ds.t;
+",
+    );
+    assert_eq!(
+        res,
+        vec![
+            (
+                "No matching signature for operator = for argument types: STRING, INT64."
+                    .to_string(),
+                2,
+                10
+            ),
+            (
+                "No matching signature for aggregate function SUM for argument types: STRING."
+                    .to_string(),
+                2,
+                15
+            ),
+            (
+                "No matching signature for operator IN for argument types: INT64, STRING."
+                    .to_string(),
+                2,
+                50
+            ),
+            (
+                "No matching signature for operator - for argument types: STRING.".to_string(),
+                2,
+                56
+            ),
+            (
+                "No matching signature for function CONCAT for argument types: STRING, INT64."
+                    .to_string(),
+                2,
+                60
+            ),
+            (
+                "WHERE clause should return type BOOL, but returns INT64.".to_string(),
+                3,
+                7
+            ),
+            (
+                "No common supertype for INT64 and STRING.".to_string(),
+                4,
+                8
+            ),
+            (
+                "No matching signature for operator NOT for argument types: INT64.".to_string(),
+                4,
+                41
+            ),
+            (
+                "No matching signature for operator AND for argument types: BOOL, INT64."
+                    .to_string(),
+                4,
+                50
+            ),
+            (
+                "No common supertype for INT64 and STRING.".to_string(),
+                4,
+                57
+            ),
+            (
+                "No matching signature for operator BETWEEN for argument types: STRING, INT64."
+                    .to_string(),
+                4,
+                69
+            ),
+            (
+                "No matching signature for operator BETWEEN for argument types: STRING, INT64."
+                    .to_string(),
+                4,
+                69
+            ),
+            (
+                "Array element type DATE does not coerce to INT64.".to_string(),
+                5,
+                24
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_infer_types_quantified_like() {
+    let res = errors(
+        "\
+CREATE TABLE ds.t (s STRING, arr ARRAY<STRING>);
+SELECT s LIKE ANY ('a%', 'b%'), s NOT LIKE ALL UNNEST(arr), s LIKE SOME ('a%') FROM ds.t;
+SELECT s LIKE ANY ('a%', 1), s LIKE ALL UNNEST([1]) FROM ds.t;
+",
+    );
+    assert_eq!(
+        res,
+        vec![
+            (
+                "No matching signature for operator LIKE for argument types: STRING, INT64."
+                    .to_string(),
+                3,
+                10
+            ),
+            (
+                "No matching signature for operator LIKE for argument types: STRING, INT64."
+                    .to_string(),
+                3,
+                32
+            ),
+        ]
+    );
+}
@@ -0,0 +1,571 @@
+#[cfg(test)]
+mod tests;
+
+use crate::bqtype::{BqType, ScalarType};
+use crate::cst::Node;
+use crate::cst::NodeType;
+use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::literal::{decode_string_literal, LiteralValue};
+use crate::numeric::numeric_value;
+use crate::token::Token;
+use crate::validate::{Diagnostic, DiagnosticKind};
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+// datetime parts which can be used in `INTERVAL '...' x TO y`, in order
+const INTERVAL_RANGE_PARTS: [&str; 6] = ["YEAR", "MONTH", "DAY", "HOUR", "MINUTE", "SECOND"];
+const INTERVAL_PARTS: [&str; 10] = [
+    "YEAR",
+    "QUARTER",
+    "MONTH",
+    "WEEK",
+    "DAY",
+    "HOUR",
+    "MINUTE",
+    "SECOND",
+    "MILLISECOND",
+    "MICROSECOND",
+];
+
+/// Checks the contents of typed literals.
+/// `DATE`, `TIME`, `DATETIME`, `TIMESTAMP`, `JSON`, `NUMERIC`, `BIGNUMERIC`, `INTERVAL` and `RANGE` literals are checked.
+/// The literals are syntactically valid, so they are reported as diagnostics rather than parse errors.
+pub fn check_typed_literals(stmts: &[Node]) -> Vec<Diagnostic> {
+    fn check(node: &Node, statement: usize, diagnostics: &mut Vec<Diagnostic>) {
+        let result = match node.node_type {
+            NodeType::UnaryOperator => validate_unary(node),
+            NodeType::IntervalLiteral => validate_interval(node),
+            NodeType::RangeLiteral => validate_range(node),
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            diagnostics.push(Diagnostic {
+                line: e.line,
+                column: e.column,
+                ..Diagnostic::new(
+                    DiagnosticKind::InvalidLiteral,
+                    e.message().to_string(),
+                    statement,
+                    node,
+                )
+            });
+        }
+        for (_, child) in node.child_nodes() {
+            check(child, statement, diagnostics);
+        }
+    }
+    let mut diagnostics = Vec::new();
+    for (i, stmt) in stmts.iter().enumerate() {
+        check(stmt, i, &mut diagnostics);
+    }
+    diagnostics
+}
+
+fn validate_unary(node: &Node) -> BQ2CSTResult<()> {
+    let (Some(token), Some(right)) = (node.token.as_ref(), node.get_node("right")) else {
+        return Ok(());
+    };
+    let (&NodeType::StringLiteral, Some(string)) = (&right.node_type, right.token.as_ref()) else {
+        return Ok(());
+    };
+    let type_ = token.literal.to_uppercase();
+    let valid = match type_.as_str() {
+        "DATE" | "TIME" | "DATETIME" | "TIMESTAMP" => {
+            let value = string_value(string, &type_)?;
+            datetime_key(&type_, value.trim()).is_some()
+        }
+        "JSON" => is_valid_json(&string_value(string, &type_)?),
+        // e.g. NUMERIC '1e30'
+        "NUMERIC" | "DECIMAL" | "BIGNUMERIC" | "BIGDECIMAL" => {
+            return match numeric_value(node) {
+                Some(Err(e)) => Err(e),
+                _ => Ok(()),
+            }
+        }
+        _ => return Ok(()),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_literal(string, &type_))
+    }
+}
+
+fn validate_interval(node: &Node) -> BQ2CSTResult<()> {
+    let Some(date_part) = node.get_node("date_part") else {
+        return Ok(());
+    };
+    let Some(from_token) = date_part.token.as_ref() else {
+        return Ok(());
+    };
+    let from = from_token.literal.to_uppercase();
+    let to = match node.get_node("to_date_part") {
+        Some(to_date_part) => match to_date_part.token.as_ref() {
+            Some(token) => Some((token, token.literal.to_uppercase())),
+            None => return Ok(()),
+        },
+        None => None,
+    };
+    let parts = match &to {
+        None => {
+            if !INTERVAL_PARTS.contains(&from.as_str()) {
+                return Err(BQ2CSTError::from_token(
+                    from_token,
+                    format!("Invalid INTERVAL datetime part: {}.", from_token.literal),
+                ));
+            }
+            None
+        }
+        Some((to_token, to)) => {
+            let position = |part: &str| INTERVAL_RANGE_PARTS.iter().position(|p| *p == part);
+            match (position(&from), position(to)) {
+                (Some(i), Some(j)) if i < j => Some((i, j)),
+                _ => {
+                    return Err(BQ2CSTError::from_token(
+                        to_token,
+                        format!(
+                            "Invalid INTERVAL datetime part range: {} TO {}.",
+                            from_token.literal, to_token.literal
+                        ),
+                    ))
+                }
+            }
+        }
+    };
+    // `INTERVAL 1 DAY` or `INTERVAL x DAY` is not checked any further
+    let expr = match node.get_node("expr") {
+        Some(expr) if expr.node_type == NodeType::StringLiteral => expr,
+        _ => return Ok(()),
+    };
+    let Some(string) = expr.token.as_ref() else {
+        return Ok(());
+    };
+    let value = string_value(string, "INTERVAL")?;
+    let valid = match parts {
+        None => is_interval_number(strip_sign(value.trim()), from == "SECOND"),
+        Some((i, j)) => is_valid_interval_range(&value, i, j),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_literal(string, "INTERVAL"))
+    }
+}
+
+fn validate_range(node: &Node) -> BQ2CSTResult<()> {
+    let (Some(token), Some(type_)) = (node.token.as_ref(), node.get_node("type")) else {
+        return Ok(());
+    };
+    let element = match BqType::from_type(type_) {
+        Some(BqType::Range(element)) => *element,
+        _ => return Ok(()),
+    };
+    let element_name = match element.scalar() {
+        Some(ScalarType::Date) => "DATE",
+        Some(ScalarType::Datetime) => "DATETIME",
+        Some(ScalarType::Timestamp) => "TIMESTAMP",
+        _ => {
+            return Err(BQ2CSTError::from_token(
+                token,
+                format!(
+                    "Invalid RANGE literal: RANGE<{}> is not supported.",
+                    element
+                ),
+            ))
+        }
+    };
+    let value = string_value(token, "RANGE")?;
+    let bounds = value
+        .trim()
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(')'))
+        .and_then(|v| v.split_once(','))
+        .filter(|(_, upper)| !upper.contains(','));
+    let Some((lower, upper)) = bounds else {
+        return Err(invalid_literal(token, "RANGE"));
+    };
+    // `None` means UNBOUNDED, `Some(None)` means the bound cannot be compared
+    let bound = |bound: &str| -> Option<Option<Option<i64>>> {
+        let bound = bound.trim();
+        if bound.eq_ignore_ascii_case("UNBOUNDED") || bound.eq_ignore_ascii_case("NULL") {
+            return Some(None);
+        }
+        datetime_key(element_name, bound).map(Some)
+    };
+    match (bound(lower), bound(upper)) {
+        (Some(Some(Some(lower))), Some(Some(Some(upper)))) if upper <= lower => {
+            Err(BQ2CSTError::from_token(
+                token,
+                format!(
+                    "Invalid RANGE literal: {}. The lower bound must be less than the upper bound.",
+                    token.literal
+                ),
+            ))
+        }
+        (Some(_), Some(_)) => Ok(()),
+        _ => Err(invalid_literal(token, "RANGE")),
+    }
+}
+
+fn invalid_literal(token: &Token, type_: &str) -> BQ2CSTError {
+    BQ2CSTError::from_token(
+        token,
+        format!("Invalid {} literal: {}", type_, token.literal),
+    )
+}
+
+fn string_value(token: &Token, type_: &str) -> BQ2CSTResult<String> {
+    match decode_string_literal(token)?.value {
+        LiteralValue::String(s) => Ok(s),
+        LiteralValue::Bytes(_) => Err(invalid_literal(token, type_)),
+    }
+}
+
+// ----- DATE, TIME, DATETIME and TIMESTAMP -----
+
+/// Returns `None` if the value is invalid.
+/// Otherwise returns the value in microseconds, which is used to compare values of the same type.
+/// The inner value is `None` if it cannot be determined (e.g. `TIMESTAMP` with a time zone name).
+fn datetime_key(type_: &str, value: &str) -> Option<Option<i64>> {
+    match type_ {
+        "DATE" => parse_date(value).map(Some),
+        "TIME" => parse_time(value).map(Some),
+        "DATETIME" => {
+            let (date, time) = split_date_time(value);
+            let micros = match time {
+                Some(time) => parse_time(time)?,
+                None if date.len() == value.len() => 0,
+                None => return None,
+            };
+            Some(Some(parse_date(date)? + micros))
+        }
+        "TIMESTAMP" => {
+            let (date, rest) = split_date_time(value);
+            let days = parse_date(date)?;
+            let (time, zone) = match rest {
+                Some(rest) => {
+                    let end = rest
+                        .find(|c: char| !c.is_ascii_digit() && c != ':' && c != '.')
+                        .unwrap_or(rest.len());
+                    (parse_time(&rest[..end])?, &rest[end..])
+                }
+                // time zone without time (e.g. `2024-01-01 UTC`)
+                None => (0, &value[date.len()..]),
+            };
+            let offset = parse_time_zone(zone.trim_start())?;
+            Some(offset.map(|offset| days + time - offset))
+        }
+        _ => None,
+    }
+}
+
+// splits `2024-01-01 12:00:00` or `2024-01-01T12:00:00`
+fn split_date_time(value: &str) -> (&str, Option<&str>) {
+    match value.find([' ', 'T', 't']) {
+        Some(i) if value[i + 1..].starts_with(|c: char| c.is_ascii_digit()) => {
+            (&value[..i], Some(&value[i + 1..]))
+        }
+        _ => {
+            let end = value
+                .find(|c: char| !c.is_ascii_digit() && c != '-')
+                .unwrap_or(value.len());
+            // `-` of the time zone offset (e.g. `2024-01-01-08`) is not a part of the date
+            match value[..end].match_indices('-').nth(2) {
+                Some((i, _)) => (&value[..i], None),
+                None => (&value[..end], None),
+            }
+        }
+    }
+}
+
+// [Y]YYY-[M]M-[D]D
+fn parse_date(value: &str) -> Option<i64> {
+    let parts: Vec<&str> = value.split('-').collect();
+    let [year, month, day] = parts[..] else {
+        return None;
+    };
+    let year = parse_digits(year, 4)?;
+    let month = parse_digits(month, 2)?;
+    let day = parse_digits(day, 2)?;
+    if !(1..=9999).contains(&year) || !(1..=12).contains(&month) {
+        return None;
+    }
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    if !(1..=days_in_month).contains(&day) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day) * MICROS_PER_DAY)
+}
+
+// [H]H:[M]M[:[S]S[.F]]
+fn parse_time(value: &str) -> Option<i64> {
+    let (value, fraction) = match value.split_once('.') {
+        Some((value, fraction)) => (value, Some(fraction)),
+        None => (value, None),
+    };
+    let parts: Vec<&str> = value.split(':').collect();
+    let (hour, minute, second) = match parts[..] {
+        [hour, minute] if fraction.is_none() => (hour, minute, "0"),
+        [hour, minute, second] => (hour, minute, second),
+        _ => return None,
+    };
+    let hour = parse_digits(hour, 2)?;
+    let minute = parse_digits(minute, 2)?;
+    let second = parse_digits(second, 2)?;
+    if 23 < hour || 59 < minute || 59 < second {
+        return None;
+    }
+    let micros = match fraction {
+        Some(fraction) if (1..=6).contains(&fraction.len()) => {
+            parse_digits(fraction, 6)? * 10_i64.pow(6 - fraction.len() as u32)
+        }
+        Some(_) => return None,
+        None => 0,
+    };
+    Some(((hour * 60 + minute) * 60 + second) * 1_000_000 + micros)
+}
+
+/// Returns the offset in microseconds.
+/// The inner value is `None` if the time zone is given by its name (e.g. `America/Los_Angeles`).
+fn parse_time_zone(zone: &str) -> Option<Option<i64>> {
+    if zone.is_empty() || zone.eq_ignore_ascii_case("Z") || zone.eq_ignore_ascii_case("UTC") {
+        return Some(Some(0));
+    }
+    if let Some(offset) = zone.strip_prefix(['+', '-']) {
+        let (hour, minute) = match offset.split_once(':') {
+            Some((hour, minute)) => (hour, minute),
+            None if offset.len() == 4 => offset.split_at(2),
+            None => (offset, "0"),
+        };
+        let hour = parse_digits(hour, 2)?;
+        let minute = parse_digits(minute, 2)?;
+        if 14 < hour || 59 < minute {
+            return None;
+        }
+        let offset = (hour * 60 + minute) * 60_000_000;
+        return Some(Some(if zone.starts_with('-') {
+            -offset
+        } else {
+            offset
+        }));
+    }
+    let is_name = zone.starts_with(|c: char| c.is_ascii_alphabetic())
+        && zone
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_/+-".contains(c));
+    if is_name {
+        Some(None)
+    } else {
+        None
+    }
+}
+
+fn parse_digits(digits: &str, max_len: usize) -> Option<i64> {
+    if digits.is_empty() || max_len < digits.len() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+// the number of days since 1970-01-01
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = (month + 9) % 12;
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// ----- INTERVAL -----
+
+fn strip_sign(value: &str) -> &str {
+    value.strip_prefix(['+', '-']).unwrap_or(value)
+}
+
+fn is_interval_number(value: &str, fraction: bool) -> bool {
+    let (value, fraction) = match value.split_once('.') {
+        Some((value, f)) if fraction => (value, Some(f)),
+        _ => (value, None),
+    };
+    let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    is_digits(value) && fraction.is_none_or(|f| f.len() <= 6 && is_digits(f))
+}
+
+// e.g. `Y-M D H:M:S` for YEAR TO SECOND, `D H` for DAY TO HOUR
+fn is_valid_interval_range(value: &str, from: usize, to: usize) -> bool {
+    let parts: Vec<usize> = (from..=to).collect();
+    let groups: Vec<Vec<usize>> = [vec![0, 1], vec![2], vec![3, 4, 5]]
+        .into_iter()
+        .map(|group| group.into_iter().filter(|p| parts.contains(p)).collect())
+        .filter(|group: &Vec<usize>| !group.is_empty())
+        .collect();
+    let values: Vec<&str> = value.split_whitespace().collect();
+    if values.len() != groups.len() {
+        return false;
+    }
+    groups.iter().zip(values).all(|(group, value)| {
+        let separator = if group[0] <= 1 { '-' } else { ':' };
+        let numbers: Vec<&str> = strip_sign(value).split(separator).collect();
+        if numbers.len() != group.len() {
+            return false;
+        }
+        numbers
+            .iter()
+            .zip(group)
+            .enumerate()
+            .all(|(i, (number, part))| {
+                if !is_interval_number(number, *part == 5) {
+                    return false;
+                }
+                if i == 0 {
+                    return true;
+                }
+                // MONTH in `Y-M` and MINUTE, SECOND in `H:M:S` are bounded
+                let max = if *part == 1 { 11 } else { 59 };
+                let integer = number.split('.').next().unwrap_or("");
+                integer.parse::<i64>().is_ok_and(|n| n <= max)
+            })
+    })
+}
+
+// ----- JSON -----
+
+fn is_valid_json(value: &str) -> bool {
+    let mut checker = JsonChecker {
+        bytes: value.as_bytes(),
+        position: 0,
+    };
+    checker.value() && {
+        checker.skip_whitespace();
+        checker.position == checker.bytes.len()
+    }
+}
+
+struct JsonChecker<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl JsonChecker<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.position += 1;
+        }
+    }
+    fn value(&mut self) -> bool {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.sequence(b'}', |c| {
+                c.skip_whitespace();
+                c.string()
+                    && {
+                        c.skip_whitespace();
+                        c.eat(b':')
+                    }
+                    && c.value()
+            }),
+            Some(b'[') => self.sequence(b']', |c| c.value()),
+            Some(b'"') => self.string(),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => ["true", "false", "null"].iter().any(|keyword| {
+                let matched = self.bytes[self.position..].starts_with(keyword.as_bytes());
+                if matched {
+                    self.position += keyword.len();
+                }
+                matched
+            }),
+        }
+    }
+    // `{...}` or `[...]`
+    fn sequence(&mut self, close: u8, element: fn(&mut Self) -> bool) -> bool {
+        self.position += 1;
+        self.skip_whitespace();
+        if self.eat(close) {
+            return true;
+        }
+        loop {
+            if !element(self) {
+                return false;
+            }
+            self.skip_whitespace();
+            if self.eat(close) {
+                return true;
+            }
+            if !self.eat(b',') {
+                return false;
+            }
+        }
+    }
+    fn string(&mut self) -> bool {
+        if !self.eat(b'"') {
+            return false;
+        }
+        while let Some(byte) = self.peek() {
+            self.position += 1;
+            match byte {
+                b'"' => return true,
+                b'\\' => match self.peek() {
+                    Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => {
+                        self.position += 1
+                    }
+                    Some(b'u') => {
+                        let hex = &self.bytes[self.position + 1..];
+                        if hex.len() < 4 || !hex[..4].iter().all(|b| b.is_ascii_hexdigit()) {
+                            return false;
+                        }
+                        self.position += 5;
+                    }
+                    _ => return false,
+                },
+                0x00..=0x1f => return false,
+                _ => (),
+            }
+        }
+        false
+    }
+    fn number(&mut self) -> bool {
+        self.eat(b'-');
+        if !self.eat(b'0') && !self.digits() {
+            return false;
+        }
+        if self.eat(b'.') && !self.digits() {
+            return false;
+        }
+        if self.eat(b'e') || self.eat(b'E') {
+            if !self.eat(b'+') {
+                self.eat(b'-');
+            }
+            if !self.digits() {
+                return false;
+            }
+        }
+        true
+    }
+    // returns false if there is no digit
+    fn digits(&mut self) -> bool {
+        let start = self.position;
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.position += 1;
+        }
+        start < self.position
+    }
+}
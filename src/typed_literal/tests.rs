@@ -0,0 +1,147 @@
+use super::*;
+use crate::validate::tests::{diagnostic_tuples, parse};
+
+// returns (line, column) of the diagnostics
+fn diagnostics(code: &str) -> Vec<(usize, usize)> {
+    diagnostic_tuples(check_typed_literals(&parse(code)))
+        .into_iter()
+        .map(|(_, _, line, column)| (line, column))
+        .collect()
+}
+
+#[test]
+fn test_valid_typed_literals() {
+    for expr in vec![
+        "DATE '2024-02-29'",
+        "DATE '1-1-1'",
+        "DATE \"9999-12-31\"",
+        "TIME '23:59:59.999999'",
+        "TIME '1:2:3'",
+        "DATETIME '2024-01-01'",
+        "DATETIME '2024-01-01T12:30:00.5'",
+        "TIMESTAMP '2024-01-01'",
+        "TIMESTAMP '2024-01-01 12:30:00+09'",
+        "TIMESTAMP '2024-01-01 12:30:00.45-08:00'",
+        "TIMESTAMP '2024-01-01T12:30:00Z'",
+        "TIMESTAMP '2024-01-01 12:30:00 America/Los_Angeles'",
+        "TIMESTAMP '2024-01-01 UTC'",
+        "INTERVAL 1 DAY",
+        "INTERVAL x + 1 MICROSECOND",
+        "INTERVAL '-5' QUARTER",
+        "INTERVAL '1.5' SECOND",
+        "INTERVAL '1-2' YEAR TO MONTH",
+        "INTERVAL '-1-11 3 -4:5:6.789' YEAR TO SECOND",
+        "INTERVAL '10 23' DAY TO HOUR",
+        "INTERVAL '100:59' HOUR TO MINUTE",
+        "RANGE<DATE> '[2024-01-01, UNBOUNDED)'",
+        "RANGE<DATETIME> '[UNBOUNDED, NULL)'",
+        "RANGE<TIMESTAMP> '[2024-01-01 00:00:00+09, 2023-12-31 16:00:00)'",
+        "RANGE<TIMESTAMP> '[2024-01-01 Asia/Tokyo, 2024-01-01 UTC)'",
+        "JSON '{\"a\": [1, -2.5e3, true, null], \"b\\\\u00e9\": {}}'",
+        "JSON ' \"abc\" '",
+        "NUMERIC '1.5'",
+        "BIGNUMERIC '1e30'",
+    ] {
+        assert_eq!(
+            diagnostics(&format!("SELECT {};", expr)),
+            vec![],
+            "{}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn test_invalid_typed_literals() {
+    // they are syntactically valid, so `parse_code()` succeeds
+    for (expr, column) in vec![
+        ("DATE '2023-02-29'", 13),
+        ("DATE '2024-13-01'", 13),
+        ("DATE '2024/01/01'", 13),
+        ("DATE b'2024-01-01'", 13),
+        ("TIME '24:00:00'", 13),
+        ("TIME '12:00:00.1234567'", 13),
+        ("DATETIME '2024-01-01 12:60:00'", 17),
+        ("DATETIME '2024-01-01+09'", 17),
+        ("TIMESTAMP '2024-01-01 12:00:00+15'", 18),
+        ("TIMESTAMP '2024-01-01 12:00:00 ???'", 18),
+        ("INTERVAL 1 DAYS", 19),
+        ("INTERVAL '1' MONTH TO YEAR", 30),
+        ("INTERVAL '1' WEEK TO DAY", 29),
+        ("INTERVAL '1.5' DAY", 17),
+        ("INTERVAL '1-12' YEAR TO MONTH", 17),
+        ("INTERVAL '1 2' YEAR TO MONTH", 17),
+        ("INTERVAL '1:60' HOUR TO MINUTE", 17),
+        ("INTERVAL '1 2:3' DAY TO SECOND", 17),
+        ("INTERVAL '10:70' HOUR TO MINUTE", 17),
+        ("RANGE<INT64> '[1, 2)'", 21),
+        ("RANGE<DATE> '[2024-01-01, 2024-02-30)'", 20),
+        ("RANGE<DATE> '(2024-01-01, UNBOUNDED)'", 20),
+        ("RANGE<DATE> '[2024-01-02, 2024-01-01)'", 20),
+        ("RANGE<DATE> '[2024-01-01, 2024-01-01)'", 20),
+        ("JSON '{\"a\": 1,}'", 13),
+        ("JSON '[01]'", 13),
+        ("JSON \"{'a': 1}\"", 13),
+        ("JSON ''", 13),
+        ("NUMERIC '1e30'", 16),
+    ] {
+        assert_eq!(
+            diagnostics(&format!("SELECT {};", expr)),
+            vec![(1, column)],
+            "{}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn test_multiple_diagnostics() {
+    let stmts = parse("SELECT DATE '2024-02-30',\n  TIME '25:00';\nSELECT JSON '{';");
+    let diagnostics: Vec<(DiagnosticKind, String, usize, usize, usize, usize, usize)> =
+        check_typed_literals(&stmts)
+            .into_iter()
+            .map(|d| {
+                (
+                    d.kind,
+                    d.message,
+                    d.statement,
+                    d.line,
+                    d.column,
+                    d.end_line,
+                    d.end_column,
+                )
+            })
+            .collect();
+    assert_eq!(
+        diagnostics,
+        vec![
+            (
+                DiagnosticKind::InvalidLiteral,
+                "Invalid DATE literal: '2024-02-30'".to_string(),
+                0,
+                1,
+                13,
+                1,
+                26 // including `,`
+            ),
+            (
+                DiagnosticKind::InvalidLiteral,
+                "Invalid TIME literal: '25:00'".to_string(),
+                0,
+                2,
+                8,
+                2,
+                15
+            ),
+            (
+                DiagnosticKind::InvalidLiteral,
+                "Invalid JSON literal: '{'".to_string(),
+                1,
+                3,
+                13,
+                3,
+                16
+            ),
+        ]
+    );
+}
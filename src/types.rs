@@ -3,13 +3,44 @@ use wasm_bindgen::prelude::*;
 #[wasm_bindgen(typescript_custom_section)]
 const NODES: &'static str = r#"
 export function parse(code: string): UnknownNode[];
+export function parse_sqlx(code: string): UnknownNode[];
+export function parse_with_dynamic_sql(code: string): UnknownNode[];
+export function parse_jinja_branches(code: string): UnknownNode[][];
 export function tokenize(code: string): Token[];
+export function extract_docs(code: string): ObjectDoc[];
+export function get_suppressions(code: string): Suppression[];
+export function decode_literal(literal: string): DecodedLiteral;
+export function numeric_value(node: UnknownNode): NumericValue | null;
+export function canonical_type(node: Type): Type | null;
+export function expand_macros(code: string): MacroExpansion;
+export function collect_parameters(code: string): ParameterInventory;
+export function analyze_privacy(code: string): PrivacyPolicy[];
+export function extract_functions(code: string): FunctionDefinition[];
+export function build_catalog(code: string): Catalog;
+export function validate_columns(
+  code: string,
+  schemas?: { [table: string]: TableFieldSchema[] },
+): Diagnostic[];
+export function infer_types(
+  code: string,
+  schemas?: { [table: string]: TableFieldSchema[] },
+): TypeReport;
+export function check_functions(code: string): Diagnostic[];
+export function check_arguments(code: string): Diagnostic[];
+export function check_typed_literals(code: string): Diagnostic[];
+export function analyze_control_flow(code: string): ControlFlowGraph;
+export function check_variables(code: string): Diagnostic[];
+export function build_dependency_graph(files: {
+  [file: string]: string;
+}): DependencyGraph;
+export function fingerprint_query(code: string): QueryFingerprint;
 
 export type UnknownNode =
   | AccessOperator
   | AddColumnClause
   | AddConstraintClause
   | AggregatePipeOperator
+  | AggregationThresholdClause
   | AlterBICapacityStatement
   | AlterColumnStatement
   | AlterModelStatement
@@ -22,6 +53,7 @@ export type UnknownNode =
   | AlterVectorIndexStatement
   | AlterViewStatement
   | ArrayLiteral
+  | AssertPipeOperator
   | AssertStatement
   | Asterisk
   | BasePipeOperator
@@ -45,13 +77,16 @@ export type UnknownNode =
   | CreateIndexStatement
   | CreateModelStatement
   | CreateProcedureStatement
+  | CreatePropertyGraphStatement
   | CreateReservationStatement
   | CreateRowAccessPolicyStatement
   | CreateSchemaStatement
   | CreateTableStatement
   | CreateViewStatement
   | DeclareStatement
+  | DefineMacroStatement
   | DeleteStatement
+  | DescribePipeOperator
   | DifferentialPrivacyClause
   | DotOperator
   | DropRowAccessPolicyStatement
@@ -64,10 +99,19 @@ export type UnknownNode =
   | ExportModelStatement
   | ExtractArgument
   | ForStatement
+  | ForkPipeOperator
   | ForSystemTimeAsOfClause
   | FromStatement
   | FunctionChain
   | GrantStatement
+  | GraphEdgePattern
+  | GraphLabel
+  | GraphMatchOperator
+  | GraphNodePattern
+  | GraphPropertySpec
+  | GraphStatement
+  | GraphTableConfig
+  | GraphTableExpr
   | GroupByExprs
   | GroupedExpr
   | GroupedExprs
@@ -78,6 +122,7 @@ export type UnknownNode =
   | GroupedType
   | Identifier
   | IfStatement
+  | IfPipeOperator
   | IdentWithOptions
   | InOperator
   | InsertStatement
@@ -96,7 +141,9 @@ export type UnknownNode =
   | LimitClause
   | LimitPipeOperator
   | LoadStatement
+  | LogPipeOperator
   | LoopStatement
+  | MacroInvocation
   | MatchRecognizeClause
   | MatchRecognizeConfig
   | MatchRecognizePipeOperator
@@ -117,6 +164,7 @@ export type UnknownNode =
   | PivotConfig
   | RaiseStatement
   | RangeLiteral
+  | RecursiveUnionPipeOperator
   | RenameColumnClause
   | RepeatStatement
   | RevokeStatement
@@ -125,8 +173,10 @@ export type UnknownNode =
   | SetOperator
   | SetStatement
   | SingleTokenStatement
+  | SqlxBlock
   | StringLiteral
   | StructLiteral
+  | Subpipeline
   | Symbol_
   | TableSampleClause
   | TableSamplePipeOperator
@@ -149,6 +199,7 @@ export type UnknownNode =
   | WindowClause
   | WindowExpr
   | WindowFrameClause
+  | WindowPipeOperator
   | WindowSpecification
   | WithClause
   | WithOffsetClause
@@ -163,6 +214,213 @@ export type Token = {
   literal: string;
 };
 
+export type ObjectDoc = {
+  object: string;
+  kind: string;
+  line: number;
+  column: number;
+  comment: string | null;
+  description: string | null;
+  parameters: ColumnDoc[];
+  columns: ColumnDoc[];
+  returns: string | null;
+};
+
+export type ColumnDoc = {
+  name: string | null;
+  type: string | null;
+  comment: string | null;
+  description: string | null;
+  fields: ColumnDoc[];
+};
+
+export type Suppression = {
+  rules: string[];
+  start_line: number;
+  end_line: number;
+  statements: number[];
+};
+
+export type BQ2CSTError = {
+  line: number;
+  column: number;
+  message: string;
+  kind: "Syntax" | "LegacySql";
+};
+
+export type Catalog = {
+  datasets: {
+    name: string;
+    options: { [key: string]: string };
+    tables: CatalogTable[];
+  }[];
+  issues: { message: string; statement: number; line: number; column: number }[];
+};
+
+export type CatalogTable = {
+  name: string;
+  kind: "Table" | "ExternalTable" | "View" | "MaterializedView";
+  temp: boolean;
+  schema: TableFieldSchema[];
+  options: { [key: string]: string };
+  primary_key: string[];
+  foreign_keys: {
+    name: string | null;
+    columns: string[];
+    referenced_table: string;
+    referenced_columns: string[];
+  }[];
+  partition_by: string | null;
+  cluster_by: string[];
+};
+
+export type TableFieldSchema = {
+  name: string;
+  type: string;
+  mode: "NULLABLE" | "REQUIRED" | "REPEATED";
+  fields?: TableFieldSchema[];
+  description?: string;
+  maxLength?: string;
+  precision?: string;
+  scale?: string;
+  collation?: string;
+  defaultValueExpression?: string;
+  rangeElementType?: { type: string };
+};
+
+export type Diagnostic = {
+  kind:
+    | "UnknownTable"
+    | "UnknownColumn"
+    | "AmbiguousColumn"
+    | "InvalidFieldAccess"
+    | "TypeMismatch"
+    | "UnknownFunction"
+    | "WrongArgumentCount"
+    | "InvalidFunctionCall"
+    | "InvalidArgument"
+    | "InvalidControlFlow"
+    | "InvalidLabel"
+    | "UnreachableStatement"
+    | "MisplacedDeclaration"
+    | "UndeclaredVariable"
+    | "DuplicateVariable"
+    | "WrongValueCount"
+    | "InvalidLiteral";
+  message: string;
+  statement: number;
+  line: number;
+  column: number;
+  end_line: number;
+  end_column: number;
+};
+
+export type ControlFlowGraph = {
+  nodes: {
+    kind: "Entry" | "Exit" | "Statement" | "Clause";
+    statement: number;
+    line: number;
+    column: number;
+  }[];
+  edges: [number, number][];
+  diagnostics: Diagnostic[];
+};
+
+export type DatabaseObject = {
+  kind: "Schema" | "Table" | "View" | "Function" | "Procedure" | "Model";
+  name: string;
+};
+
+export type DependencyGraph = {
+  files: string[];
+  statements: {
+    file: number;
+    statement: number;
+    line: number;
+    column: number;
+    creates: DatabaseObject[];
+    references: DatabaseObject[];
+  }[];
+  edges: [number, number][];
+  order: number[];
+  cycles: number[][];
+};
+
+export type QueryFingerprint = {
+  normalized: string;
+  hash: string;
+};
+
+export type TypeReport = {
+  types: { type: string; literal: string; line: number; column: number }[];
+  diagnostics: Diagnostic[];
+};
+
+export type DecodedLiteral = {
+  value: { type: "STRING"; value: string } | { type: "BYTES"; value: number[] };
+  positions: [number, number][];
+};
+
+export type NumericValue =
+  | { type: "INT64"; value: string }
+  | { type: "FLOAT64"; value: number }
+  | { type: "NUMERIC"; value: string }
+  | { type: "BIGNUMERIC"; value: string };
+
+export type FunctionDefinition = {
+  name: string;
+  language: string | null;
+  table: boolean;
+  aggregate: boolean;
+  params: { name: string; type: string; not_aggregate: boolean }[];
+  returns: string | null;
+  library: string[];
+  body: {
+    value: string;
+    positions: [number, number][];
+    start: [number, number];
+    end: [number, number];
+    raw: boolean;
+  } | null;
+  statement: number;
+  line: number;
+  column: number;
+};
+
+export type MacroExpansion = {
+  code: string;
+  positions: [number, number][];
+};
+
+export type ParameterInventory = {
+  named: string[];
+  positional_count: number;
+  mixed: boolean;
+  system_variables: string[];
+  usages: ParameterUsage[];
+};
+
+export type ParameterUsage = {
+  kind: "Named" | "Positional" | "SystemVariable";
+  name: string | null;
+  index: number | null;
+  context: string;
+  statement: number;
+  line: number;
+  column: number;
+};
+
+export type PrivacyPolicy = {
+  kind: "AggregationThreshold" | "DifferentialPrivacy";
+  clause: string;
+  privacy_unit_column: string | null;
+  threshold: string | null;
+  options: [string, string][];
+  statement: number;
+  line: number;
+  column: number;
+};
+
 interface BaseNode {
   token: Token | null;
   node_type: string;
@@ -174,6 +432,7 @@ interface BaseNode {
 
 export type NodeChild = { Node: UnknownNode };
 export type NodeVecChild = { NodeVec: UnknownNode[] };
+export type ErrorChild = { Error: BQ2CSTError };
 
 // ----- sub types of BaseNode (abstract) -----
 export type CallingFunctionGeneral = Expr & {
@@ -223,6 +482,12 @@ export type IdentifierGeneral = FromItemExpr & {
     // TABLESAMPLE SYSTEM can only be applied directly to base tables
     tablesample?: NodeChild;
     for_system_time_as_of?: NodeChild;
+    // element table of CREATE PROPERTY GRAPH
+    key?: NodeChild;
+    source?: NodeChild;
+    destination?: NodeChild;
+    labels?: NodeVecChild;
+    properties?: NodeChild;
   };
 };
 
@@ -262,10 +527,21 @@ export type AddConstraintClause = BaseNode & {
 export type AggregatePipeOperator = PipeOperator & {
   node_type: "AggregatePipeOperator";
   children: {
+    aggregation_threshold?: NodeChild;
+    differential_privacy?: NodeChild;
     groupby?: NodeChild;
   }
 }
 
+export type AggregationThresholdClause = BaseNode & {
+  token: Token;
+  node_type: "AggregationThresholdClause";
+  children: {
+    aggregation_threshold: NodeChild;
+    options?: NodeChild;
+  };
+};
+
 export type AlterBICapacityStatement = XXXStatement & {
   node_type: "AlterBICapacityStatement";
   children: {
@@ -449,6 +725,10 @@ export type Asterisk = Expr & {
   };
 };
 
+export type AssertPipeOperator = PipeOperator & {
+  node_type: "AssertPipeOperator";
+}
+
 export type BasePipeOperator = PipeOperator & {
   node_type: "BasePipeOperator";
 }
@@ -663,6 +943,19 @@ export type CreateProcedureStatement = XXXStatement & {
   };
 };
 
+export type CreatePropertyGraphStatement = XXXStatement & {
+  node_type: "CreatePropertyGraphStatement";
+  children: {
+    or_replace?: NodeVecChild;
+    what: NodeChild;
+    if_not_exists?: NodeVecChild;
+    ident: NodeChild;
+    node_tables: NodeChild;
+    edge_tables?: NodeChild;
+    options?: NodeChild;
+  };
+};
+
 export type CreateReservationStatement = XXXStatement & {
   node_type: "CreateReservationStatement";
   children: {
@@ -768,6 +1061,15 @@ export type DeclareStatement = XXXStatement & {
   };
 };
 
+export type DefineMacroStatement = XXXStatement & {
+  node_type: "DefineMacroStatement";
+  children: {
+    what: NodeChild;
+    ident: NodeChild;
+    body?: NodeVecChild;
+  };
+};
+
 export type DeleteStatement = XXXStatement & {
   node_type: "DeleteStatement";
   children: {
@@ -777,6 +1079,13 @@ export type DeleteStatement = XXXStatement & {
   };
 };
 
+export type DescribePipeOperator = PipeOperator & {
+  node_type: "DescribePipeOperator";
+  children: {
+    exprs: undefined;
+  }
+}
+
 export type DifferentialPrivacyClause = BaseNode & {
   token: Token;
   node_type: "DifferentialPrivacyClause";
@@ -846,6 +1155,8 @@ export type ExecuteStatement = XXXStatement & {
   node_type: "ExecuteStatement";
   children: {
     immediate: NodeChild;
+    stmts?: NodeVecChild;
+    error?: ErrorChild;
     sql_expr: NodeChild;
     into: NodeChild;
     using?: NodeChild;
@@ -892,6 +1203,14 @@ export type ForStatement = LabelableStatement & {
   };
 };
 
+export type ForkPipeOperator = PipeOperator & {
+  node_type: "ForkPipeOperator";
+  children: {
+    exprs: undefined;
+    subpipelines: NodeVecChild;
+  }
+}
+
 export type ForSystemTimeAsOfClause = BaseNode & {
   token: Token;
   node_type: "ForSystemTimeAsOfClause";
@@ -928,6 +1247,88 @@ export type GrantStatement = XXXStatement & {
   };
 };
 
+export type GraphEdgePattern = BaseNode & {
+  token: Token;
+  node_type: "GraphEdgePattern";
+  children: {
+    var?: NodeChild;
+    label?: NodeChild;
+    properties?: NodeChild;
+    where?: NodeChild;
+    rbracket?: NodeChild;
+    quantifier?: NodeChild;
+    comma?: NodeChild;
+  };
+};
+
+export type GraphLabel = BaseNode & {
+  token: Token;
+  node_type: "GraphLabel";
+  children: {
+    default?: NodeChild;
+    name?: NodeChild;
+    properties?: NodeChild;
+  };
+};
+
+export type GraphMatchOperator = BaseNode & {
+  token: Token;
+  node_type: "GraphMatchOperator";
+  children: {
+    optional?: NodeChild;
+    patterns: NodeVecChild;
+    where?: NodeChild;
+  };
+};
+
+export type GraphNodePattern = BaseNode & {
+  token: Token;
+  node_type: "GraphNodePattern";
+  children: {
+    var?: NodeChild;
+    label?: NodeChild;
+    properties?: NodeChild;
+    where?: NodeChild;
+    rparen: NodeChild;
+    comma?: NodeChild;
+  };
+};
+
+export type GraphPropertySpec = BaseNode & {
+  token: Token;
+  node_type: "GraphPropertySpec";
+  children: {
+    exprs: NodeVecChild;
+    rbrace: NodeChild;
+  };
+};
+
+export type GraphStatement = XXXStatement & {
+  node_type: "GraphStatement";
+  children: {
+    ident: NodeChild;
+    operators: NodeVecChild;
+  };
+};
+
+export type GraphTableConfig = BaseNode & {
+  token: Token;
+  node_type: "GraphTableConfig";
+  children: {
+    ident: NodeChild;
+    operators: NodeVecChild;
+    columns?: NodeChild;
+    rparen: NodeChild;
+  };
+};
+
+export type GraphTableExpr = Expr & {
+  node_type: "GraphTableExpr";
+  children: {
+    config: NodeChild;
+  };
+};
+
 export type GroupByExprs = BaseNode & {
   token: Token;
   node_type: "GroupByExprs";
@@ -1033,6 +1434,17 @@ export type IfStatement = XXXStatement & {
   };
 };
 
+export type IfPipeOperator = PipeOperator & {
+  node_type: "IfPipeOperator";
+  children: {
+    exprs: undefined;
+    condition: NodeChild;
+    then: NodeChild;
+    elseifs?: NodeVecChild;
+    else?: NodeChild;
+  }
+}
+
 export type InOperator = Expr & {
   node_type: "InOperator";
   children: {
@@ -1168,6 +1580,22 @@ export type LimitPipeOperator = PipeOperator & {
   }
 }
 
+export type LogPipeOperator = PipeOperator & {
+  node_type: "LogPipeOperator";
+  children: {
+    exprs: undefined;
+    subpipeline?: NodeChild;
+  }
+}
+
+export type MacroInvocation = IdentifierGeneral & {
+  node_type: "MacroInvocation";
+  children: {
+    args?: { Node: GroupedExprs };
+    semicolon?: { Node: Symbol_ };
+  };
+};
+
 export type MatchRecognizeClause = BaseNode & {
   token: Token;
   node_type: "MatchRecognizeClause";
@@ -1315,7 +1743,7 @@ export type PatternQuantifier = BaseNode & {
 export type PipeStatement = XXXStatement & {
   node_type: "PipeStatement";
   children: {
-    left: NodeChild;
+    left?: NodeChild; // undefined in subpipeline
     right: NodeChild;
   };
 }
@@ -1365,6 +1793,18 @@ export type RangeLiteral = Expr & {
   };
 };
 
+export type RecursiveUnionPipeOperator = PipeOperator & {
+  node_type: "RecursiveUnionPipeOperator";
+  children: {
+    union: NodeChild;
+    by?: NodeChild;
+    corresponding?: NodeChild;
+    subpipeline?: NodeChild;
+    as?: NodeChild;
+    alias?: NodeChild;
+  }
+}
+
 export type RenameColumnClause = BaseNode & {
   token: Token;
   node_type: "RenameColumnClause";
@@ -1403,6 +1843,7 @@ export type SelectStatement = XXXStatement & {
   children: {
     with?: { Node: WithClause };
     differential_privacy?: NodeChild;
+    aggregation_threshold?: NodeChild;
     as_struct_or_value?: NodeVecChild;
     distinct_or_all?: NodeChild;
     exprs: NodeVecChild;
@@ -1420,6 +1861,8 @@ export type SelectStatement = XXXStatement & {
 export type SelectPipeOperator = PipeOperator & {
   node_type: "SelectPipeOperator";
   children: {
+    aggregation_threshold?: NodeChild;
+    differential_privacy?: NodeChild;
     window?: NodeChild;
   };
 }
@@ -1449,6 +1892,10 @@ export type SingleTokenStatement = XXXStatement & {
   node_type: "SingleTokenStatement";
 };
 
+export type SqlxBlock = XXXStatement & {
+  node_type: "SqlxBlock";
+};
+
 export type StringLiteral = Expr & {
   node_type: "StringLiteral";
 };
@@ -1462,6 +1909,16 @@ export type StructLiteral = Expr & {
   };
 };
 
+export type Subpipeline = BaseNode & {
+  token: Token;
+  node_type: "Subpipeline";
+  children: {
+    stmt?: { Node: PipeStatement };
+    rparen: NodeChild;
+    comma?: NodeChild;
+  };
+};
+
 export type Symbol_ = BaseNode & {
   token: Token;
   node_type: "Symbol";
@@ -1668,6 +2125,10 @@ export type WindowFrameClause = BaseNode & {
   };
 };
 
+export type WindowPipeOperator = PipeOperator & {
+  node_type: "WindowPipeOperator";
+}
+
 export type WindowSpecification = BaseNode & {
   token: Token;
   node_type: "WindowSpecification";
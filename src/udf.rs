@@ -0,0 +1,198 @@
+#[cfg(test)]
+mod tests;
+
+use crate::bqtype::BqType;
+use crate::cst::{Node, NodeType};
+use crate::error::BQ2CSTResult;
+use crate::literal::{self, LiteralValue};
+use serde::Serialize;
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct FunctionParameter {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String, // e.g. ARRAY<STRING>
+    pub not_aggregate: bool,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct FunctionBody {
+    pub value: String, // decoded
+    // (line, column) of each character of value in the source
+    pub positions: Vec<(usize, usize)>,
+    pub start: (usize, usize), // the first character inside the quotes
+    pub end: (usize, usize),   // the closing quote
+    pub raw: bool,             // if true, value is identical to the source between start and end
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct FunctionDefinition {
+    pub name: String,
+    pub language: Option<String>, // e.g. js, python (None for SQL)
+    pub table: bool,
+    pub aggregate: bool,
+    pub params: Vec<FunctionParameter>,
+    pub returns: Option<String>,
+    pub library: Vec<String>,
+    pub body: Option<FunctionBody>, // only if the body is a string literal
+    pub statement: usize,           // index of the statement
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Lists the functions defined by `CREATE FUNCTION` statements.
+pub fn extract_functions(stmts: &[Node]) -> BQ2CSTResult<Vec<FunctionDefinition>> {
+    let mut functions = Vec::new();
+    for (idx, stmt) in stmts.iter().enumerate() {
+        walk(stmt, idx, &mut functions)?;
+    }
+    Ok(functions)
+}
+
+fn walk(
+    node: &Node,
+    statement: usize,
+    functions: &mut Vec<FunctionDefinition>,
+) -> BQ2CSTResult<()> {
+    if node.node_type == NodeType::CreateFunctionStatement {
+        functions.push(definition(node, statement)?);
+        return Ok(());
+    }
+    for (_, child) in node.child_nodes() {
+        walk(child, statement, functions)?;
+    }
+    Ok(())
+}
+
+fn definition(node: &Node, statement: usize) -> BQ2CSTResult<FunctionDefinition> {
+    let name = match node.get_node("ident").and_then(|i| i.identifier_parts()) {
+        Some(parts) => parts.join("."),
+        None => "".to_string(),
+    };
+    let language = node
+        .get_node("language")
+        .and_then(|l| l.get_node("expr"))
+        .map(|e| e.literal().to_string());
+    let params = node
+        .get_node("group")
+        .and_then(|g| g.get_node_vec("declarations"))
+        .into_iter()
+        .flatten()
+        .map(|decl| FunctionParameter {
+            name: decl.literal().trim_matches('`').to_string(),
+            type_: decl.get_node("type").map(type_text).unwrap_or_default(),
+            not_aggregate: decl
+                .get_node("type")
+                .is_some_and(|t| t.get_node("aggregate").is_some()),
+        })
+        .collect();
+    let returns = node
+        .get_node("returns")
+        .and_then(|r| r.get_node("type"))
+        .map(type_text);
+
+    let mut library = Vec::new();
+    let options = node
+        .get_node("options")
+        .and_then(|o| o.get_node("group"))
+        .and_then(|g| g.get_node_vec("exprs"));
+    for option in options.into_iter().flatten() {
+        let (Some(left), Some(right)) = (option.get_node("left"), option.get_node("right")) else {
+            continue;
+        };
+        if !left.literal().eq_ignore_ascii_case("library") {
+            continue;
+        }
+        let values = match right.get_node_vec("exprs") {
+            Some(exprs) => exprs.iter().collect(),
+            None => vec![right],
+        };
+        for value in values {
+            if let Some(s) = string_value(value)? {
+                library.push(s);
+            }
+        }
+    }
+
+    let body = match node.get_node("as").and_then(|a| a.get_node("expr")) {
+        Some(expr) if expr.node_type == NodeType::StringLiteral => Some(body(expr)?),
+        _ => None,
+    };
+    let (line, column) = node.position();
+    Ok(FunctionDefinition {
+        name,
+        language,
+        table: node.get_node("table").is_some(),
+        aggregate: node.get_node("aggregate").is_some(),
+        params,
+        returns,
+        library,
+        body,
+        statement,
+        line,
+        column,
+    })
+}
+
+fn string_value(node: &Node) -> BQ2CSTResult<Option<String>> {
+    if node.node_type != NodeType::StringLiteral {
+        return Ok(None);
+    }
+    match literal::decode_string_literal(node.token.as_ref().unwrap())?.value {
+        LiteralValue::String(s) => Ok(Some(s)),
+        LiteralValue::Bytes(_) => Ok(None),
+    }
+}
+
+fn body(node: &Node) -> BQ2CSTResult<FunctionBody> {
+    let token = node.token.as_ref().unwrap();
+    let decoded = literal::decode_string_literal(token)?;
+    let value = match decoded.value {
+        LiteralValue::String(s) => s,
+        LiteralValue::Bytes(_) => "".to_string(),
+    };
+    let chars: Vec<char> = token.literal.chars().collect();
+    let prefix_len = chars
+        .iter()
+        .take_while(|c| **c != '\'' && **c != '"')
+        .count();
+    let quote = chars[prefix_len];
+    let quote_len = if 6 <= chars.len() - prefix_len
+        && chars[prefix_len + 1] == quote
+        && chars[prefix_len + 2] == quote
+    {
+        3
+    } else {
+        1
+    };
+    // (line, column) of each char of the literal
+    let mut positions = Vec::new();
+    let (mut line, mut column) = (token.line, token.column);
+    for ch in &chars {
+        positions.push((line, column));
+        if *ch == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    Ok(FunctionBody {
+        value,
+        positions: decoded.positions,
+        start: positions[prefix_len + quote_len],
+        end: positions[chars.len() - quote_len],
+        raw: chars[..prefix_len]
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(&'r')),
+    })
+}
+
+/// Formats a Type node, e.g. `ARRAY<STRUCT<x INT64>>`, `NUMERIC(10, 2)`.
+/// Unknown types are printed as they are written.
+fn type_text(node: &Node) -> String {
+    match BqType::from_type(node) {
+        Some(type_) => type_.to_string(),
+        None => node.literal().to_uppercase(),
+    }
+}
@@ -0,0 +1,95 @@
+use super::*;
+use crate::validate::tests::parse;
+
+fn functions(code: &str) -> Vec<FunctionDefinition> {
+    extract_functions(&parse(code)).expect("Failed to extract functions.")
+}
+
+#[test]
+fn test_extract_functions_js() {
+    let res = functions(
+        "\
+CREATE TEMP FUNCTION ds.f(x INT64, y ARRAY<STRUCT<a STRING(10), b NUMERIC(10, 2)>>)
+RETURNS FLOAT64 LANGUAGE js
+OPTIONS (library = ['gs://a/b.js', \"gs://c.js\"])
+AS r'''
+  return x * 2;
+''';
+",
+    );
+    assert_eq!(res.len(), 1);
+    let f = &res[0];
+    assert_eq!(f.name, "ds.f");
+    assert_eq!(f.language.as_deref(), Some("js"));
+    assert_eq!(
+        f.params,
+        vec![
+            FunctionParameter {
+                name: "x".to_string(),
+                type_: "INT64".to_string(),
+                not_aggregate: false,
+            },
+            FunctionParameter {
+                name: "y".to_string(),
+                type_: "ARRAY<STRUCT<a STRING(10), b NUMERIC(10, 2)>>".to_string(),
+                not_aggregate: false,
+            },
+        ]
+    );
+    assert_eq!(f.returns.as_deref(), Some("FLOAT64"));
+    assert_eq!(f.library, vec!["gs://a/b.js", "gs://c.js"]);
+    let body = f.body.as_ref().unwrap();
+    assert_eq!(body.value, "\n  return x * 2;\n");
+    assert!(body.raw);
+    assert_eq!(body.start, (4, 8));
+    assert_eq!(body.end, (6, 1));
+    assert_eq!(body.positions[3], (5, 3)); // `r` of return
+    assert_eq!((f.statement, f.line, f.column), (0, 1, 1));
+}
+
+#[test]
+fn test_extract_functions_others() {
+    let res = functions(
+        "\
+SELECT 1;
+CREATE FUNCTION f(s STRING, n INT64 NOT AGGREGATE) RETURNS STRING LANGUAGE python
+OPTIONS (entry_point = 'main', library = 'gs://x.zip')
+AS 'def main(s, n):\\n  return s';
+CREATE AGGREGATE FUNCTION g(x ANY TYPE) AS (SUM(x));
+CREATE TABLE FUNCTION h(x INT64) RETURNS TABLE<a INT64> AS SELECT x AS a;
+",
+    );
+    let summary: Vec<(&str, Option<&str>, bool, bool, Option<&str>, usize)> = res
+        .iter()
+        .map(|f| {
+            (
+                f.name.as_str(),
+                f.language.as_deref(),
+                f.table,
+                f.aggregate,
+                f.returns.as_deref(),
+                f.statement,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("f", Some("python"), false, false, Some("STRING"), 1),
+            ("g", None, false, true, None, 2),
+            ("h", None, true, false, Some("TABLE<a INT64>"), 3),
+        ]
+    );
+    assert!(res[0].params[1].not_aggregate);
+    assert_eq!(res[0].library, vec!["gs://x.zip"]);
+    let body = res[0].body.as_ref().unwrap();
+    assert_eq!(body.value, "def main(s, n):\n  return s");
+    assert!(!body.raw);
+    assert_eq!(body.start, (4, 5));
+    assert_eq!(body.end, (4, 32));
+    // `\n` is decoded into one char
+    assert_eq!(body.positions[15], (4, 20));
+    assert_eq!(res[1].params[0].type_, "ANY TYPE");
+    assert!(res[1].body.is_none());
+    assert!(res[2].body.is_none());
+}
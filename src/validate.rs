@@ -0,0 +1,1225 @@
+#[cfg(test)]
+pub(crate) mod tests;
+
+use crate::bqtype::{BqType, ScalarType};
+use crate::catalog::{build_catalog, Catalog};
+use crate::cst::{Node, NodeType};
+use crate::typecheck::{self, ExprType, Operand};
+use serde::Serialize;
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub enum DiagnosticKind {
+    UnknownTable,         // FROM ds.not_exist
+    UnknownColumn,        // SELECT not_exist FROM ds.t
+    AmbiguousColumn,      // SELECT id FROM ds.t1 JOIN ds.t2 ON ...
+    InvalidFieldAccess,   // SELECT int64_col.x FROM ds.t
+    TypeMismatch,         // SELECT 'a' = 1 (reported by infer_types and check_variables)
+    UnknownFunction,      // SELECT not_exist(1) (reported by check_functions)
+    WrongArgumentCount,   // SELECT SUBSTR('a')
+    InvalidFunctionCall,  // SELECT UPPER(x) OVER ()
+    InvalidArgument,      // SELECT REGEXP_CONTAINS(s, '(') (reported by check_arguments)
+    InvalidControlFlow,   // BREAK outside of loops (reported by analyze_control_flow)
+    InvalidLabel,         // BREAK not_exist
+    UnreachableStatement, // SELECT 1 after RETURN
+    MisplacedDeclaration, // DECLARE after other statements (reported by check_variables)
+    UndeclaredVariable,   // SET not_declared = 1
+    DuplicateVariable,    // DECLARE x INT64; DECLARE x STRING;
+    WrongValueCount,      // SET (a, b) = (1, 2, 3)
+    InvalidLiteral,       // SELECT DATE '2024-02-30' (reported by check_typed_literals)
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub message: String,
+    pub statement: usize, // index of the statement
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize, // the position just after the node
+    pub end_column: usize,
+}
+
+impl Diagnostic {
+    /// The diagnostic which spans the whole node.
+    pub fn new(kind: DiagnosticKind, message: String, statement: usize, node: &Node) -> Diagnostic {
+        let (line, column) = node.position();
+        let (end_line, end_column) = node.end_position();
+        Diagnostic {
+            kind,
+            message,
+            statement,
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+}
+
+/// Checks the table and column references of the queries against the catalog.
+/// References which cannot be resolved statically (e.g. columns of a table
+/// whose schema is unknown) are not reported.
+pub fn validate_columns(stmts: &[Node], catalog: &Catalog) -> Vec<Diagnostic> {
+    walk(stmts, catalog, false).0
+}
+
+/// Resolves the references in the queries. If `infer` is true, the types of the
+/// expressions are also collected and type errors are reported.
+pub(crate) fn walk(
+    stmts: &[Node],
+    catalog: &Catalog,
+    infer: bool,
+) -> (Vec<Diagnostic>, Vec<ExprType>) {
+    let mut validator = Validator {
+        catalog,
+        ctes: Vec::new(),
+        variables: Vec::new(),
+        statement: 0,
+        diagnostics: Vec::new(),
+        infer,
+        types: Vec::new(),
+    };
+    for stmt in stmts {
+        validator.collect_variables(stmt);
+    }
+    for (idx, stmt) in stmts.iter().enumerate() {
+        validator.statement = idx;
+        validator.statement(stmt, false);
+    }
+    (validator.diagnostics, validator.types)
+}
+
+/// Infers the type of the expression outside of queries (e.g. `DECLARE x DEFAULT expr`).
+/// `variables` are the names and the types of the variables which can be referred to.
+pub(crate) fn expr_type(node: &Node, variables: &[(String, Option<BqType>)]) -> Option<BqType> {
+    let catalog = build_catalog(&[]);
+    let mut validator = Validator {
+        catalog: &catalog,
+        ctes: Vec::new(),
+        variables: Vec::new(),
+        statement: 0,
+        diagnostics: Vec::new(),
+        infer: false,
+        types: Vec::new(),
+    };
+    let sources = variables
+        .iter()
+        .map(|(name, type_)| Source {
+            name: Some(name.clone()),
+            columns: None,
+            value: Some(Column {
+                name: name.clone(),
+                type_: type_.clone(),
+            }),
+        })
+        .collect();
+    let mut scopes = vec![Scope {
+        sources,
+        ..Default::default()
+    }];
+    validator.expr(node, &mut scopes, false)
+}
+
+// e.g. DATE_TRUNC(d, MONTH), DATE_DIFF(a, b, WEEK(MONDAY))
+const DATE_PARTS: [&str; 24] = [
+    "MICROSECOND",
+    "MILLISECOND",
+    "SECOND",
+    "MINUTE",
+    "HOUR",
+    "DAY",
+    "DAYOFWEEK",
+    "DAYOFYEAR",
+    "WEEK",
+    "ISOWEEK",
+    "MONTH",
+    "QUARTER",
+    "YEAR",
+    "ISOYEAR",
+    "DATE",
+    "DATETIME",
+    "TIME",
+    "SUNDAY",
+    "MONDAY",
+    "TUESDAY",
+    "WEDNESDAY",
+    "THURSDAY",
+    "FRIDAY",
+    "SATURDAY",
+];
+
+// functions which can be called without parentheses and pseudo columns
+const BUILTIN_NAMES: [&str; 9] = [
+    "CURRENT_DATE",
+    "CURRENT_DATETIME",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+    "_PARTITIONDATE",
+    "_PARTITIONTIME",
+    "_TABLE_SUFFIX",
+    "_FILE_NAME",
+    "_CHANGE_TYPE",
+];
+
+#[derive(Clone)]
+struct Column {
+    name: String,
+    type_: Option<BqType>, // None if unknown
+}
+
+struct Source {
+    name: Option<String>,         // range variable
+    columns: Option<Vec<Column>>, // None if unknown
+    value: Option<Column>,        // for value tables (e.g. UNNEST(arr) AS x)
+}
+
+#[derive(Default)]
+struct Scope {
+    sources: Vec<Source>,
+    using: Vec<String>,   // JOIN ... USING (col)
+    aliases: Vec<String>, // aliases of the select list
+    windows: Vec<String>, // WINDOW w AS (...)
+}
+
+impl Scope {
+    /// The scope of a context which cannot be resolved (e.g. a subquery in UPDATE).
+    fn opaque() -> Scope {
+        Scope {
+            sources: vec![Source {
+                name: None,
+                columns: None,
+                value: None,
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+struct Validator<'a> {
+    catalog: &'a Catalog,
+    ctes: Vec<(String, Option<Vec<Column>>)>,
+    variables: Vec<String>, // DECLARE x; FOR x IN (...)
+    statement: usize,
+    diagnostics: Vec<Diagnostic>,
+    infer: bool,
+    types: Vec<ExprType>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, kind: DiagnosticKind, message: String, node: &Node) {
+        let diagnostic = Diagnostic::new(kind, message, self.statement, node);
+        self.diagnostics.push(diagnostic);
+    }
+    fn collect_variables(&mut self, node: &Node) {
+        match node.node_type {
+            NodeType::DeclareStatement => {
+                for ident in node.get_node_vec("idents").into_iter().flatten() {
+                    self.variables.push(ident.literal().to_string());
+                }
+            }
+            NodeType::ForStatement => {
+                if let Some(ident) = node.get_node("ident") {
+                    self.variables.push(ident.literal().to_string());
+                }
+            }
+            _ => (),
+        }
+        for (_, child) in node.child_nodes() {
+            self.collect_variables(child);
+        }
+    }
+    fn statement(&mut self, node: &Node, opaque: bool) {
+        match node.node_type {
+            NodeType::SelectStatement | NodeType::SetOperator | NodeType::GroupedStatement => {
+                let mut scopes = if opaque {
+                    vec![Scope::opaque()]
+                } else {
+                    Vec::new()
+                };
+                self.query(node, &mut scopes);
+            }
+            _ => {
+                // the queries may refer to the target table or the parameters
+                let opaque = opaque
+                    || matches!(
+                        node.node_type,
+                        NodeType::UpdateStatement
+                            | NodeType::DeleteStatement
+                            | NodeType::MergeStatement
+                            | NodeType::CreateFunctionStatement
+                            | NodeType::CreateProcedureStatement
+                    );
+                for (_, child) in node.child_nodes() {
+                    self.statement(child, opaque);
+                }
+            }
+        }
+    }
+    /// Validates the query and returns its output columns (None if unknown).
+    fn query(&mut self, node: &Node, scopes: &mut Vec<Scope>) -> Option<Vec<Column>> {
+        match node.node_type {
+            NodeType::SelectStatement => self.select(node, scopes),
+            NodeType::SetOperator => {
+                let ctes = self.ctes.len();
+                if let Some(with) = node.get_node("with") {
+                    self.with(with, scopes);
+                }
+                let columns = match node.get_node("left") {
+                    Some(left) => self.query(left, scopes),
+                    None => None,
+                };
+                if let Some(right) = node.get_node("right") {
+                    self.query(right, scopes);
+                }
+                self.ctes.truncate(ctes);
+                columns
+            }
+            NodeType::GroupedStatement => match node.get_node("stmt") {
+                Some(stmt) => self.query(stmt, scopes),
+                None => None,
+            },
+            // pipe syntax, graph query, ...
+            _ => None,
+        }
+    }
+    fn with(&mut self, with: &Node, scopes: &mut Vec<Scope>) {
+        let recursive = with.get_node("recursive").is_some();
+        for query in with.get_node_vec("queries").into_iter().flatten() {
+            let name = unquote(query.literal());
+            if recursive {
+                self.ctes.push((name.clone(), None));
+            }
+            let columns = match query.get_node("stmt") {
+                Some(stmt) => self.query(stmt, scopes),
+                None => None,
+            };
+            if recursive {
+                self.ctes.pop();
+            }
+            self.ctes.push((name, columns));
+        }
+    }
+    fn select(&mut self, node: &Node, scopes: &mut Vec<Scope>) -> Option<Vec<Column>> {
+        let ctes = self.ctes.len();
+        if let Some(with) = node.get_node("with") {
+            self.with(with, scopes);
+        }
+        scopes.push(Scope::default());
+        if let Some(from) = node.get_node("from").and_then(|f| f.get_node("expr")) {
+            self.table_expr(from, scopes);
+        }
+        let exprs: &[Node] = node.get_node_vec("exprs").map_or(&[], |v| v.as_slice());
+        if let Some(scope) = scopes.last_mut() {
+            scope.aliases = exprs
+                .iter()
+                .filter_map(|e| e.get_node("alias"))
+                .map(|a| unquote(a.literal()))
+                .collect();
+            scope.windows = node
+                .get_node("window")
+                .and_then(|w| w.get_node_vec("window_exprs"))
+                .into_iter()
+                .flatten()
+                .map(|w| unquote(w.literal()))
+                .collect();
+        }
+        let mut columns = Some(Vec::new());
+        for expr in exprs {
+            let column = self.expr(expr, scopes, false);
+            let Some(columns) = &mut columns else {
+                continue;
+            };
+            if let Some(alias) = expr.get_node("alias") {
+                columns.push(Column {
+                    name: unquote(alias.literal()),
+                    type_: column,
+                });
+                continue;
+            }
+            match expr.node_type {
+                NodeType::Asterisk => match star_columns(expr, scopes.last(), None) {
+                    Some(mut cs) => columns.append(&mut cs),
+                    None => return self.finish_select(scopes, ctes, None),
+                },
+                NodeType::DotOperator if is_star(expr) => {
+                    let name = expr
+                        .get_node("left")
+                        .and_then(|l| l.identifier_parts())
+                        .unwrap_or_default();
+                    match star_columns(expr, scopes.last(), Some(&name)) {
+                        Some(mut cs) => columns.append(&mut cs),
+                        None => return self.finish_select(scopes, ctes, None),
+                    }
+                }
+                _ => {
+                    // anonymous columns cannot be referred but have a type
+                    let parts = expr.identifier_parts().unwrap_or_default();
+                    columns.push(Column {
+                        name: parts.last().cloned().unwrap_or_default(),
+                        type_: column,
+                    });
+                }
+            }
+        }
+        for key in [
+            "where", "groupby", "having", "qualify", "window", "orderby", "limit",
+        ] {
+            let Some(clause) = node.get_node(key) else {
+                continue;
+            };
+            // the aliases of the select list are not visible in WHERE clause
+            let aliases = key != "where";
+            match (key, clause.get_node("expr")) {
+                ("where" | "having" | "qualify", Some(condition)) => {
+                    let type_ = self.expr(condition, scopes, aliases);
+                    if let Some(type_) = type_.filter(|t| t.scalar() != Some(ScalarType::Bool)) {
+                        self.type_error(
+                            Err(format!(
+                                "{} clause should return type BOOL, but returns {}.",
+                                key.to_uppercase(),
+                                type_
+                            )),
+                            condition,
+                        );
+                    }
+                }
+                ("window", _) => {
+                    for e in clause.get_node_vec("window_exprs").into_iter().flatten() {
+                        if let Some(window) = e.get_node("window") {
+                            self.window(window, scopes, aliases);
+                        }
+                    }
+                }
+                _ => {
+                    self.expr(clause, scopes, aliases);
+                }
+            }
+        }
+        if node.get_node_vec("as_struct_or_value").is_some() {
+            columns = None;
+        }
+        self.finish_select(scopes, ctes, columns)
+    }
+    fn finish_select(
+        &mut self,
+        scopes: &mut Vec<Scope>,
+        ctes: usize,
+        columns: Option<Vec<Column>>,
+    ) -> Option<Vec<Column>> {
+        scopes.pop();
+        self.ctes.truncate(ctes);
+        columns
+    }
+    fn push_source(scopes: &mut [Scope], source: Source) {
+        if let Some(scope) = scopes.last_mut() {
+            scope.sources.push(source);
+        }
+    }
+    fn table_expr(&mut self, node: &Node, scopes: &mut Vec<Scope>) {
+        let sources = scopes.last().map(|s| s.sources.len()).unwrap_or(0);
+        let alias = node.get_node("alias").map(|a| unquote(a.literal()));
+        match node.node_type {
+            NodeType::JoinOperator => {
+                for key in ["left", "right"] {
+                    if let Some(item) = node.get_node(key) {
+                        self.table_expr(item, scopes);
+                    }
+                }
+                if let Some(using) = node.get_node("using") {
+                    for arg in using.get_node_vec("args").into_iter().flatten() {
+                        if let Some(scope) = scopes.last_mut() {
+                            scope.using.push(unquote(arg.literal()));
+                        }
+                    }
+                }
+                if let Some(on) = node.get_node("on") {
+                    self.expr(on, scopes, false);
+                }
+            }
+            NodeType::GroupedExpr => {
+                if let Some(expr) = node.get_node("expr") {
+                    self.table_expr(expr, scopes);
+                }
+            }
+            NodeType::GroupedStatement => {
+                // subqueries in FROM clause cannot refer to the preceding tables
+                let current = scopes.pop();
+                let columns = self.query(node, scopes);
+                scopes.extend(current);
+                Self::push_source(
+                    scopes,
+                    Source {
+                        name: alias,
+                        columns,
+                        value: None,
+                    },
+                );
+            }
+            NodeType::CallingUnnest => {
+                let arg = node.get_node_vec("args").and_then(|args| args.first());
+                let element = match arg {
+                    Some(arg) => self.expr(arg, scopes, false),
+                    None => None,
+                };
+                let name = alias.or_else(|| {
+                    arg.and_then(|a| a.identifier_parts())
+                        .and_then(|parts| parts.last().cloned())
+                });
+                Self::push_source(scopes, element_source(name, element));
+            }
+            NodeType::Identifier | NodeType::DotOperator | NodeType::MultiTokenIdentifier => {
+                let parts = node.identifier_parts().unwrap_or_default();
+                let name = alias.or_else(|| parts.last().cloned());
+                let source = if parts.len() >= 2 && is_range_variable(scopes, &parts[0]) {
+                    // correlated array path, e.g. FROM t, t.arr AS x
+                    let element = match reference_parts(node) {
+                        Some(refs) => self.reference(&refs, scopes, false),
+                        None => None,
+                    };
+                    element_source(name, element)
+                } else {
+                    let columns = self.table_columns(node, &parts);
+                    Source {
+                        name,
+                        columns,
+                        value: None,
+                    }
+                };
+                Self::push_source(scopes, source);
+            }
+            _ => {
+                // table functions, ...
+                for arg in node.get_node_vec("args").into_iter().flatten() {
+                    self.expr(arg, scopes, false);
+                }
+                Self::push_source(
+                    scopes,
+                    Source {
+                        name: alias,
+                        columns: None,
+                        value: None,
+                    },
+                );
+            }
+        }
+        if let Some(offset) = node.get_node("with_offset") {
+            let name = offset
+                .get_node("alias")
+                .map(|a| unquote(a.literal()))
+                .unwrap_or("offset".to_string());
+            let array = BqType::Array(Box::new(ScalarType::Int64.into()));
+            Self::push_source(scopes, element_source(Some(name), Some(array)));
+        }
+        for key in ["pivot", "unpivot"] {
+            if let Some(operator) = node.get_node(key) {
+                // the output columns of PIVOT / UNPIVOT are not inferred
+                if let Some(scope) = scopes.last_mut() {
+                    scope.sources.truncate(sources);
+                    scope.sources.push(Source {
+                        name: operator.get_node("alias").map(|a| unquote(a.literal())),
+                        columns: None,
+                        value: None,
+                    });
+                }
+            }
+        }
+    }
+    fn table_columns(&mut self, node: &Node, parts: &[String]) -> Option<Vec<Column>> {
+        if parts.len() == 1 {
+            if let Some((_, columns)) = self
+                .ctes
+                .iter()
+                .rev()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&parts[0]))
+            {
+                return columns.clone();
+            }
+        }
+        let name = parts.join(".");
+        if name.ends_with('*')
+            || parts
+                .iter()
+                .any(|p| p.to_uppercase().starts_with("INFORMATION_SCHEMA"))
+        {
+            return None;
+        }
+        let mut table = self.catalog.find_table(&name);
+        if table.is_none() && parts.len() == 3 {
+            // `project.dataset.table` may be registered as `dataset.table`
+            table = self.catalog.find_table(&parts[1..].join("."));
+        }
+        match table {
+            Some(table) if table.schema.is_empty() => None,
+            Some(table) => Some(
+                table
+                    .schema
+                    .iter()
+                    .map(|f| Column {
+                        name: f.name.clone(),
+                        type_: BqType::from_field(f),
+                    })
+                    .collect(),
+            ),
+            None => {
+                self.report(
+                    DiagnosticKind::UnknownTable,
+                    format!("Table {} is not found.", name),
+                    node,
+                );
+                None
+            }
+        }
+    }
+    /// Validates the identifiers in the expression and returns its type
+    /// (None if unknown).
+    fn expr(&mut self, node: &Node, scopes: &mut Vec<Scope>, aliases: bool) -> Option<BqType> {
+        // the parameters (e.g. STRING(10)) only apply to the stored values
+        let type_ = self
+            .expr_type(node, scopes, aliases)
+            .map(|t| t.without_parameters());
+        if self.infer {
+            if let (Some(type_), Some(token)) = (&type_, &node.token) {
+                self.types.push(ExprType {
+                    type_: type_.to_string(),
+                    literal: token.literal.clone(),
+                    line: token.line,
+                    column: token.column,
+                });
+            }
+        }
+        type_
+    }
+    fn expr_type(&mut self, node: &Node, scopes: &mut Vec<Scope>, aliases: bool) -> Option<BqType> {
+        match node.node_type {
+            NodeType::Identifier | NodeType::MultiTokenIdentifier => {
+                let refs = reference_parts(node)?;
+                self.reference(&refs, scopes, aliases)
+            }
+            NodeType::DotOperator => {
+                if is_star(node) {
+                    return None;
+                }
+                if let Some(refs) = reference_parts(node) {
+                    return self.reference(&refs, scopes, aliases);
+                }
+                // e.g. arr[OFFSET(0)].x, (SELECT AS STRUCT ...).x
+                let left = self.expr(node.get_node("left")?, scopes, aliases);
+                let right = reference_parts(node.get_node("right")?)?;
+                self.access("", left, &right)
+            }
+            NodeType::SelectStatement | NodeType::SetOperator | NodeType::GroupedStatement => {
+                // scalar subquery
+                match self.query(node, scopes)?.as_slice() {
+                    [column] => column.type_.clone(),
+                    _ => None,
+                }
+            }
+            NodeType::Type => None,
+            NodeType::NumericLiteral
+            | NodeType::StringLiteral
+            | NodeType::BooleanLiteral
+            | NodeType::NullLiteral => typecheck::literal_type(node),
+            NodeType::IntervalLiteral | NodeType::RangeLiteral => {
+                self.walk_children(node, scopes, aliases);
+                typecheck::literal_type(node)
+            }
+            // TABLE ds.t, MODEL ds.m (arguments of table functions)
+            NodeType::UnaryOperator if node.token.as_ref().is_some_and(|t| t.is("TABLE")) => {
+                let right = node.get_node("right")?;
+                let parts = right.identifier_parts()?;
+                self.table_columns(right, &parts);
+                None
+            }
+            NodeType::UnaryOperator if node.token.as_ref().is_some_and(|t| t.is("MODEL")) => None,
+            NodeType::UnaryOperator => {
+                let right = match node.get_node("right") {
+                    Some(right) => self.expr(right, scopes, aliases),
+                    None => None,
+                };
+                let res = typecheck::unary_type(node.literal(), right.as_ref());
+                self.type_error(res, node)
+            }
+            // named argument, e.g. top_k => 1
+            NodeType::BinaryOperator if node.literal() == "=>" => {
+                self.expr(node.get_node("right")?, scopes, aliases)
+            }
+            // x LIKE ANY ('a%', 'b%'), x LIKE ALL UNNEST(arr)
+            NodeType::BinaryOperator if node.get_node("quantifier").is_some() => {
+                let left = self.operand(node.get_node("left"), scopes, aliases);
+                let right = node.get_node("right")?;
+                let values = match right.node_type {
+                    NodeType::StructLiteral => right
+                        .get_node_vec("exprs")
+                        .into_iter()
+                        .flatten()
+                        .map(|e| self.operand(Some(e), scopes, aliases))
+                        .collect(),
+                    NodeType::GroupedExpr => {
+                        vec![self.operand(right.get_node("expr"), scopes, aliases)]
+                    }
+                    _ => {
+                        let array = match right.get_node_vec("args").and_then(|a| a.first()) {
+                            Some(arg) => self.expr(arg, scopes, aliases),
+                            None => None,
+                        };
+                        match array {
+                            Some(BqType::Array(element)) => vec![Operand {
+                                type_: Some(*element),
+                                literal: false,
+                            }],
+                            _ => Vec::new(),
+                        }
+                    }
+                };
+                for value in values {
+                    let res = typecheck::binary_type(node.literal(), &left, &value);
+                    self.type_error(res, node);
+                }
+                Some(ScalarType::Bool.into())
+            }
+            NodeType::BinaryOperator => {
+                let left = self.operand(node.get_node("left"), scopes, aliases);
+                let right = self.operand(node.get_node("right"), scopes, aliases);
+                let res = typecheck::binary_type(node.literal(), &left, &right);
+                self.type_error(res, node)
+            }
+            NodeType::BetweenOperator => {
+                let left = self.operand(node.get_node("left"), scopes, aliases);
+                for key in ["right_min", "right_max"] {
+                    let right = self.operand(node.get_node(key), scopes, aliases);
+                    self.compare("BETWEEN", node, &left, &right);
+                }
+                Some(ScalarType::Bool.into())
+            }
+            NodeType::IsDistinctFromOperator => {
+                let left = self.operand(node.get_node("left"), scopes, aliases);
+                let right = self.operand(node.get_node("right"), scopes, aliases);
+                self.compare("IS DISTINCT FROM", node, &left, &right);
+                Some(ScalarType::Bool.into())
+            }
+            NodeType::InOperator => {
+                let left = self.operand(node.get_node("left"), scopes, aliases);
+                let right = node.get_node("right")?;
+                match right.node_type {
+                    NodeType::GroupedExprs => {
+                        for e in right.get_node_vec("exprs").into_iter().flatten() {
+                            let value = self.operand(Some(e), scopes, aliases);
+                            self.compare("IN", e, &left, &value);
+                        }
+                    }
+                    NodeType::CallingUnnest => {
+                        let array = match right.get_node_vec("args").and_then(|a| a.first()) {
+                            Some(arg) => self.expr(arg, scopes, aliases),
+                            None => None,
+                        };
+                        if let Some(BqType::Array(element)) = array {
+                            let value = Operand {
+                                type_: Some(*element),
+                                literal: false,
+                            };
+                            self.compare("IN UNNEST", right, &left, &value);
+                        }
+                    }
+                    _ => {
+                        let value = self.operand(Some(right), scopes, aliases);
+                        self.compare("IN", right, &left, &value);
+                    }
+                }
+                Some(ScalarType::Bool.into())
+            }
+            NodeType::CaseExpr => {
+                let subject = node
+                    .get_node("expr")
+                    .map(|e| self.operand(Some(e), scopes, aliases));
+                let mut results = Vec::new();
+                for arm in node.get_node_vec("arms").into_iter().flatten() {
+                    if let Some(condition) = arm.get_node("expr") {
+                        let value = self.operand(Some(condition), scopes, aliases);
+                        match &subject {
+                            Some(subject) => self.compare("CASE", condition, subject, &value),
+                            None => {
+                                if let Some(type_) =
+                                    value.type_.filter(|t| t.scalar() != Some(ScalarType::Bool))
+                                {
+                                    self.type_error(
+                                        Err(format!(
+                                            "WHEN clause should return type BOOL, but returns {}.",
+                                            type_
+                                        )),
+                                        condition,
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    results.push(match arm.get_node("result") {
+                        Some(result) => self.expr(result, scopes, aliases),
+                        None => None,
+                    });
+                }
+                let res = typecheck::common_type(&results);
+                self.type_error(res, node)
+            }
+            NodeType::CastArgument => {
+                if let Some(from) = node.get_node("cast_from") {
+                    self.expr(from, scopes, aliases);
+                }
+                BqType::from_type(node.get_node("cast_to")?)
+            }
+            NodeType::ExtractArgument => {
+                if let Some(from) = node.get_node("extract_from") {
+                    self.expr(from, scopes, aliases);
+                }
+                let part = node.get_node("extract_datepart")?.literal().to_uppercase();
+                match part.as_str() {
+                    "DATE" => Some(ScalarType::Date.into()),
+                    "TIME" => Some(ScalarType::Time.into()),
+                    "DATETIME" => Some(ScalarType::Datetime.into()),
+                    _ => Some(ScalarType::Int64.into()),
+                }
+            }
+            NodeType::ArrayLiteral => {
+                let mut elements = Vec::new();
+                for e in node.get_node_vec("exprs").into_iter().flatten() {
+                    elements.push(self.expr(e, scopes, aliases));
+                }
+                if let Some(type_) = node.get_node("type") {
+                    let declared = BqType::from_type(type_);
+                    if let Some(BqType::Array(target)) = &declared {
+                        let exprs = node.get_node_vec("exprs").into_iter().flatten();
+                        for (e, element) in exprs.zip(&elements) {
+                            // literals are coerced to the element type
+                            let literal = typecheck::literal_type(e).is_some()
+                                || e.node_type == NodeType::UnaryOperator;
+                            match element {
+                                Some(t) if !literal && !t.coercible_to(target) => {
+                                    let message = format!(
+                                        "Array element type {} does not coerce to {}.",
+                                        t, target
+                                    );
+                                    self.type_error(Err(message), e);
+                                }
+                                _ => (),
+                            }
+                        }
+                    }
+                    return declared;
+                }
+                let res = typecheck::common_type(&elements);
+                self.type_error(res, node)
+                    .map(|element| BqType::Array(Box::new(element)))
+            }
+            NodeType::StructLiteral => {
+                let mut fields = Vec::new();
+                for e in node.get_node_vec("exprs").into_iter().flatten() {
+                    let type_ = self.expr(e, scopes, aliases);
+                    let name = match (e.get_node("alias"), e.identifier_parts()) {
+                        (Some(alias), _) => Some(unquote(alias.literal())),
+                        (None, Some(parts)) => parts.last().cloned(),
+                        _ => None,
+                    };
+                    fields.push((name, type_));
+                }
+                match node.get_node("type") {
+                    Some(type_) if type_.get_node("type_declaration").is_some() => {
+                        BqType::from_type(type_)
+                    }
+                    _ => {
+                        let mut res = Vec::new();
+                        for (name, type_) in fields {
+                            res.push((name, type_?));
+                        }
+                        Some(BqType::Struct(res))
+                    }
+                }
+            }
+            NodeType::GroupedExpr => self.expr(node.get_node("expr")?, scopes, aliases),
+            NodeType::AccessOperator => {
+                let left = match node.get_node("left") {
+                    Some(left) => self.expr(left, scopes, aliases),
+                    None => None,
+                };
+                if let Some(right) = node.get_node("right") {
+                    self.expr(right, scopes, aliases);
+                }
+                match left? {
+                    BqType::Array(element) => Some(*element),
+                    json @ BqType::Scalar(ScalarType::Json, _) => Some(json),
+                    _ => None,
+                }
+            }
+            NodeType::CallingFunction => {
+                let mut args = Vec::new();
+                for arg in node.get_node_vec("args").into_iter().flatten() {
+                    let date_part = arg.node_type == NodeType::Identifier
+                        && DATE_PARTS.contains(&arg.literal().to_uppercase().as_str());
+                    args.push(match date_part {
+                        true => None,
+                        false => self.expr(arg, scopes, aliases),
+                    });
+                }
+                for (key, child) in node.child_nodes() {
+                    match key {
+                        "func" | "args" | "alias" => (),
+                        "over" => {
+                            if let Some(window) = child.get_node("window") {
+                                self.window(window, scopes, aliases);
+                            }
+                        }
+                        _ => {
+                            self.expr(child, scopes, aliases);
+                        }
+                    }
+                }
+                let func = node.get_node("func")?;
+                let name = func.identifier_parts()?.join(".");
+                let res = typecheck::function_type(&name, &args);
+                self.type_error(res, func)
+            }
+            _ => {
+                self.walk_children(node, scopes, aliases);
+                None
+            }
+        }
+    }
+    fn walk_children(&mut self, node: &Node, scopes: &mut Vec<Scope>, aliases: bool) {
+        for (key, child) in node.child_nodes() {
+            let skip = match node.node_type {
+                NodeType::WindowSpecification => key == "name",
+                _ => key == "alias" || key == "func",
+            };
+            if !skip {
+                self.expr(child, scopes, aliases);
+            }
+        }
+    }
+    /// Validates `OVER w`, `OVER (w ORDER BY ...)` or `OVER (PARTITION BY ...)`.
+    /// The named windows are resolved against the WINDOW clause.
+    fn window(&mut self, node: &Node, scopes: &mut Vec<Scope>, aliases: bool) {
+        let name = match node.node_type {
+            NodeType::Identifier => Some(node),
+            NodeType::WindowSpecification => {
+                self.walk_children(node, scopes, aliases);
+                node.get_node("name")
+            }
+            _ => {
+                self.expr(node, scopes, aliases);
+                None
+            }
+        };
+        if let Some(name) = name {
+            let literal = unquote(name.literal());
+            let found = scopes
+                .last()
+                .is_some_and(|s| s.windows.iter().any(|w| w.eq_ignore_ascii_case(&literal)));
+            if !found {
+                self.report(
+                    DiagnosticKind::UnknownColumn,
+                    format!("Unrecognized window alias: {}.", literal),
+                    name,
+                );
+            }
+        }
+    }
+    fn operand(&mut self, node: Option<&Node>, scopes: &mut Vec<Scope>, aliases: bool) -> Operand {
+        match node {
+            Some(node) => Operand {
+                type_: self.expr(node, scopes, aliases),
+                literal: node.node_type == NodeType::StringLiteral,
+            },
+            None => Operand {
+                type_: None,
+                literal: false,
+            },
+        }
+    }
+    /// Reports if the values cannot be compared (e.g. `x IN (...)`).
+    fn compare(&mut self, op: &str, node: &Node, left: &Operand, right: &Operand) {
+        if typecheck::binary_type("=", left, right).is_ok() {
+            return;
+        }
+        if let (Some(l), Some(r)) = (&left.type_, &right.type_) {
+            let message = format!(
+                "No matching signature for operator {} for argument types: {}, {}.",
+                op, l, r
+            );
+            self.type_error(Err(message), node);
+        }
+    }
+    /// Reports the type error (only if types are inferred).
+    fn type_error(&mut self, res: Result<Option<BqType>, String>, node: &Node) -> Option<BqType> {
+        match res {
+            Ok(type_) => type_,
+            Err(message) => {
+                if self.infer {
+                    // the position of the operator (or the first token)
+                    let (line, column) = match &node.token {
+                        Some(token) => (token.line, token.column),
+                        None => node.position(),
+                    };
+                    self.diagnostics.push(Diagnostic {
+                        line,
+                        column,
+                        ..Diagnostic::new(
+                            DiagnosticKind::TypeMismatch,
+                            message,
+                            self.statement,
+                            node,
+                        )
+                    });
+                }
+                None
+            }
+        }
+    }
+    fn reference(
+        &mut self,
+        refs: &[(String, &Node)],
+        scopes: &[Scope],
+        aliases: bool,
+    ) -> Option<BqType> {
+        let (first, node) = refs.first()?;
+        if refs.len() == 1 && BUILTIN_NAMES.contains(&first.to_uppercase().as_str()) {
+            return None;
+        }
+        for (depth, scope) in scopes.iter().enumerate().rev() {
+            if aliases
+                && depth == scopes.len() - 1
+                && scope.aliases.iter().any(|a| a.eq_ignore_ascii_case(first))
+            {
+                return None;
+            }
+            let source = scope.sources.iter().find(|s| {
+                s.name
+                    .as_ref()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(first))
+            });
+            if let Some(source) = source {
+                if let Some(value) = &source.value {
+                    return self.access(&value.name, value.type_.clone(), &refs[1..]);
+                }
+                // t refers to the whole row
+                let (name, node) = refs.get(1)?;
+                let columns = source.columns.as_ref()?;
+                match find_column(columns, name) {
+                    Some(column) => {
+                        return self.access(&column.name, column.type_.clone(), &refs[2..])
+                    }
+                    None => {
+                        self.report(
+                            DiagnosticKind::UnknownColumn,
+                            format!("Name {} is not found inside {}.", name, first),
+                            node,
+                        );
+                        return None;
+                    }
+                }
+            }
+            let mut matches = Vec::new();
+            let mut unknown = false;
+            for source in &scope.sources {
+                match &source.columns {
+                    Some(columns) => matches.extend(find_column(columns, first).cloned()),
+                    None => unknown = true,
+                }
+            }
+            if matches.len() > 1 && !scope.using.iter().any(|u| u.eq_ignore_ascii_case(first)) {
+                self.report(
+                    DiagnosticKind::AmbiguousColumn,
+                    format!("Column name {} is ambiguous.", first),
+                    node,
+                );
+                return None;
+            }
+            if let Some(column) = matches.into_iter().next() {
+                return self.access(&column.name, column.type_, &refs[1..]);
+            }
+            if unknown {
+                return None;
+            }
+        }
+        if self.variables.iter().any(|v| v.eq_ignore_ascii_case(first)) {
+            return None;
+        }
+        self.report(
+            DiagnosticKind::UnknownColumn,
+            format!("Unrecognized name: {}.", first),
+            node,
+        );
+        None
+    }
+    /// Follows the field accesses (e.g. `.x.y`) of the value named `name`.
+    fn access(
+        &mut self,
+        name: &str,
+        type_: Option<BqType>,
+        refs: &[(String, &Node)],
+    ) -> Option<BqType> {
+        let mut name = name.to_string();
+        let mut current = type_;
+        for (field, node) in refs {
+            let message = match current? {
+                BqType::Struct(fields) => {
+                    let found = fields
+                        .into_iter()
+                        .find(|(n, _)| n.as_ref().is_some_and(|n| n.eq_ignore_ascii_case(field)));
+                    match found {
+                        Some((_, type_)) => {
+                            current = Some(type_);
+                            name = field.clone();
+                            continue;
+                        }
+                        None => format!("Field name {} does not exist in STRUCT {}.", field, name),
+                    }
+                }
+                BqType::Scalar(ScalarType::Json, _) => {
+                    current = Some(ScalarType::Json.into());
+                    continue;
+                }
+                other => format!(
+                    "Cannot access field {} on a value with type {}.",
+                    field, other
+                ),
+            };
+            self.report(DiagnosticKind::InvalidFieldAccess, message, node);
+            return None;
+        }
+        current
+    }
+}
+
+fn unquote(s: &str) -> String {
+    s.trim_matches('`').to_string()
+}
+
+/// Returns the parts of the identifier with the nodes they come from.
+fn reference_parts(node: &Node) -> Option<Vec<(String, &Node)>> {
+    match node.node_type {
+        NodeType::DotOperator => {
+            let mut left = reference_parts(node.get_node("left")?)?;
+            left.append(&mut reference_parts(node.get_node("right")?)?);
+            Some(left)
+        }
+        NodeType::Identifier | NodeType::MultiTokenIdentifier => Some(
+            node.identifier_parts()?
+                .into_iter()
+                .map(|p| (p, node))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn is_star(node: &Node) -> bool {
+    node.get_node("right")
+        .is_some_and(|r| r.node_type == NodeType::Asterisk)
+}
+
+fn is_range_variable(scopes: &[Scope], name: &str) -> bool {
+    scopes.iter().any(|scope| {
+        scope.sources.iter().any(|s| {
+            s.name
+                .as_ref()
+                .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        })
+    })
+}
+
+fn find_column<'a>(columns: &'a [Column], name: &str) -> Option<&'a Column> {
+    columns.iter().find(|c| c.name.eq_ignore_ascii_case(name))
+}
+
+/// The source of UNNEST(arr), whose element type is known if the type of arr is known.
+/// The fields of a STRUCT element can be referred to without the name.
+fn element_source(name: Option<String>, array: Option<BqType>) -> Source {
+    let type_ = match array {
+        Some(BqType::Array(element)) => Some(*element),
+        _ => None,
+    };
+    let columns = match &type_ {
+        Some(BqType::Struct(fields)) => Some(
+            fields
+                .iter()
+                .filter_map(|(name, type_)| {
+                    Some(Column {
+                        name: name.clone()?,
+                        type_: Some(type_.clone()),
+                    })
+                })
+                .collect(),
+        ),
+        Some(_) => Some(Vec::new()),
+        None => None,
+    };
+    Source {
+        value: Some(Column {
+            name: name.clone().unwrap_or_default(),
+            type_,
+        }),
+        name,
+        columns,
+    }
+}
+
+/// Expands `*` or `t.*` (the latter if `name` is given).
+fn star_columns(
+    asterisk: &Node,
+    scope: Option<&Scope>,
+    name: Option<&[String]>,
+) -> Option<Vec<Column>> {
+    let scope = scope?;
+    let mut columns = Vec::new();
+    for source in &scope.sources {
+        if let Some(name) = name {
+            let is_target = name.len() == 1
+                && source
+                    .name
+                    .as_ref()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(&name[0]));
+            if !is_target {
+                continue;
+            }
+        }
+        match (&source.value, &source.columns) {
+            (Some(value), _) => match &value.type_ {
+                Some(BqType::Struct(fields)) => {
+                    columns.extend(fields.iter().map(|(name, type_)| Column {
+                        name: name.clone().unwrap_or_default(),
+                        type_: Some(type_.clone()),
+                    }))
+                }
+                _ => return None,
+            },
+            (None, Some(cs)) => columns.extend(cs.iter().cloned()),
+            (None, None) => return None,
+        }
+    }
+    let asterisk = match name {
+        Some(_) => asterisk.get_node("right")?,
+        None => asterisk,
+    };
+    let except = asterisk
+        .get_node("except")
+        .and_then(|e| e.get_node("group"))
+        .and_then(|g| g.get_node_vec("exprs"));
+    for e in except.into_iter().flatten() {
+        let name = unquote(e.literal());
+        columns.retain(|c| !c.name.eq_ignore_ascii_case(&name));
+    }
+    let replace = asterisk
+        .get_node("replace")
+        .and_then(|r| r.get_node("group"))
+        .and_then(|g| g.get_node_vec("exprs"));
+    for r in replace.into_iter().flatten() {
+        if let Some(alias) = r.get_node("alias") {
+            let name = unquote(alias.literal());
+            for c in columns
+                .iter_mut()
+                .filter(|c| c.name.eq_ignore_ascii_case(&name))
+            {
+                c.type_ = None;
+            }
+        }
+    }
+    Some(columns)
+}
@@ -0,0 +1,195 @@
+use super::*;
+use crate::catalog::{build_catalog, TableFieldSchema};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+const DDL: &str = "\
+CREATE TABLE ds.users (id INT64, name STRING, address STRUCT<city STRING, zip STRING>, tags ARRAY<STRING>);
+CREATE TABLE ds.orders (id INT64, user_id INT64, items ARRAY<STRUCT<sku STRING, qty INT64>>);
+CREATE TABLE ds.logs AS SELECT 1 AS x;
+";
+
+pub(crate) fn parse(code: &str) -> Vec<Node> {
+    let l = Lexer::new(code.to_string());
+    let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."));
+    p.parse_code().expect("Failed to parse code.")
+}
+
+// returns (kind, message, line, column) of the diagnostics
+pub(crate) fn diagnostic_tuples(
+    diagnostics: Vec<Diagnostic>,
+) -> Vec<(DiagnosticKind, String, usize, usize)> {
+    diagnostics
+        .into_iter()
+        .map(|d| (d.kind, d.message, d.line, d.column))
+        .collect()
+}
+
+fn diagnostics(code: &str) -> Vec<(DiagnosticKind, String, usize, usize)> {
+    let catalog = build_catalog(&parse(DDL));
+    diagnostic_tuples(validate_columns(&parse(code), &catalog))
+}
+
+#[test]
+fn test_validate_columns_valid() {
+    let res = diagnostics(
+        "\
+DECLARE v INT64;
+SELECT u.id, name, address.city, u.address.zip, o.user_id, item.sku, v, CURRENT_DATE
+FROM ds.users AS u JOIN ds.orders o ON u.id = o.user_id, o.items AS item
+WHERE EXISTS (SELECT 1 FROM UNNEST(u.tags) AS tag WHERE tag = name)
+ORDER BY name;
+WITH c AS (SELECT id AS uid, * EXCEPT (id) FROM ds.users)
+SELECT uid, c.address.city, DATE_TRUNC(CURRENT_DATE, MONTH) FROM c;
+SELECT id FROM ds.users JOIN ds.orders USING (id);
+SELECT anything FROM ds.logs, `project.ds.users`;
+SELECT x.y.z FROM (SELECT * FROM ds.logs);
+SELECT name AS n, COUNT(*) OVER (PARTITION BY id ORDER BY name) FROM ds.users GROUP BY n HAVING n > '' QUALIFY TRUE;
+SELECT id, EXTRACT(DAY FROM CURRENT_DATE), STRUCT(id AS a), ARRAY(SELECT t FROM UNNEST(tags) t) FROM ds.users WHERE 'a' IN UNNEST(tags)
+UNION ALL SELECT user_id, 1, NULL, [] FROM ds.orders;
+WITH RECURSIVE r AS (SELECT 1 AS n UNION ALL SELECT n + 1 FROM r WHERE n < 3) SELECT n FROM r;
+SELECT * FROM ds.users PIVOT (COUNT(*) FOR name IN ('a')) AS p WHERE p.a > 0;
+UPDATE ds.users SET name = 'x' WHERE id IN (SELECT user_id FROM ds.orders WHERE name = 'x');
+SELECT * FROM ML.PREDICT(MODEL ds.m, TABLE ds.users), GAP_FILL(TABLE ds.logs, ts_column => 'x', bucket_width => INTERVAL 1 DAY);
+SELECT * FROM VECTOR_SEARCH(TABLE ds.users, 'id', TABLE ds.orders, 'id', top_k => 1);
+SELECT ST_GEOGFROMTEXT('POINT(1 1)', make_valid => TRUE);
+SELECT COUNT(*) c FROM ds.users;
+SELECT DATE_TRUNC(CURRENT_DATE, MONTH) AS m FROM ds.users GROUP BY m;
+SELECT ROW_NUMBER() OVER w AS rn, SUM(id) OVER (w2 ROWS 1 PRECEDING) FROM ds.users
+WINDOW w AS (PARTITION BY name ORDER BY id), w2 AS w;
+SELECT ARRAY(SELECT AS STRUCT sku, qty FROM UNNEST(items)) FROM ds.orders;
+SELECT sku, item.qty, i FROM ds.orders, UNNEST(items) AS item WITH OFFSET AS i;
+",
+    );
+    assert_eq!(res, vec![]);
+}
+
+#[test]
+fn test_validate_columns_invalid() {
+    let res = diagnostics(
+        "\
+SELECT id FROM ds.users JOIN ds.orders ON users.id = orders.user_id;
+SELECT nam, u.nam FROM ds.users AS u;
+SELECT address.country, id.x, tags.x FROM ds.users;
+SELECT 1 FROM ds.not_exist;
+WITH c AS (SELECT id AS uid FROM ds.users)
+SELECT id FROM c;
+SELECT item.price FROM ds.orders, UNNEST(items) AS item;
+SELECT * FROM GAP_FILL(TABLE ds.not_exist, ts_column => 'x');
+SELECT ROW_NUMBER() OVER w FROM ds.users WINDOW v AS (ORDER BY id);
+SELECT price FROM ds.orders, UNNEST(items);
+",
+    );
+    assert_eq!(
+        res,
+        vec![
+            (
+                DiagnosticKind::AmbiguousColumn,
+                "Column name id is ambiguous.".to_string(),
+                1,
+                8
+            ),
+            (
+                DiagnosticKind::UnknownColumn,
+                "Unrecognized name: nam.".to_string(),
+                2,
+                8
+            ),
+            (
+                DiagnosticKind::UnknownColumn,
+                "Name nam is not found inside u.".to_string(),
+                2,
+                15
+            ),
+            (
+                DiagnosticKind::InvalidFieldAccess,
+                "Field name country does not exist in STRUCT address.".to_string(),
+                3,
+                16
+            ),
+            (
+                DiagnosticKind::InvalidFieldAccess,
+                "Cannot access field x on a value with type INT64.".to_string(),
+                3,
+                28
+            ),
+            (
+                DiagnosticKind::InvalidFieldAccess,
+                "Cannot access field x on a value with type ARRAY<STRING>.".to_string(),
+                3,
+                36
+            ),
+            (
+                DiagnosticKind::UnknownTable,
+                "Table ds.not_exist is not found.".to_string(),
+                4,
+                15
+            ),
+            (
+                DiagnosticKind::UnknownColumn,
+                "Unrecognized name: id.".to_string(),
+                6,
+                8
+            ),
+            (
+                DiagnosticKind::InvalidFieldAccess,
+                "Field name price does not exist in STRUCT item.".to_string(),
+                7,
+                13
+            ),
+            (
+                DiagnosticKind::UnknownTable,
+                "Table ds.not_exist is not found.".to_string(),
+                8,
+                30
+            ),
+            (
+                DiagnosticKind::UnknownColumn,
+                "Unrecognized window alias: w.".to_string(),
+                9,
+                26
+            ),
+            (
+                DiagnosticKind::UnknownColumn,
+                "Unrecognized name: price.".to_string(),
+                10,
+                8
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_columns_added_table() {
+    let stmts = parse("SELECT id, payload.a, id.b FROM `project.ds.events`");
+    let mut catalog = build_catalog(&[]);
+    let field = |name: &str, type_: &str| TableFieldSchema {
+        name: name.to_string(),
+        type_: type_.to_string(),
+        mode: "NULLABLE".to_string(),
+        fields: Vec::new(),
+        description: None,
+        max_length: None,
+        precision: None,
+        scale: None,
+        collation: None,
+        default_value_expression: None,
+        range_element_type: None,
+    };
+    catalog.add_table(
+        "ds.events",
+        vec![field("id", "INT64"), field("payload", "JSON")],
+    );
+    let res: Vec<(String, usize, usize)> = validate_columns(&stmts, &catalog)
+        .into_iter()
+        .map(|d| (d.message, d.line, d.column))
+        .collect();
+    assert_eq!(
+        res,
+        vec![(
+            "Cannot access field b on a value with type INT64.".to_string(),
+            1,
+            26
+        )]
+    );
+}
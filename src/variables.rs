@@ -0,0 +1,361 @@
+#[cfg(test)]
+mod tests;
+
+use crate::bqtype::BqType;
+use crate::cst::{Node, NodeType};
+use crate::validate::{self, Diagnostic, DiagnosticKind};
+
+/// Checks the scripting variables: placement of DECLARE statements, references
+/// to variables before their declaration, duplicate declarations, the number
+/// of values of `SET (a, b) = ...` and the type of DEFAULT values.
+pub fn check_variables(stmts: &[Node]) -> Vec<Diagnostic> {
+    let mut checker = Checker {
+        declared: Vec::new(),
+        scopes: vec![Vec::new()],
+        statement: 0,
+        diagnostics: Vec::new(),
+    };
+    for stmt in stmts {
+        checker.collect_declarations(stmt);
+    }
+    // DECLARE is allowed at the beginning of the script
+    let mut leading = true;
+    for (idx, stmt) in stmts.iter().enumerate() {
+        checker.statement = idx;
+        checker.stmt(stmt, &mut leading);
+    }
+    checker.diagnostics
+}
+
+struct Checker {
+    declared: Vec<String>, // all variables declared in the script
+    scopes: Vec<Vec<(String, Option<BqType>)>>, // variables of the enclosing blocks
+    statement: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker {
+    fn report(&mut self, kind: DiagnosticKind, message: String, node: &Node) {
+        let diagnostic = Diagnostic::new(kind, message, self.statement, node);
+        self.diagnostics.push(diagnostic);
+    }
+    fn collect_declarations(&mut self, node: &Node) {
+        if node.node_type == NodeType::DeclareStatement {
+            for ident in node.get_node_vec("idents").into_iter().flatten() {
+                self.declared.push(ident.literal().to_string());
+            }
+        }
+        for (_, child) in node.child_nodes() {
+            self.collect_declarations(child);
+        }
+    }
+    fn visible(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .flatten()
+            .any(|(v, _)| v.eq_ignore_ascii_case(name))
+    }
+    /// `leading` is true while only DECLARE statements appear in the block.
+    fn stmt(&mut self, node: &Node, leading: &mut bool) {
+        if node.node_type == NodeType::DeclareStatement {
+            if !*leading {
+                self.report(
+                    DiagnosticKind::MisplacedDeclaration,
+                    "DECLARE must be at the beginning of the script or a BEGIN block.".to_string(),
+                    node,
+                );
+            }
+            self.declare(node);
+            return;
+        }
+        if node.node_type != NodeType::EOF {
+            *leading = false;
+        }
+        match node.node_type {
+            NodeType::SelectStatement | NodeType::SetOperator | NodeType::GroupedStatement => {
+                self.refs(Some(node))
+            }
+            NodeType::SetStatement => self.set(node),
+            NodeType::BeginStatement => {
+                self.scopes.push(Vec::new());
+                self.block(Some(node), true);
+                self.scopes.pop();
+                self.block(node.get_node("then"), false);
+            }
+            NodeType::IfStatement => {
+                self.refs(node.get_node("condition"));
+                self.block(node.get_node("then"), false);
+                for elseif in node.get_node_vec("elseifs").into_iter().flatten() {
+                    self.refs(elseif.get_node("condition"));
+                    self.block(elseif.get_node("then"), false);
+                }
+                self.block(node.get_node("else"), false);
+            }
+            NodeType::CaseStatement => {
+                self.refs(node.get_node("expr"));
+                for arm in node.get_node_vec("arms").into_iter().flatten() {
+                    self.refs(arm.get_node("expr"));
+                    self.block(Some(arm), false);
+                }
+            }
+            NodeType::LoopStatement => self.block(Some(node), false),
+            NodeType::RepeatStatement => {
+                self.block(Some(node), false);
+                self.refs(node.get_node("until").and_then(|u| u.get_node("expr")));
+            }
+            NodeType::WhileStatement => {
+                self.refs(node.get_node("condition"));
+                self.block(node.get_node("do"), false);
+            }
+            NodeType::ForStatement => {
+                self.refs(node.get_node("in").and_then(|i| i.get_node("group")));
+                let ident = node.get_node("ident").map(|i| i.literal().to_string());
+                self.scopes
+                    .push(ident.into_iter().map(|i| (i, None)).collect());
+                self.block(node.get_node("do"), false);
+                self.scopes.pop();
+            }
+            // RAISE USING MESSAGE = expr
+            NodeType::RaiseStatement => self.refs(
+                node.get_node("using")
+                    .and_then(|u| u.get_node("expr"))
+                    .and_then(|e| e.get_node("right")),
+            ),
+            NodeType::ExecuteStatement => {
+                self.refs(node.get_node("sql_expr"));
+                let using = node.get_node("using").and_then(|u| u.get_node_vec("exprs"));
+                for expr in using.into_iter().flatten() {
+                    self.refs(Some(expr));
+                }
+                let into = node.get_node("into").and_then(|i| i.get_node_vec("exprs"));
+                for ident in into.into_iter().flatten() {
+                    self.target(ident);
+                }
+            }
+            NodeType::CallStatement => {
+                let args = node
+                    .get_node("procedure")
+                    .and_then(|p| p.get_node_vec("args"));
+                for arg in args.into_iter().flatten() {
+                    self.refs(Some(arg));
+                }
+            }
+            NodeType::CreateProcedureStatement => {
+                // the body cannot refer to the variables of the script
+                let scopes = std::mem::take(&mut self.scopes);
+                let params = node
+                    .get_node("group")
+                    .and_then(|g| g.get_node_vec("declarations"))
+                    .into_iter()
+                    .flatten()
+                    .map(|d| {
+                        (
+                            d.literal().to_string(),
+                            d.get_node("type").and_then(BqType::from_type),
+                        )
+                    })
+                    .collect();
+                self.scopes.push(params);
+                if let Some(body) = node.get_node("stmt") {
+                    self.stmt(body, &mut true);
+                }
+                self.scopes = scopes;
+            }
+            _ => (),
+        }
+    }
+    /// `declarable` is true if the block can start with DECLARE statements.
+    fn block(&mut self, node: Option<&Node>, declarable: bool) {
+        let mut leading = declarable;
+        for stmt in node
+            .and_then(|n| n.get_node_vec("stmts"))
+            .into_iter()
+            .flatten()
+        {
+            self.stmt(stmt, &mut leading);
+        }
+    }
+    fn declare(&mut self, node: &Node) {
+        let declared_type = node.get_node("variable_type").and_then(BqType::from_type);
+        let default = node.get_node("default").and_then(|d| d.get_node("expr"));
+        // DEFAULT cannot refer to the variables being declared
+        self.refs(default);
+        let idents = node.get_node_vec("idents").map_or(&[][..], |i| i);
+        let default_type = default.and_then(|d| {
+            let variables: Vec<_> = self.scopes.iter().flatten().cloned().collect();
+            validate::expr_type(d, &variables)
+        });
+        if let (Some(declared), Some(actual), Some(default)) =
+            (&declared_type, &default_type, default)
+        {
+            // string literals are coerced to date and time types
+            let literal = default.node_type == NodeType::StringLiteral && declared.is_datetime();
+            if !literal && !actual.coercible_to(&declared.without_parameters()) {
+                let names: Vec<&str> = idents.iter().map(|i| i.literal()).collect();
+                self.report(
+                    DiagnosticKind::TypeMismatch,
+                    format!(
+                        "Value of type {} cannot be assigned to {}, which has type {}.",
+                        actual,
+                        names.join(", "),
+                        declared
+                    ),
+                    default,
+                );
+            }
+        }
+        let type_ = declared_type.or(default_type);
+        for ident in idents {
+            let name = ident.literal();
+            if self.visible(name) {
+                self.report(
+                    DiagnosticKind::DuplicateVariable,
+                    format!("Variable {} is already declared.", name),
+                    ident,
+                );
+            }
+            if let Some(scope) = self.scopes.last_mut() {
+                scope.push((name.to_string(), type_.clone()));
+            }
+        }
+    }
+    fn set(&mut self, node: &Node) {
+        let Some(expr) = node.get_node("expr") else {
+            return;
+        };
+        let (Some(left), right) = (expr.get_node("left"), expr.get_node("right")) else {
+            return;
+        };
+        self.refs(right);
+        match left.node_type {
+            // SET (a, b) = (1, 2)
+            NodeType::StructLiteral => {
+                let targets = left.get_node_vec("exprs").map_or(&[][..], |e| e);
+                for target in targets {
+                    self.target(target);
+                }
+                let Some(right) = right else {
+                    return;
+                };
+                if let Some(values) = value_count(right) {
+                    if values != targets.len() {
+                        self.report(
+                            DiagnosticKind::WrongValueCount,
+                            format!(
+                                "The number of variables ({}) does not match the number of values ({}).",
+                                targets.len(),
+                                values
+                            ),
+                            right,
+                        );
+                    }
+                }
+            }
+            _ => self.target(left),
+        }
+    }
+    // the variable which is assigned
+    fn target(&mut self, node: &Node) {
+        // system variables (e.g. @@dataset_id) are not declared
+        if !matches!(
+            node.node_type,
+            NodeType::Identifier | NodeType::MultiTokenIdentifier
+        ) {
+            return;
+        }
+        let name = node.literal();
+        if self.visible(name) {
+            return;
+        }
+        let message = if self.declared.iter().any(|d| d.eq_ignore_ascii_case(name)) {
+            format!("Variable {} is used before its declaration.", name)
+        } else {
+            format!("Variable {} is not declared.", name)
+        };
+        self.report(DiagnosticKind::UndeclaredVariable, message, node);
+    }
+    // the variables referred to in the expression or the query
+    fn refs(&mut self, node: Option<&Node>) {
+        let Some(node) = node else {
+            return;
+        };
+        match node.node_type {
+            NodeType::Identifier | NodeType::MultiTokenIdentifier => {
+                let name = node.literal();
+                // other names may be columns or functions without parentheses
+                if !self.visible(name) && self.declared.iter().any(|d| d.eq_ignore_ascii_case(name))
+                {
+                    self.report(
+                        DiagnosticKind::UndeclaredVariable,
+                        format!("Variable {} is used before its declaration.", name),
+                        node,
+                    );
+                }
+                return;
+            }
+            // x.field
+            NodeType::DotOperator => return self.refs(node.get_node("left")),
+            NodeType::Type => return,
+            _ => (),
+        }
+        for (key, child) in node.child_nodes() {
+            match key {
+                "func" | "alias" | "date_part" | "to_date_part" => continue,
+                "from" => self.table_refs(child.get_node("expr")),
+                _ => self.refs(Some(child)),
+            }
+        }
+    }
+    // the variables referred to in FROM clause (table names are not variables)
+    fn table_refs(&mut self, node: Option<&Node>) {
+        let Some(node) = node else {
+            return;
+        };
+        match node.node_type {
+            NodeType::Identifier | NodeType::DotOperator | NodeType::MultiTokenIdentifier => {
+                // e.g. FOR SYSTEM_TIME AS OF expr
+                for (key, child) in node.child_nodes() {
+                    match key {
+                        "left" | "right" | "trailing_idents" | "alias" => continue,
+                        _ => self.refs(Some(child)),
+                    }
+                }
+            }
+            NodeType::JoinOperator => {
+                self.table_refs(node.get_node("left"));
+                self.table_refs(node.get_node("right"));
+                self.refs(node.get_node("on"));
+            }
+            NodeType::GroupedExpr => self.table_refs(node.get_node("expr")),
+            _ => self.refs(Some(node)),
+        }
+    }
+}
+
+// the number of values of `(1, 2)` or `(SELECT AS STRUCT 1, 2)`
+fn value_count(node: &Node) -> Option<usize> {
+    match node.node_type {
+        NodeType::StructLiteral => node.get_node_vec("exprs").map(|e| e.len()),
+        NodeType::GroupedStatement => {
+            let select = node.get_node("stmt")?;
+            if select.node_type != NodeType::SelectStatement
+                || select.get_node_vec("as_struct_or_value").is_none()
+            {
+                return None;
+            }
+            let exprs = select.get_node_vec("exprs")?;
+            // `*` expands to an unknown number of columns
+            let star = exprs.iter().any(|e| {
+                e.node_type == NodeType::Asterisk
+                    || e.get_node("right")
+                        .is_some_and(|r| r.node_type == NodeType::Asterisk)
+            });
+            if star {
+                None
+            } else {
+                Some(exprs.len())
+            }
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,311 @@
+use super::*;
+use crate::validate::tests::{diagnostic_tuples, parse};
+
+fn diagnostics(code: &str) -> Vec<(DiagnosticKind, String, usize, usize)> {
+    diagnostic_tuples(check_variables(&parse(code)))
+}
+
+#[test]
+fn test_valid_variables() {
+    let codes = vec![
+        "\
+DECLARE x INT64 DEFAULT 1;
+DECLARE y, z STRING;
+SET x = x + 1;
+SET (y, z) = ('a', 'b');
+SET (y, z) = (SELECT AS STRUCT 'a', 'b');
+SET (y, z) = (SELECT AS STRUCT * FROM t);
+SET @@dataset_id = 'ds';
+",
+        "DECLARE d DATE DEFAULT '2020-01-01';",
+        "DECLARE n NUMERIC DEFAULT 1;",
+        "DECLARE s STRING(10) DEFAULT 'abc';",
+        "DECLARE a ARRAY<INT64> DEFAULT [1, 2];",
+        "DECLARE x INT64; DECLARE y INT64 DEFAULT x * 2;",
+        // columns are not variables
+        "SELECT x FROM t; SET @@x = 1;",
+        "\
+BEGIN
+  DECLARE x INT64;
+  SET x = 1;
+END;
+BEGIN
+  DECLARE x STRING;
+  SET x = 'a';
+END;
+",
+        "\
+DECLARE x INT64;
+FOR r IN (SELECT 1 AS a) DO
+  SET x = r.a;
+END FOR;
+",
+        "\
+CREATE PROCEDURE ds.p(a INT64, OUT b INT64)
+BEGIN
+  DECLARE c INT64 DEFAULT a;
+  SET b = c;
+END;
+",
+        "\
+DECLARE x INT64;
+EXECUTE IMMEDIATE 'SELECT ?' INTO x USING 1;
+",
+        // the type of columns is unknown
+        "DECLARE x INT64 DEFAULT (SELECT c FROM t);",
+    ];
+    for code in codes {
+        assert_eq!(diagnostics(code), vec![], "{}", code);
+    }
+}
+
+#[test]
+fn test_misplaced_declaration() {
+    assert_eq!(
+        diagnostics(
+            "\
+SELECT 1;
+DECLARE x INT64;
+BEGIN
+  SELECT 1;
+  DECLARE y INT64;
+END;
+IF TRUE THEN
+  DECLARE z INT64;
+END IF;
+"
+        ),
+        vec![
+            (
+                DiagnosticKind::MisplacedDeclaration,
+                "DECLARE must be at the beginning of the script or a BEGIN block.".to_string(),
+                2,
+                1
+            ),
+            (
+                DiagnosticKind::MisplacedDeclaration,
+                "DECLARE must be at the beginning of the script or a BEGIN block.".to_string(),
+                5,
+                3
+            ),
+            (
+                DiagnosticKind::MisplacedDeclaration,
+                "DECLARE must be at the beginning of the script or a BEGIN block.".to_string(),
+                8,
+                3
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_undeclared_variable() {
+    assert_eq!(
+        diagnostics(
+            "\
+SET x = 1;
+SET (y, z) = (1, 2);
+BEGIN
+  DECLARE w INT64;
+END;
+SET w = 1;
+DECLARE y INT64;
+"
+        ),
+        vec![
+            (
+                DiagnosticKind::UndeclaredVariable,
+                "Variable x is not declared.".to_string(),
+                1,
+                5
+            ),
+            (
+                DiagnosticKind::UndeclaredVariable,
+                "Variable y is used before its declaration.".to_string(),
+                2,
+                6
+            ),
+            (
+                DiagnosticKind::UndeclaredVariable,
+                "Variable z is not declared.".to_string(),
+                2,
+                9
+            ),
+            (
+                DiagnosticKind::UndeclaredVariable,
+                "Variable w is used before its declaration.".to_string(),
+                6,
+                5
+            ),
+            (
+                DiagnosticKind::MisplacedDeclaration,
+                "DECLARE must be at the beginning of the script or a BEGIN block.".to_string(),
+                7,
+                1
+            ),
+        ]
+    );
+    assert_eq!(
+        diagnostics(
+            "\
+DECLARE a INT64 DEFAULT b;
+DECLARE b INT64;
+IF b > 0 THEN
+  SELECT 1;
+END IF;
+"
+        ),
+        vec![(
+            DiagnosticKind::UndeclaredVariable,
+            "Variable b is used before its declaration.".to_string(),
+            1,
+            25
+        )]
+    );
+    assert_eq!(
+        diagnostics(
+            "\
+SELECT y FROM t JOIN ds.y USING (id) WHERE EXISTS (SELECT 1 FROM UNNEST([y]));
+DECLARE y INT64;
+BEGIN
+  DECLARE z INT64;
+  SELECT z;
+END;
+SELECT z, t.z FROM t AS z;
+"
+        ),
+        vec![
+            (
+                DiagnosticKind::UndeclaredVariable,
+                "Variable y is used before its declaration.".to_string(),
+                1,
+                8
+            ),
+            (
+                DiagnosticKind::UndeclaredVariable,
+                "Variable y is used before its declaration.".to_string(),
+                1,
+                74
+            ),
+            (
+                DiagnosticKind::MisplacedDeclaration,
+                "DECLARE must be at the beginning of the script or a BEGIN block.".to_string(),
+                2,
+                1
+            ),
+            (
+                DiagnosticKind::UndeclaredVariable,
+                "Variable z is used before its declaration.".to_string(),
+                7,
+                8
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_duplicate_variable() {
+    assert_eq!(
+        diagnostics(
+            "\
+DECLARE x INT64;
+DECLARE x STRING;
+DECLARE y, y INT64;
+BEGIN
+  DECLARE x INT64;
+END;
+"
+        ),
+        vec![
+            (
+                DiagnosticKind::DuplicateVariable,
+                "Variable x is already declared.".to_string(),
+                2,
+                9
+            ),
+            (
+                DiagnosticKind::DuplicateVariable,
+                "Variable y is already declared.".to_string(),
+                3,
+                12
+            ),
+            (
+                DiagnosticKind::DuplicateVariable,
+                "Variable x is already declared.".to_string(),
+                5,
+                11
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_wrong_value_count() {
+    assert_eq!(
+        diagnostics(
+            "\
+DECLARE a, b INT64;
+SET (a, b) = (1, 2, 3);
+SET (a, b) = (SELECT AS STRUCT 1);
+"
+        ),
+        vec![
+            (
+                DiagnosticKind::WrongValueCount,
+                "The number of variables (2) does not match the number of values (3).".to_string(),
+                2,
+                14
+            ),
+            (
+                DiagnosticKind::WrongValueCount,
+                "The number of variables (2) does not match the number of values (1).".to_string(),
+                3,
+                14
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_default_type() {
+    assert_eq!(
+        diagnostics(
+            "\
+DECLARE x INT64 DEFAULT 'a';
+DECLARE y, z BOOL DEFAULT 1.5;
+DECLARE s STRING DEFAULT x;
+"
+        ),
+        vec![
+            (
+                DiagnosticKind::TypeMismatch,
+                "Value of type STRING cannot be assigned to x, which has type INT64.".to_string(),
+                1,
+                25
+            ),
+            (
+                DiagnosticKind::TypeMismatch,
+                "Value of type FLOAT64 cannot be assigned to y, z, which has type BOOL."
+                    .to_string(),
+                2,
+                27
+            ),
+            (
+                DiagnosticKind::TypeMismatch,
+                "Value of type INT64 cannot be assigned to s, which has type STRING.".to_string(),
+                3,
+                26
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_span() {
+    let stmts = parse("DECLARE x INT64 DEFAULT\n  'abc';");
+    let spans: Vec<(usize, usize, usize, usize)> = check_variables(&stmts)
+        .into_iter()
+        .map(|d| (d.line, d.column, d.end_line, d.end_column))
+        .collect();
+    assert_eq!(spans, vec![(2, 3, 2, 8)]);
+}
@@ -4,10 +4,46 @@
 #![cfg(target_arch = "wasm32")]
 
 use bq2cst;
+use serde::Serialize;
+use wasm_bindgen::JsValue;
 use wasm_bindgen_test::*;
 
 #[wasm_bindgen_test]
 fn pass() {
     bq2cst::parse("select 1;".to_string()).expect("Failed to parse code.");
+    bq2cst::parse_sqlx("config {} select 1;".to_string()).expect("Failed to parse sqlx.");
+    bq2cst::parse_with_dynamic_sql("execute immediate 'select 1';".to_string())
+        .expect("Failed to parse dynamic sql.");
+    bq2cst::parse_jinja_branches("select {% if x %}1{% else %}2{% endif %};".to_string())
+        .expect("Failed to parse jinja branches.");
     bq2cst::tokenize("select 1;".to_string()).expect("Failed to tokenize code.");
+    bq2cst::extract_docs("select 1;".to_string()).expect("Failed to extract docs.");
+    bq2cst::get_suppressions("select 1;".to_string()).expect("Failed to get suppressions.");
+    bq2cst::collect_parameters("select @x;".to_string()).expect("Failed to collect parameters.");
+    bq2cst::analyze_privacy("select 1;".to_string()).expect("Failed to analyze privacy.");
+    bq2cst::extract_functions("create function f() as (1);".to_string())
+        .expect("Failed to extract functions.");
+    bq2cst::build_catalog("create table t (x int64);".to_string())
+        .expect("Failed to build catalog.");
+    bq2cst::validate_columns("select x from t;".to_string(), JsValue::UNDEFINED)
+        .expect("Failed to validate columns.");
+    bq2cst::infer_types("select 1 + 1;".to_string(), JsValue::UNDEFINED)
+        .expect("Failed to infer types.");
+    bq2cst::check_functions("select upper('a');".to_string()).expect("Failed to check functions.");
+    bq2cst::check_arguments("select regexp_contains('a', 'a');".to_string())
+        .expect("Failed to check arguments.");
+    bq2cst::check_typed_literals("select date '2024-01-01';".to_string())
+        .expect("Failed to check typed literals.");
+    bq2cst::analyze_control_flow("loop break; end loop;".to_string())
+        .expect("Failed to analyze control flow.");
+    bq2cst::check_variables("DECLARE x INT64; SET x = 1;".to_string())
+        .expect("Failed to check variables.");
+    let files = std::collections::BTreeMap::from([("a.sql", "SELECT 1;")])
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .expect("Failed to convert files.");
+    bq2cst::build_dependency_graph(files).expect("Failed to build dependency graph.");
+    bq2cst::fingerprint_query("SELECT 1;".to_string()).expect("Failed to fingerprint query.");
+    bq2cst::expand_macros("DEFINE MACRO x 1; select $x;".to_string())
+        .expect("Failed to expand macros.");
+    bq2cst::decode_literal("'\\n'".to_string()).expect("Failed to decode literal.");
 }